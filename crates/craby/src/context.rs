@@ -1,3 +1,11 @@
+use std::sync::Arc;
+
+use crate::{
+    invoker::Invoker,
+    storage::Storage,
+    task::{CancellationToken, TaskGroup, TaskHandle, TeardownPolicy},
+};
+
 /// The context of the Craby Module.
 pub struct Context {
     /// This is a unique identifier(pointer address) for the current TurboModule instance.
@@ -9,6 +17,8 @@ pub struct Context {
     ///
     /// **WARNING**: Only access files within this directory, do not write to other directories.
     pub data_path: String,
+    invoker: Invoker,
+    tasks: Arc<TaskGroup>,
 }
 
 impl Context {
@@ -16,6 +26,68 @@ impl Context {
         Context {
             id,
             data_path: data_path.to_string(),
+            invoker: Invoker::detached(id),
+            tasks: TaskGroup::register(id),
+        }
+    }
+
+    /// Creates a context whose tasks are not tied to the TurboModule instance.
+    ///
+    /// Used where the module is not created (eg. computing the constants),
+    /// so the tasks of the module instance are not replaced.
+    /// The tasks spawned by this context are not cancelled when the TurboModule is invalidated.
+    pub fn detached(id: usize, data_path: &str) -> Self {
+        Context {
+            id,
+            data_path: data_path.to_string(),
+            invoker: Invoker::detached(id),
+            tasks: TaskGroup::detached(),
         }
     }
+
+    /// Binds the `react::CallInvoker` of the TurboModule instance.
+    pub fn with_invoker(mut self, invoker: Invoker) -> Self {
+        self.invoker = invoker;
+        self
+    }
+
+    /// Returns the handle to schedule tasks on the JS thread.
+    pub fn invoker(&self) -> Invoker {
+        self.invoker
+    }
+
+    /// Opens the persistent key-value storage of the namespace.
+    ///
+    /// The storage files are stored under the `data_path`.
+    pub fn storage(&self, namespace: &str) -> Result<Storage, anyhow::Error> {
+        Storage::open(&self.data_path, namespace)
+    }
+
+    /// Runs the task on the shared worker threads.
+    ///
+    /// The task receives a token that is cancelled when the TurboModule is invalidated.
+    /// Use `spawn_blocking` for tasks that block for a long time (eg. network or file I/O).
+    pub fn spawn<F, T>(&self, f: F) -> TaskHandle<T>
+    where
+        F: FnOnce(CancellationToken) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.tasks.spawn(f, false)
+    }
+
+    /// Runs the task on a dedicated thread.
+    ///
+    /// The task receives a token that is cancelled when the TurboModule is invalidated.
+    pub fn spawn_blocking<F, T>(&self, f: F) -> TaskHandle<T>
+    where
+        F: FnOnce(CancellationToken) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.tasks.spawn(f, true)
+    }
+
+    /// Sets how the spawned tasks are handled when the TurboModule is invalidated.
+    pub fn set_teardown_policy(&self, policy: TeardownPolicy) {
+        self.tasks.set_policy(policy);
+    }
 }
@@ -0,0 +1,107 @@
+/// A task that runs on the JS thread.
+pub type Task = Box<dyn FnOnce() + Send + 'static>;
+
+/// Dispatches the task to the JS thread of the TurboModule instance.
+///
+/// Returns `false` if the task could not be scheduled (eg. the module is invalidated).
+pub type Dispatch = fn(id: usize, task: Task) -> bool;
+
+/// Handle of the TurboModule's `react::CallInvoker`.
+///
+/// Tasks posted through the invoker run on the JS thread in the order they were posted,
+/// after the current JS task (eg. the current method call) returns.
+///
+/// ```rust,ignore
+/// let invoker = self.ctx.invoker();
+///
+/// invoker.invoke_async(move || {
+///     // Runs on the JS thread
+/// });
+/// ```
+#[derive(Clone, Copy)]
+pub struct Invoker {
+    id: usize,
+    dispatch: Option<Dispatch>,
+}
+
+impl Invoker {
+    pub fn new(id: usize, dispatch: Dispatch) -> Self {
+        Invoker {
+            id,
+            dispatch: Some(dispatch),
+        }
+    }
+
+    /// Creates an invoker that is not bound to any TurboModule.
+    ///
+    /// Every task posted to this invoker is dropped without running.
+    pub fn detached(id: usize) -> Self {
+        Invoker { id, dispatch: None }
+    }
+
+    /// Posts the task to the JS thread.
+    ///
+    /// Returns `false` if the task was dropped without being scheduled.
+    pub fn invoke_async<F>(&self, task: F) -> bool
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        match self.dispatch {
+            Some(dispatch) => dispatch(self.id, Box::new(task)),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    };
+
+    use super::*;
+
+    static DISPATCHED_ID: AtomicUsize = AtomicUsize::new(0);
+
+    /// Runs the task immediately (the delegate of the module is attached).
+    fn run_task(id: usize, task: Task) -> bool {
+        DISPATCHED_ID.store(id, Ordering::SeqCst);
+        task();
+        true
+    }
+
+    /// Drops the task (the delegate of the module is not attached).
+    fn drop_task(_: usize, _: Task) -> bool {
+        false
+    }
+
+    #[test]
+    fn test_invoke_async() {
+        let (tx, rx) = mpsc::channel();
+        let invoker = Invoker::new(7, run_task);
+
+        assert!(invoker.invoke_async(move || tx.send("done").unwrap()));
+        assert_eq!(rx.try_recv(), Ok("done"));
+        assert_eq!(DISPATCHED_ID.load(Ordering::SeqCst), 7);
+    }
+
+    #[test]
+    fn test_invoke_async_without_delegate() {
+        let (tx, rx) = mpsc::channel();
+        let invoker = Invoker::new(1, drop_task);
+
+        assert!(!invoker.invoke_async(move || tx.send(()).unwrap()));
+        // Dropped without running
+        assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn test_detached_invoker() {
+        let (tx, rx) = mpsc::channel();
+        let invoker = Invoker::detached(1);
+
+        assert!(!invoker.invoke_async(move || tx.send(()).unwrap()));
+        assert_eq!(rx.try_recv(), Err(mpsc::TryRecvError::Disconnected));
+    }
+}
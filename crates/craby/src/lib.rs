@@ -4,12 +4,21 @@ pub mod macros;
 /// This module provides the prelude for Craby Modules.
 pub mod prelude {
     pub use crate::context::*;
+    pub use crate::invoker::Invoker;
+    pub use crate::storage::Storage;
+    pub use crate::task::{
+        CancellationToken, TaskHandle, TeardownPolicy, DEFAULT_TEARDOWN_TIMEOUT,
+    };
     pub use crate::types::*;
     pub use craby_macro::craby_module;
 }
 
 pub mod context;
+pub mod invoker;
+pub mod storage;
+pub mod task;
 pub mod types;
 
 // craby_marco crate
 pub use craby_macro;
+pub use craby_macro::method;
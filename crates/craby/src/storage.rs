@@ -0,0 +1,333 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock, Weak},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// Directory name of the storage files under the application's data path.
+const STORAGE_DIR: &str = "craby-storage";
+
+/// Raw entries of the storage.
+pub type Entries = BTreeMap<String, Value>;
+
+#[derive(Default, Serialize, Deserialize)]
+struct Store {
+    version: u32,
+    entries: Entries,
+}
+
+/// Opened stores in the current process.
+///
+/// Each namespace file is shared between all handles in the process,
+/// so multiple modules can open the same namespace without coordination.
+fn registry() -> &'static Mutex<HashMap<PathBuf, Weak<Mutex<Store>>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<PathBuf, Weak<Mutex<Store>>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Namespaced persistent key-value store.
+///
+/// Values are serialized as JSON and written atomically
+/// (`<namespace>.json.tmp` is written first and then renamed to `<namespace>.json`).
+///
+/// ```rust,ignore
+/// let storage = self.ctx.storage("settings")?;
+///
+/// storage.set("theme", &"dark")?;
+/// let theme = storage.get::<String>("theme")?; // Some("dark")
+/// ```
+#[derive(Clone)]
+pub struct Storage {
+    path: PathBuf,
+    store: Arc<Mutex<Store>>,
+}
+
+impl Storage {
+    /// Opens the storage of the namespace under the given data path.
+    ///
+    /// The namespace allows only alphanumeric characters, `_`, `-` and `.`.
+    pub fn open(data_path: impl AsRef<Path>, namespace: &str) -> Result<Self, anyhow::Error> {
+        if !is_valid_namespace(namespace) {
+            anyhow::bail!("Invalid storage namespace: {}", namespace);
+        }
+
+        let dir = data_path.as_ref().join(STORAGE_DIR);
+        let path = dir.join(format!("{namespace}.json"));
+        let mut registry = registry().lock().unwrap();
+
+        if let Some(store) = registry.get(&path).and_then(|store| store.upgrade()) {
+            return Ok(Storage { path, store });
+        }
+
+        fs::create_dir_all(&dir)?;
+        let store = if path.try_exists()? {
+            let raw = fs::read_to_string(&path)?;
+            serde_json::from_str::<Store>(&raw)
+                .map_err(|e| anyhow::anyhow!("Corrupted storage ({}): {}", path.display(), e))?
+        } else {
+            Store::default()
+        };
+
+        let store = Arc::new(Mutex::new(store));
+        registry.retain(|_, store| store.strong_count() > 0);
+        registry.insert(path.clone(), Arc::downgrade(&store));
+
+        Ok(Storage { path, store })
+    }
+
+    /// Returns the value of the key.
+    ///
+    /// Returns `None` if the key does not exist.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, anyhow::Error> {
+        let store = self.store.lock().unwrap();
+
+        match store.entries.get(key) {
+            Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Sets the value of the key and persists the storage.
+    pub fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<(), anyhow::Error> {
+        let value = serde_json::to_value(value)?;
+        self.update(|entries| {
+            entries.insert(key.to_string(), value);
+            Ok(())
+        })
+    }
+
+    /// Removes the key and persists the storage.
+    ///
+    /// Returns `true` if the key existed.
+    pub fn remove(&self, key: &str) -> Result<bool, anyhow::Error> {
+        let mut removed = false;
+        self.update(|entries| {
+            removed = entries.remove(key).is_some();
+            Ok(())
+        })?;
+
+        Ok(removed)
+    }
+
+    /// Returns `true` if the key exists.
+    pub fn contains(&self, key: &str) -> bool {
+        self.store.lock().unwrap().entries.contains_key(key)
+    }
+
+    /// Returns all keys in the storage.
+    pub fn keys(&self) -> Vec<String> {
+        self.store.lock().unwrap().entries.keys().cloned().collect()
+    }
+
+    /// Removes all keys and persists the storage.
+    pub fn clear(&self) -> Result<(), anyhow::Error> {
+        self.update(|entries| {
+            entries.clear();
+            Ok(())
+        })
+    }
+
+    /// Returns the schema version of the storage.
+    ///
+    /// New storages start at version `0`.
+    pub fn version(&self) -> u32 {
+        self.store.lock().unwrap().version
+    }
+
+    /// Runs the migration if the storage version is lower than the given version.
+    ///
+    /// The new version is persisted only if the migration succeeds.
+    ///
+    /// ```rust,ignore
+    /// storage.migrate(1, |entries| {
+    ///     if let Some(theme) = entries.remove("color") {
+    ///         entries.insert("theme".to_string(), theme);
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub fn migrate<F>(&self, version: u32, migration: F) -> Result<(), anyhow::Error>
+    where
+        F: FnOnce(&mut Entries) -> Result<(), anyhow::Error>,
+    {
+        let mut store = self.store.lock().unwrap();
+
+        if store.version >= version {
+            return Ok(());
+        }
+
+        let mut entries = store.entries.clone();
+        migration(&mut entries)?;
+
+        let next = Store { version, entries };
+        write_atomic(&self.path, &next)?;
+        *store = next;
+
+        Ok(())
+    }
+
+    fn update<F>(&self, f: F) -> Result<(), anyhow::Error>
+    where
+        F: FnOnce(&mut Entries) -> Result<(), anyhow::Error>,
+    {
+        let mut store = self.store.lock().unwrap();
+        let mut entries = store.entries.clone();
+        f(&mut entries)?;
+
+        let next = Store {
+            version: store.version,
+            entries,
+        };
+        write_atomic(&self.path, &next)?;
+        *store = next;
+
+        Ok(())
+    }
+}
+
+fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty()
+        && !namespace.starts_with('.')
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Writes the store to a temporary file and renames it to the destination.
+///
+/// The rename is atomic, so the previous content remains intact if the process crashes while writing.
+fn write_atomic(path: &Path, store: &Store) -> Result<(), anyhow::Error> {
+    let tmp_path = path.with_extension("json.tmp");
+    let content = serde_json::to_vec(store)?;
+
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(&content)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Temporary data path of the test (removed on drop).
+    struct TempDataPath(PathBuf);
+
+    impl std::ops::Deref for TempDataPath {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl AsRef<Path> for TempDataPath {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDataPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn temp_data_path(name: &str) -> TempDataPath {
+        let path = std::env::temp_dir().join(format!(
+            "craby-storage-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        TempDataPath(path)
+    }
+
+    #[test]
+    fn test_get_set() {
+        let data_path = temp_data_path("get_set");
+        let storage = Storage::open(&data_path, "settings").unwrap();
+
+        assert_eq!(storage.get::<String>("theme").unwrap(), None);
+
+        storage.set("theme", "dark").unwrap();
+        storage.set("count", &3).unwrap();
+
+        assert_eq!(
+            storage.get::<String>("theme").unwrap(),
+            Some("dark".to_string())
+        );
+        assert_eq!(storage.get::<u32>("count").unwrap(), Some(3));
+        assert!(storage.remove("count").unwrap());
+        assert_eq!(storage.keys(), vec!["theme".to_string()]);
+    }
+
+    #[test]
+    fn test_persist_and_share() {
+        let data_path = temp_data_path("persist");
+
+        {
+            let storage = Storage::open(&data_path, "shared").unwrap();
+            let other = Storage::open(&data_path, "shared").unwrap();
+
+            storage.set("value", &1.5).unwrap();
+            assert_eq!(other.get::<f64>("value").unwrap(), Some(1.5));
+        }
+
+        let reopened = Storage::open(&data_path, "shared").unwrap();
+        assert_eq!(reopened.get::<f64>("value").unwrap(), Some(1.5));
+        assert!(!data_path.join(STORAGE_DIR).join("shared.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_migrate() {
+        let data_path = temp_data_path("migrate");
+        let storage = Storage::open(&data_path, "settings").unwrap();
+        storage.set("color", "dark").unwrap();
+
+        storage
+            .migrate(1, |entries| {
+                let color = entries.remove("color").unwrap();
+                entries.insert("theme".to_string(), color);
+                Ok(())
+            })
+            .unwrap();
+
+        // Already migrated
+        storage
+            .migrate(1, |_| anyhow::bail!("Should not be called"))
+            .unwrap();
+
+        // Failed migration keeps the previous state
+        assert!(storage
+            .migrate(2, |entries| {
+                entries.clear();
+                anyhow::bail!("Failed")
+            })
+            .is_err());
+
+        assert_eq!(storage.version(), 1);
+        assert_eq!(
+            storage.get::<String>("theme").unwrap(),
+            Some("dark".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_namespace() {
+        let data_path = temp_data_path("invalid");
+
+        assert!(Storage::open(&data_path, "").is_err());
+        assert!(Storage::open(&data_path, "../escape").is_err());
+        assert!(Storage::open(&data_path, ".hidden").is_err());
+    }
+}
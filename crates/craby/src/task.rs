@@ -0,0 +1,391 @@
+use std::{
+    collections::HashMap,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Condvar, Mutex, OnceLock, Weak,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Default time to wait for the running tasks when the TurboModule is invalidated ([`TeardownPolicy::Wait`]).
+pub const DEFAULT_TEARDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Token that is cancelled when the TurboModule is invalidated.
+///
+/// Long-running tasks should check the token periodically and return early once it is cancelled.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// Determines how the spawned tasks are handled when the TurboModule is invalidated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeardownPolicy {
+    /// Waits for all running tasks to finish, up to the given timeout.
+    ///
+    /// The module is invalidated on the JS thread, so the JS thread is blocked while waiting.
+    /// The tasks waiting on the JS thread cannot finish until the timeout expires.
+    Wait(Duration),
+    /// Leaves the running tasks without waiting.
+    Detach,
+}
+
+impl Default for TeardownPolicy {
+    /// Waits up to [`DEFAULT_TEARDOWN_TIMEOUT`].
+    fn default() -> Self {
+        TeardownPolicy::Wait(DEFAULT_TEARDOWN_TIMEOUT)
+    }
+}
+
+/// Handle of the spawned task.
+pub struct TaskHandle<T> {
+    rx: mpsc::Receiver<thread::Result<T>>,
+}
+
+impl<T> TaskHandle<T> {
+    /// Waits for the task to finish and returns its result.
+    ///
+    /// Returns an error if the task panicked or was not run because the module is invalidated.
+    pub fn join(self) -> Result<T, anyhow::Error> {
+        match self.rx.recv() {
+            Ok(Ok(val)) => Ok(val),
+            Ok(Err(e)) => Err(anyhow::anyhow!(panic_message(e))),
+            Err(_) => Err(anyhow::anyhow!("Task was cancelled")),
+        }
+    }
+}
+
+/// Tasks spawned by the TurboModule instance.
+pub(crate) struct TaskGroup {
+    token: CancellationToken,
+    policy: Mutex<TeardownPolicy>,
+    state: Mutex<TaskState>,
+    idle: Condvar,
+}
+
+/// Updated under one lock, so no task is accepted after the group is cancelled.
+#[derive(Default)]
+struct TaskState {
+    cancelled: bool,
+    pending: usize,
+}
+
+impl TaskGroup {
+    /// Creates a task group that is not invalidated with the module.
+    pub(crate) fn detached() -> Arc<TaskGroup> {
+        Arc::new(TaskGroup {
+            token: CancellationToken::default(),
+            policy: Mutex::new(TeardownPolicy::default()),
+            state: Mutex::new(TaskState::default()),
+            idle: Condvar::new(),
+        })
+    }
+
+    /// Creates a task group and registers it to be invalidated by the module id.
+    pub(crate) fn register(id: usize) -> Arc<TaskGroup> {
+        let group = TaskGroup::detached();
+
+        let mut registry = registry().lock().unwrap();
+        registry.retain(|_, group| group.strong_count() > 0);
+        registry.insert(id, Arc::downgrade(&group));
+
+        group
+    }
+
+    pub(crate) fn set_policy(&self, policy: TeardownPolicy) {
+        *self.policy.lock().unwrap() = policy;
+    }
+
+    pub(crate) fn spawn<F, T>(self: &Arc<Self>, f: F, dedicated: bool) -> TaskHandle<T>
+    where
+        F: FnOnce(CancellationToken) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.cancelled {
+                return TaskHandle { rx };
+            }
+            state.pending += 1;
+        }
+
+        let group = self.clone();
+        let job_tx = tx.clone();
+        let job: Job = Box::new(move || {
+            let token = group.token.clone();
+            let _ = job_tx.send(catch_unwind(AssertUnwindSafe(|| f(token))));
+            group.finish();
+        });
+
+        if dedicated {
+            let spawned = thread::Builder::new()
+                .name("craby-task".to_string())
+                .spawn(job);
+
+            // The job is dropped without running
+            if let Err(e) = spawned {
+                let _ = tx.send(Err(Box::new(format!("Failed to spawn thread: {e}"))));
+                self.finish();
+            }
+        } else {
+            pool().execute(job);
+        }
+
+        TaskHandle { rx }
+    }
+
+    fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.pending -= 1;
+
+        if state.pending == 0 {
+            self.idle.notify_all();
+        }
+    }
+
+    fn invalidate(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.cancelled = true;
+        self.token.cancel();
+
+        if let TeardownPolicy::Wait(timeout) = *self.policy.lock().unwrap() {
+            drop(
+                self.idle
+                    .wait_timeout_while(state, timeout, |state| state.pending > 0),
+            );
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<usize, Weak<TaskGroup>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<usize, Weak<TaskGroup>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Cancels the tasks spawned by the TurboModule instance and tears them down by its policy.
+///
+/// Called by the generated code when the TurboModule is invalidated.
+/// Blocks the calling (JS) thread while waiting for the tasks ([`TeardownPolicy::Wait`]).
+pub fn invalidate(id: usize) {
+    let group = registry()
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .and_then(|group| group.upgrade());
+
+    if let Some(group) = group {
+        group.invalidate();
+    }
+}
+
+/// Shared worker threads for `Context::spawn`.
+struct Pool {
+    tx: Mutex<mpsc::Sender<Job>>,
+}
+
+impl Pool {
+    fn new(size: usize) -> Self {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        for idx in 0..size {
+            let rx = rx.clone();
+            thread::Builder::new()
+                .name(format!("craby-worker-{idx}"))
+                .spawn(move || loop {
+                    let job = match rx.lock().unwrap().recv() {
+                        Ok(job) => job,
+                        Err(_) => return,
+                    };
+                    job();
+                })
+                .expect("Failed to spawn worker thread");
+        }
+
+        Pool { tx: Mutex::new(tx) }
+    }
+
+    fn execute(&self, job: Job) {
+        self.tx
+            .lock()
+            .unwrap()
+            .send(job)
+            .expect("Worker threads are terminated");
+    }
+}
+
+fn pool() -> &'static Pool {
+    static POOL: OnceLock<Pool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let size = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        Pool::new(size)
+    })
+}
+
+fn panic_message(e: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic occurred".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::AtomicUsize,
+        time::{Duration, Instant},
+    };
+
+    use crate::context::Context;
+
+    use super::*;
+
+    #[test]
+    fn test_spawn() {
+        let ctx = Context::new(1, "");
+        let handle = ctx.spawn(|_| 1 + 1);
+        let blocking_handle = ctx.spawn_blocking(|_| "done");
+
+        assert_eq!(handle.join().unwrap(), 2);
+        assert_eq!(blocking_handle.join().unwrap(), "done");
+        assert!(ctx.spawn(|_| panic!("Oops")).join().is_err());
+    }
+
+    #[test]
+    fn test_invalidate_wait() {
+        let ctx = Context::new(2, "");
+        let finished = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..3 {
+            let finished = finished.clone();
+            ctx.spawn_blocking(move |token| {
+                while !token.is_cancelled() {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                finished.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        invalidate(2);
+
+        assert_eq!(finished.load(Ordering::SeqCst), 3);
+        assert!(ctx.spawn(|_| ()).join().is_err());
+    }
+
+    #[test]
+    fn test_spawn_while_invalidating() {
+        for id in 1000..1050 {
+            let group = TaskGroup::register(id);
+            let invalidated = Arc::new(AtomicBool::new(false));
+            let run_after_invalidated = Arc::new(AtomicBool::new(false));
+
+            // Spawns the tasks until the module is invalidated
+            let spawners = (0..4)
+                .map(|_| {
+                    let group = group.clone();
+                    let invalidated = invalidated.clone();
+                    let run_after_invalidated = run_after_invalidated.clone();
+                    thread::spawn(move || {
+                        while !invalidated.load(Ordering::SeqCst) {
+                            let invalidated = invalidated.clone();
+                            let run_after_invalidated = run_after_invalidated.clone();
+                            let task = move |_| {
+                                if invalidated.load(Ordering::SeqCst) {
+                                    run_after_invalidated.store(true, Ordering::SeqCst);
+                                }
+                            };
+                            group.spawn(task, false);
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            thread::sleep(Duration::from_micros(100));
+            invalidate(id);
+            invalidated.store(true, Ordering::SeqCst);
+
+            for spawner in spawners {
+                spawner.join().unwrap();
+            }
+
+            // Tasks scheduled after the invalidation (if any) run on the pool
+            thread::sleep(Duration::from_millis(1));
+            assert!(!run_after_invalidated.load(Ordering::SeqCst));
+        }
+    }
+
+    #[test]
+    fn test_invalidate_timeout() {
+        let ctx = Context::new(4, "");
+        let (tx, rx) = mpsc::channel::<()>();
+        ctx.set_teardown_policy(TeardownPolicy::Wait(Duration::from_millis(10)));
+
+        // Never finishes until the sender is dropped
+        let handle = ctx.spawn_blocking(move |_| {
+            let _ = rx.recv();
+        });
+
+        let started = Instant::now();
+        invalidate(4);
+        assert!(started.elapsed() < DEFAULT_TEARDOWN_TIMEOUT);
+
+        drop(tx);
+        assert!(handle.join().is_ok());
+    }
+
+    #[test]
+    fn test_detached_context() {
+        let ctx = Context::new(5, "");
+        let detached_ctx = Context::detached(5, "");
+
+        let handle = ctx.spawn_blocking(|token| {
+            while !token.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        let detached_handle = detached_ctx.spawn(|token| token.is_cancelled());
+
+        // The detached context does not replace the task group of the module
+        invalidate(5);
+
+        assert!(handle.join().is_ok());
+        assert!(!detached_handle.join().unwrap());
+        assert!(ctx.spawn(|_| ()).join().is_err());
+    }
+
+    #[test]
+    fn test_invalidate_detach() {
+        let ctx = Context::new(3, "");
+        let (tx, rx) = mpsc::channel();
+        ctx.set_teardown_policy(TeardownPolicy::Detach);
+
+        let handle = ctx.spawn_blocking(move |token| {
+            rx.recv().unwrap();
+            token.is_cancelled()
+        });
+
+        invalidate(3);
+        tx.send(()).unwrap();
+
+        assert!(handle.join().unwrap());
+    }
+}
@@ -6,6 +6,11 @@ pub type Array<T> = std::vec::Vec<T>;
 pub type Promise<T> = std::result::Result<T, anyhow::Error>;
 pub type Void = ();
 
+/// Rust object exposed to JS as a host object (eg. `NativeObject<dyn DecoderSpec>`).
+///
+/// The object is dropped when the JS GC collects the host object.
+pub type NativeObject<T> = Box<T>;
+
 /// JavaScript-like Promise utilities.
 pub mod promise {
     use super::Promise;
@@ -23,48 +28,74 @@ pub mod promise {
     }
 }
 
-/// JavaScript-like Nullable utilities.
-///
-/// Used to represent optional values.
-///
-/// ```typescript
-/// let value: number | null = null;
-/// let value: number | null = 123;
-/// ```
+/// Nullable type of the previous versions.
 ///
-pub struct Nullable<T> {
-    val: Option<T>,
-}
+/// Generated `*Spec` traits use `Option<T>` for `T | null`.
+/// Enable the `compat-nullable` feature to keep the existing `Nullable<T>` code compiling.
+#[cfg(feature = "compat-nullable")]
+pub type Nullable<T> = Option<T>;
 
-impl<T> Nullable<T> {
+/// `Nullable<T>` methods for `Option<T>`.
+#[cfg(feature = "compat-nullable")]
+pub trait NullableExt<T> {
     /// Creates a new `Nullable` with an optional value.
-    pub fn new(val: Option<T>) -> Self {
-        Nullable { val }
-    }
+    fn new(val: Option<T>) -> Self;
 
     /// Creates a new `Nullable` with a some value.
-    pub fn some(val: T) -> Self {
-        Nullable { val: Some(val) }
-    }
+    fn some(val: T) -> Self;
 
     /// Creates a new `Nullable` with a none value.
-    pub fn none() -> Self {
-        Nullable { val: None }
-    }
+    fn none() -> Self;
 
     /// Sets the value of the `Nullable`.
-    pub fn value(mut self, val: T) -> Self {
-        self.val = Some(val);
-        self
-    }
+    fn value(self, val: T) -> Self;
 
     /// Borrow the value reference of the `Nullable`.
-    pub fn value_of(&self) -> Option<&T> {
-        self.val.as_ref()
-    }
+    fn value_of(&self) -> Option<&T>;
 
     /// Takes the value out of the `Nullable`.
-    pub fn into_value(self) -> Option<T> {
-        self.val
+    fn into_value(self) -> Option<T>;
+}
+
+#[cfg(feature = "compat-nullable")]
+impl<T> NullableExt<T> for Option<T> {
+    fn new(val: Option<T>) -> Self {
+        val
+    }
+
+    fn some(val: T) -> Self {
+        Some(val)
+    }
+
+    fn none() -> Self {
+        None
+    }
+
+    fn value(self, val: T) -> Self {
+        Some(val)
+    }
+
+    fn value_of(&self) -> Option<&T> {
+        self.as_ref()
+    }
+
+    fn into_value(self) -> Option<T> {
+        self
+    }
+}
+
+#[cfg(all(test, feature = "compat-nullable"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullable_compat() {
+        let some_value = Nullable::<Number>::some(42.0);
+        let none_value = Nullable::<Number>::none();
+
+        assert_eq!(some_value.value_of(), Some(&42.0));
+        assert_eq!(none_value.value_of(), None);
+        assert_eq!(none_value.value(123.0), Some(123.0));
+        assert_eq!(Nullable::new(Some(1.0)).into_value(), Some(1.0));
     }
 }
@@ -1,13 +1,13 @@
 use std::path::PathBuf;
 
 use craby_build::platform::{android as android_build, ios as ios_build};
-use craby_codegen::codegen;
+use craby_codegen::{codegen, load_method_attrs, resolve_types, types::CodegenContext};
 use craby_common::{config::load_config, env::is_initialized};
 use log::{debug, info};
 use owo_colors::OwoColorize;
 
 use crate::{
-    commands::build::validate_schema,
+    commands::codegen::{ensure_generated_files, generate_files, Manifest},
     utils::{
         build_targets::{get_build_targets, print_build_targets},
         terminal::with_spinner,
@@ -31,17 +31,36 @@ pub fn perform(opts: BuildOptions) -> anyhow::Result<()> {
     }
 
     debug!(
-        "Collecting source files to validate schema(s)... ({})",
-        config.source_dir.display()
+        "Collecting source files to check generated files... ({})",
+        config.source_dirs_display()
     );
     let schemas = codegen(craby_codegen::CodegenOptions {
         project_root: &opts.project_root,
-        source_dir: &config.source_dir,
+        source_dirs: &config.source_dirs,
+        spec_patterns: &config.spec_patterns,
     })?;
+    let schemas = resolve_types(schemas, config.types.namespace)?;
     let total_schemas = schemas.len();
     debug!("{} module schema(s) found", total_schemas);
 
-    validate_schema(&opts.project_root, &schemas)?;
+    let method_attrs = load_method_attrs(&opts.project_root, &schemas)?;
+    let ctx = CodegenContext {
+        project_name: config.project.name.clone(),
+        root: opts.project_root.clone(),
+        schemas,
+        android_package_name: config.android.package_name.clone(),
+        method_attrs,
+    };
+
+    // The external generators only run with `craby codegen`
+    let mut manifest = Manifest::load(&opts.project_root)?;
+    let generate_res = generate_files(&ctx, &config, &mut manifest, false)?;
+    ensure_generated_files(
+        &opts.project_root,
+        &generate_res,
+        manifest.clang_format.as_deref(),
+        false,
+    )?;
 
     info!("Starting to build the Cargo project...");
     print_build_targets(&build_targets);
@@ -1,5 +1,3 @@
 pub use handler::*;
-pub use validate_schema::*;
 
 mod handler;
-mod validate_schema;
@@ -0,0 +1,377 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
+
+use craby_codegen::generators::types::TemplateResult;
+use craby_common::constants::craby_tmp_dir;
+use log::warn;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::utils::file::write_file;
+
+/// Content hashes of the generated files (`.craby/manifest.json`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Key: Path relative to the project root
+    pub files: BTreeMap<String, String>,
+    /// Content hashes of the generated files (key: hash of the path, the formatter settings and the rendered content)
+    ///
+    /// Unchanged outputs are not formatted again if the files on disk have the same content.
+    #[serde(default)]
+    pub outputs: BTreeMap<String, String>,
+    /// clang-format version that formatted the C++ files (`None` if `format.cxx` is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clang_format: Option<String>,
+    /// Files generated by the external generators (path relative to the project root)
+    ///
+    /// They are not checked if the external generators did not run (`craby build`).
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub external: BTreeSet<String>,
+}
+
+impl Manifest {
+    /// Records the content of the generated file that has been written (or is already up to date on disk).
+    ///
+    /// Files kept by `--no-overwrite` must not be recorded to keep the hash of the content written last time.
+    pub fn record(&mut self, project_root: &Path, res: &TemplateResult) -> anyhow::Result<()> {
+        self.files.insert(
+            relative_path(project_root, &res.path)?,
+            content_hash(&res.content),
+        );
+        Ok(())
+    }
+
+    /// Returns the content of the file on disk if it is the generated content of the output (see [`Manifest::outputs`]).
+    pub fn cached_output(&self, path: &Path, key: &str) -> Option<String> {
+        let hash = self.outputs.get(key)?;
+        let content = fs::read_to_string(path).ok()?;
+
+        (content_hash(&content) == *hash).then_some(content)
+    }
+
+    /// Forgets the files that are no longer generated.
+    pub fn retain_generated(&mut self, project_root: &Path, files: &[TemplateResult]) {
+        self.files
+            .retain(|path, _| is_generated(project_root, files, path));
+    }
+
+    /// Returns the empty manifest if the project has not been generated yet.
+    pub fn load(project_root: &Path) -> anyhow::Result<Self> {
+        let path = manifest_path(project_root);
+
+        if !path.try_exists()? {
+            return Ok(Manifest::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Returns the files of the manifest that are no longer generated but still exist (key: path relative to the project root).
+    pub fn stale_files(
+        &self,
+        project_root: &Path,
+        files: &[TemplateResult],
+    ) -> anyhow::Result<Vec<String>> {
+        let mut stale_files = vec![];
+
+        for path in self.files.keys() {
+            if !is_generated(project_root, files, path) && project_root.join(path).try_exists()? {
+                stale_files.push(path.clone());
+            }
+        }
+
+        Ok(stale_files)
+    }
+
+    pub fn save(&self, project_root: &Path) -> anyhow::Result<()> {
+        write_file(
+            &manifest_path(project_root),
+            &serde_json::to_string_pretty(self)?,
+            true,
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum Drift {
+    /// Generated file does not exist
+    Missing,
+    /// Generated file is different from the regenerated content
+    Outdated {
+        /// `true` if the file has been edited after the last codegen
+        edited: bool,
+        diff: String,
+    },
+    /// File is no longer generated but still exists
+    Stale,
+}
+
+#[derive(Debug)]
+pub struct FileDrift {
+    /// Path relative to the project root
+    pub path: String,
+    pub drift: Drift,
+}
+
+/// Compares the regenerated files with the files on disk.
+///
+/// Only the files owned by codegen are compared (implementation stubs are owned by the user).
+/// If the files were formatted by another clang-format version (`clang_format`: the current one),
+/// the C++ files are not compared since the formatted code depends on the version.
+/// Without `external` (the external generators did not run), their files are not reported as stale.
+pub fn check_generated_files(
+    project_root: &Path,
+    files: &[TemplateResult],
+    clang_format: Option<&str>,
+    external: bool,
+) -> anyhow::Result<Vec<FileDrift>> {
+    let manifest = Manifest::load(project_root)?;
+    let mut drifts = vec![];
+
+    let skip_cxx = match (&manifest.clang_format, clang_format) {
+        (Some(recorded), Some(current)) if recorded != current => {
+            warn!(
+                "C++ files were formatted by another clang-format version ({} → {}), skipping them",
+                recorded, current
+            );
+            true
+        }
+        _ => false,
+    };
+
+    for res in files.iter().filter(|res| res.overwrite) {
+        let path = relative_path(project_root, &res.path)?;
+
+        if !res.path.try_exists()? {
+            drifts.push(FileDrift {
+                path,
+                drift: Drift::Missing,
+            });
+            continue;
+        }
+
+        let content = fs::read_to_string(&res.path)?;
+        if content == res.content || (skip_cxx && is_cxx_file(&res.path)) {
+            continue;
+        }
+
+        let edited = manifest
+            .files
+            .get(&path)
+            .is_some_and(|hash| *hash != content_hash(&content));
+        let diff = TextDiff::from_lines(&content, &res.content)
+            .unified_diff()
+            .header(&format!("a/{}", path), &format!("b/{}", path))
+            .to_string();
+
+        drifts.push(FileDrift {
+            path,
+            drift: Drift::Outdated { edited, diff },
+        });
+    }
+
+    for path in manifest.stale_files(project_root, files)? {
+        if !external && manifest.external.contains(&path) {
+            continue;
+        }
+
+        drifts.push(FileDrift {
+            path,
+            drift: Drift::Stale,
+        });
+    }
+
+    Ok(drifts)
+}
+
+/// Bails if any generated file is out of date (the drifts are printed).
+pub fn ensure_generated_files(
+    project_root: &Path,
+    files: &[TemplateResult],
+    clang_format: Option<&str>,
+    external: bool,
+) -> anyhow::Result<()> {
+    let drifts = check_generated_files(project_root, files, clang_format, external)?;
+
+    if !drifts.is_empty() {
+        print_drifts(&drifts);
+        anyhow::bail!(
+            "{} generated file(s) are out of date. Please run `craby codegen` to update them.",
+            drifts.len()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn print_drifts(drifts: &[FileDrift]) {
+    for FileDrift { path, drift } in drifts {
+        match drift {
+            Drift::Missing => println!("{} {}", "missing".red(), path),
+            Drift::Stale => println!("{} {} (no longer generated)", "stale".red(), path),
+            Drift::Outdated { edited, diff } => {
+                if *edited {
+                    println!("{} {} (edited by hand)", "modified".red(), path);
+                } else {
+                    println!("{} {}", "outdated".red(), path);
+                }
+
+                for line in diff.lines() {
+                    if line.starts_with("+++") || line.starts_with("---") {
+                        println!("{}", line.bold());
+                    } else if line.starts_with('+') {
+                        println!("{}", line.green());
+                    } else if line.starts_with('-') {
+                        println!("{}", line.red());
+                    } else if line.starts_with("@@") {
+                        println!("{}", line.cyan());
+                    } else {
+                        println!("{}", line);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn manifest_path(project_root: &Path) -> PathBuf {
+    craby_tmp_dir(project_root).join("manifest.json")
+}
+
+pub(super) fn relative_path(project_root: &Path, path: &Path) -> anyhow::Result<String> {
+    Ok(path
+        .strip_prefix(project_root)?
+        .to_string_lossy()
+        .replace('\\', "/"))
+}
+
+fn is_cxx_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext, "cpp" | "hpp" | "h" | "mm"))
+}
+
+fn is_generated(project_root: &Path, files: &[TemplateResult], path: &str) -> bool {
+    files
+        .iter()
+        .any(|res| relative_path(project_root, &res.path).is_ok_and(|p| p == path))
+}
+
+pub(super) fn content_hash(content: &str) -> String {
+    let mut hasher = Xxh3::new();
+    hasher.write(content.as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::temp_dir::TempDir;
+
+    use super::*;
+
+    fn generated(project_root: &Path, path: &str, content: &str) -> TemplateResult {
+        TemplateResult {
+            path: project_root.join(path),
+            content: content.to_string(),
+            overwrite: true,
+        }
+    }
+
+    #[test]
+    fn test_check_generated_files() {
+        let project_root = TempDir::new("check-test");
+
+        let files = vec![
+            generated(&project_root, "cpp/A.cpp", "a\n"),
+            generated(&project_root, "cpp/B.cpp", "b\n"),
+            generated(&project_root, "cpp/C.cpp", "c\n"),
+        ];
+        let mut manifest = Manifest::default();
+        for res in &files {
+            write_file(&res.path, &res.content, true).unwrap();
+            manifest.record(&project_root, res).unwrap();
+        }
+        manifest.save(&project_root).unwrap();
+        assert!(check_generated_files(&project_root, &files, None, true)
+            .unwrap()
+            .is_empty());
+
+        // Hand edit, removed file and spec change (`C.cpp` is no longer generated)
+        fs::write(project_root.join("cpp/A.cpp"), "edited\n").unwrap();
+        fs::remove_file(project_root.join("cpp/B.cpp")).unwrap();
+        let files = vec![
+            generated(&project_root, "cpp/A.cpp", "a\n"),
+            generated(&project_root, "cpp/B.cpp", "b\n"),
+        ];
+
+        let drifts = check_generated_files(&project_root, &files, None, true).unwrap();
+        assert_eq!(drifts.len(), 3);
+        assert!(matches!(
+            &drifts[0],
+            FileDrift { path, drift: Drift::Outdated { edited: true, diff } }
+                if path == "cpp/A.cpp" && diff.contains("-edited\n+a\n")
+        ));
+        assert!(
+            matches!(&drifts[1], FileDrift { path, drift: Drift::Missing } if path == "cpp/B.cpp")
+        );
+        assert!(
+            matches!(&drifts[2], FileDrift { path, drift: Drift::Stale } if path == "cpp/C.cpp")
+        );
+
+        // Files of the external generators are not stale if the generators did not run
+        manifest.external.insert("cpp/C.cpp".to_string());
+        manifest.save(&project_root).unwrap();
+        assert_eq!(
+            check_generated_files(&project_root, &files, None, false)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_clang_format_version() {
+        let project_root = TempDir::new("check-clang-format-test");
+        let files = vec![
+            generated(&project_root, "cpp/A.cpp", "a\n"),
+            generated(&project_root, "src/lib.rs", "b\n"),
+        ];
+
+        let mut manifest = Manifest {
+            clang_format: Some("clang-format version 17.0.0".to_string()),
+            ..Default::default()
+        };
+        for res in &files {
+            write_file(&res.path, &"formatted\n".to_string(), true).unwrap();
+            manifest.record(&project_root, res).unwrap();
+        }
+        manifest.save(&project_root).unwrap();
+
+        // C++ files are not compared if they were formatted by another version
+        let drifts = check_generated_files(
+            &project_root,
+            &files,
+            Some("clang-format version 18.1.3"),
+            true,
+        )
+        .unwrap();
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].path, "src/lib.rs");
+
+        let drifts = check_generated_files(
+            &project_root,
+            &files,
+            Some("clang-format version 17.0.0"),
+            true,
+        )
+        .unwrap();
+        assert_eq!(drifts.len(), 2);
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::{Path, PathBuf},
+};
+
+use craby_codegen::{
+    constants::GENERATED_COMMENT,
+    formatter::Formatter,
+    generators::{
+        android_generator::AndroidGenerator,
+        cxx_generator::CxxGenerator,
+        external_generator::ExternalGenerator,
+        ios_generator::IosGenerator,
+        rs_generator::RsGenerator,
+        types::{GeneratorInvoker, TemplateResult},
+    },
+    types::CodegenContext,
+};
+use craby_common::config::CompleteConfig;
+
+use crate::commands::codegen::{content_hash, relative_path, Manifest};
+
+/// Renders all files of the generators (the contents are the same as the files written to disk).
+///
+/// With `external`, the external generators of `craby.toml` run after the built-in generators
+/// (the files they generate are recorded in the manifest), then the files are formatted.
+/// The outputs cached in the manifest are read from disk instead of being formatted again
+/// (the cache and the clang-format version of the manifest are updated).
+pub fn generate_files(
+    ctx: &CodegenContext,
+    config: &CompleteConfig,
+    manifest: &mut Manifest,
+    external: bool,
+) -> anyhow::Result<Vec<TemplateResult>> {
+    let mut generate_res = vec![];
+    let generators: Vec<Box<dyn GeneratorInvoker>> = vec![
+        Box::new(AndroidGenerator::new()),
+        Box::new(IosGenerator::new()),
+        Box::new(RsGenerator::new()),
+        Box::new(CxxGenerator::new()),
+    ];
+
+    for generator in generators {
+        generate_res.extend(generator.invoke_generate(ctx)?);
+    }
+
+    if external {
+        let mut paths = generate_res
+            .iter()
+            .map(|res| res.path.clone())
+            .collect::<HashSet<_>>();
+        let mut external_files = BTreeSet::new();
+
+        for generator_config in &config.generators {
+            let files = ExternalGenerator::new(generator_config.clone()).invoke_generate(ctx)?;
+            check_external_paths(&generator_config.name, &mut paths, &files)?;
+
+            for res in &files {
+                external_files.insert(relative_path(&config.project_root, &res.path)?);
+            }
+            generate_res.extend(files);
+        }
+
+        manifest.external = external_files;
+    }
+
+    let formatter = Formatter::new(&config.project_root, &config.format);
+    let fingerprint = formatter.fingerprint();
+    let mut outputs = BTreeMap::new();
+    let generate_res = generate_res
+        .into_iter()
+        .map(|res| {
+            let key = content_hash(&format!(
+                "{}\n{}\n{}\n{}",
+                res.path.display(),
+                res.overwrite,
+                fingerprint,
+                res.content
+            ));
+
+            let content = match manifest.cached_output(&res.path, &key) {
+                Some(content) => content,
+                None => {
+                    let content = formatter.format(&res.path, &res.content)?;
+                    if res.overwrite {
+                        with_generated_comment(&res.path, &content)
+                    } else {
+                        without_generated_comment(&content)
+                    }
+                }
+            };
+
+            outputs.insert(key, content_hash(&content));
+            Ok(TemplateResult { content, ..res })
+        })
+        .collect::<anyhow::Result<_>>()?;
+    manifest.outputs = outputs;
+    manifest.clang_format = formatter.clang_format_version().map(str::to_string);
+
+    Ok(generate_res)
+}
+
+/// Bails if the external generator writes the file that is already generated (by Craby or another generator).
+fn check_external_paths(
+    name: &str,
+    paths: &mut HashSet<PathBuf>,
+    files: &[TemplateResult],
+) -> anyhow::Result<()> {
+    for res in files {
+        if !paths.insert(res.path.clone()) {
+            anyhow::bail!(
+                "Generator `{}` cannot write {} (already generated by another generator)",
+                name,
+                res.path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn with_generated_comment(path: &Path, code: &str) -> String {
+    match path.extension() {
+        Some(ext) => match ext.to_str().unwrap() {
+            // Source files
+            "rs" | "cpp" | "hpp" | "mm" => format!("// {}\n{}\n", GENERATED_COMMENT, code),
+            // CMakeLists.txt
+            "txt" => format!("# {}\n{}\n", GENERATED_COMMENT, code),
+            _ => without_generated_comment(code),
+        },
+        None => without_generated_comment(code),
+    }
+}
+
+fn without_generated_comment(code: &str) -> String {
+    format!("{}\n", code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generated(path: &str) -> TemplateResult {
+        TemplateResult {
+            path: PathBuf::from(path),
+            content: String::new(),
+            overwrite: true,
+        }
+    }
+
+    #[test]
+    fn test_check_external_paths() {
+        let mut paths = HashSet::from([PathBuf::from("cpp/CxxCalculatorModule.cpp")]);
+
+        check_external_paths("docs", &mut paths, &[generated("docs/api.md")]).unwrap();
+        assert_eq!(
+            check_external_paths("cpp", &mut paths, &[generated("cpp/CxxCalculatorModule.cpp")])
+                .unwrap_err()
+                .to_string(),
+            "Generator `cpp` cannot write cpp/CxxCalculatorModule.cpp (already generated by another generator)"
+        );
+        assert!(check_external_paths("mock", &mut paths, &[generated("docs/api.md")]).is_err());
+    }
+}
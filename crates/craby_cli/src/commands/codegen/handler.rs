@@ -1,30 +1,44 @@
 use std::{
+    fs,
     path::{Path, PathBuf},
     time::Instant,
 };
 
 use craby_codegen::{
     codegen,
-    constants::GENERATED_COMMENT,
     generators::{
-        android_generator::AndroidGenerator,
-        cxx_generator::CxxGenerator,
-        ios_generator::IosGenerator,
-        rs_generator::RsGenerator,
-        types::{Generator, GeneratorInvoker},
+        android_generator::AndroidGenerator, cxx_generator::CxxGenerator,
+        ios_generator::IosGenerator, rs_generator::RsGenerator, types::Generator,
     },
-    types::CodegenContext,
+    ir::SchemaIr,
+    load_method_attrs,
+    merge::{merge_impl, ImplMerge},
+    resolve_types,
+    types::{CodegenContext, Schema},
 };
-use craby_common::{config::load_config, constants::craby_tmp_dir, env::is_initialized};
-use log::{debug, info};
+use craby_common::{
+    config::{load_config, CompleteConfig},
+    constants::craby_tmp_dir,
+    env::is_initialized,
+};
+use log::{debug, info, warn};
 use owo_colors::OwoColorize;
 
-use crate::utils::{file::write_file, schema::print_schema};
+use crate::{
+    commands::codegen::{ensure_generated_files, generate_files, watch, Manifest},
+    utils::{file::write_file, schema::print_schema},
+};
 
 #[derive(Debug)]
 pub struct CodegenOptions {
     pub project_root: PathBuf,
     pub overwrite: bool,
+    /// Generates from the schema IR file instead of the spec files (`craby show --format json`)
+    pub from_ir: Option<PathBuf>,
+    /// Compares the generated files with the specs without writing them
+    pub check: bool,
+    /// Regenerates the files whenever the spec files change
+    pub watch: bool,
 }
 
 pub fn perform(opts: CodegenOptions) -> anyhow::Result<()> {
@@ -32,19 +46,51 @@ pub fn perform(opts: CodegenOptions) -> anyhow::Result<()> {
         anyhow::bail!("Craby project is not initialized. Please run `craby init` first.");
     }
 
-    let tmp_dir = craby_tmp_dir(&opts.project_root);
     let config = load_config(&opts.project_root)?;
     let start_time = Instant::now();
 
     debug!("Options: {:?}", opts);
-    info!(
-        "Collecting source files... {}",
-        format!("({})", config.source_dir.display()).dimmed()
-    );
-    let schemas = codegen(craby_codegen::CodegenOptions {
-        project_root: &opts.project_root,
-        source_dir: &config.source_dir,
-    })?;
+    if opts.watch {
+        if opts.check || opts.from_ir.is_some() {
+            anyhow::bail!("`--watch` cannot be used with `--check` or `--from-ir`");
+        }
+        return watch(&opts, &config);
+    }
+
+    let schemas = match &opts.from_ir {
+        Some(ir_path) => {
+            let ir_path = opts.project_root.join(ir_path);
+            info!(
+                "Loading schema IR... {}",
+                format!("({})", ir_path.display()).dimmed()
+            );
+            SchemaIr::from_json(&fs::read_to_string(&ir_path)?)?.modules
+        }
+        None => {
+            info!(
+                "Collecting source files... {}",
+                format!("({})", config.source_dirs_display()).dimmed()
+            );
+            codegen(craby_codegen::CodegenOptions {
+                project_root: &opts.project_root,
+                source_dirs: &config.source_dirs,
+                spec_patterns: &config.spec_patterns,
+            })?
+        }
+    };
+
+    generate(&opts, &config, schemas, start_time)
+}
+
+/// Generates the files of the schemas (or checks them with `--check`).
+pub(super) fn generate(
+    opts: &CodegenOptions,
+    config: &CompleteConfig,
+    schemas: Vec<Schema>,
+    start_time: Instant,
+) -> anyhow::Result<()> {
+    let tmp_dir = craby_tmp_dir(&opts.project_root);
+    let schemas = resolve_types(schemas, config.types.namespace)?;
     let total_schemas = schemas.len();
     info!("{} module schema(s) found", total_schemas);
 
@@ -60,51 +106,88 @@ pub fn perform(opts: CodegenOptions) -> anyhow::Result<()> {
         println!();
     }
 
+    let method_attrs = load_method_attrs(&opts.project_root, &schemas)?;
     let ctx = CodegenContext {
-        project_name: config.project.name,
+        project_name: config.project.name.clone(),
         root: opts.project_root.clone(),
         schemas,
-        android_package_name: config.android.package_name,
+        android_package_name: config.android.package_name.clone(),
+        method_attrs,
     };
 
-    debug!("Cleaning up...");
-    AndroidGenerator::cleanup(&ctx)?;
-    IosGenerator::cleanup(&ctx)?;
-    RsGenerator::cleanup(&ctx)?;
-    CxxGenerator::cleanup(&ctx)?;
-
-    let mut generate_res = vec![];
-    let generators: Vec<Box<dyn GeneratorInvoker>> = vec![
-        Box::new(AndroidGenerator::new()),
-        Box::new(IosGenerator::new()),
-        Box::new(RsGenerator::new()),
-        Box::new(CxxGenerator::new()),
-    ];
+    let mut manifest = Manifest::load(&opts.project_root)?;
+
+    if opts.check {
+        info!("Checking generated files...");
+        let generate_res = generate_files(&ctx, config, &mut manifest, true)?;
+        ensure_generated_files(
+            &opts.project_root,
+            &generate_res,
+            manifest.clang_format.as_deref(),
+            true,
+        )?;
+        info!("Generated files are up to date");
+        return Ok(());
+    }
 
     info!("Generating files...");
-    for generator in generators {
-        generate_res.extend(generator.invoke_generate(&ctx)?);
+    let generate_res = generate_files(&ctx, config, &mut manifest, true)?;
+
+    // Only the files that are no longer generated are removed to keep the timestamps of the others
+    debug!("Cleaning up...");
+    AndroidGenerator::cleanup(&ctx, &generate_res)?;
+    IosGenerator::cleanup(&ctx, &generate_res)?;
+    RsGenerator::cleanup(&ctx, &generate_res)?;
+    CxxGenerator::cleanup(&ctx, &generate_res)?;
+
+    for stale_file in manifest.stale_files(&opts.project_root, &generate_res)? {
+        debug!("Removing stale file: {}", stale_file);
+        fs::remove_file(opts.project_root.join(stale_file))?;
     }
+    manifest.retain_generated(&opts.project_root, &generate_res);
 
     let mut generated_cnt = 0;
+    let mut unchanged_cnt = 0;
     let mut preserved_files = vec![];
-    for res in generate_res {
-        let content = if res.overwrite {
-            with_generated_comment(&res.path, &res.content)
-        } else {
-            without_generated_comment(&res.content)
-        };
-
+    let mut merged_files = vec![];
+    for res in &generate_res {
+        let content = &res.content;
         let should_overwrite = opts.overwrite && res.overwrite;
-        if write_file(&res.path, &content, should_overwrite)? {
+
+        // Keep the unchanged files untouched to avoid rebuilding them
+        if should_overwrite && fs::read_to_string(&res.path).is_ok_and(|src| src == *content) {
+            manifest.record(&opts.project_root, res)?;
+            unchanged_cnt += 1;
+            continue;
+        }
+
+        if write_file(&res.path, content, should_overwrite)? {
+            if res.overwrite {
+                manifest.record(&opts.project_root, res)?;
+            }
             generated_cnt += 1;
             debug!("File generated: {}", res.path.display());
         } else {
+            // Merge the new stub into the existing implementation instead
+            if opts.overwrite && !res.overwrite {
+                if let Some(merge) = try_merge_impl(&res.path, content)? {
+                    write_file(&res.path, &merge.content, true)?;
+                    merged_files.push(format!(
+                        "{} (+{} ~{} -{})",
+                        res.path.strip_prefix(&opts.project_root)?.to_string_lossy(),
+                        merge.added.len(),
+                        merge.updated.len(),
+                        merge.removed.len()
+                    ));
+                    continue;
+                }
+            }
+
             // Save the content to a temporary directory if it's not written
             let file_name = res.path.file_name().unwrap();
             let dest = tmp_dir.join(file_name);
             debug!("Saving to temporary directory: {}", dest.display());
-            write_file(&dest, &content, true)?;
+            write_file(&dest, content, true)?;
 
             if res.overwrite {
                 preserved_files.push(
@@ -117,15 +200,51 @@ pub fn perform(opts: CodegenOptions) -> anyhow::Result<()> {
         }
     }
 
+    // Saved after the files are written to keep the hashes of the files that failed to be written
+    manifest.save(&opts.project_root)?;
+
     let elapsed = start_time.elapsed().as_millis();
-    info!("{} files generated", generated_cnt);
+    info!(
+        "{} files generated {}",
+        generated_cnt,
+        format!("({} unchanged)", unchanged_cnt).dimmed()
+    );
+
+    print_files("Merged implementation files", &merged_files);
+    print_files("Preserving existing files", &preserved_files);
 
-    let preserved_file_cnt = preserved_files.len();
-    if preserved_file_cnt > 0 {
-        info!("Preserving existing files");
+    info!(
+        "Codegen completed successfully 🎉 {}",
+        format!("({}ms)", elapsed).dimmed()
+    );
 
-        for (idx, file) in preserved_files.iter().enumerate() {
-            let line = if idx == preserved_file_cnt - 1 {
+    Ok(())
+}
+
+/// Merges the new stub into the existing implementation file (`*_impl.rs`).
+///
+/// Returns `None` if the file cannot be merged or has nothing to merge.
+fn try_merge_impl(path: &Path, stub: &str) -> anyhow::Result<Option<ImplMerge>> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+        return Ok(None);
+    }
+
+    match merge_impl(&fs::read_to_string(path)?, stub) {
+        Ok(merge) => Ok(merge.filter(ImplMerge::is_changed)),
+        Err(e) => {
+            warn!("Failed to merge {}: {}", path.display(), e);
+            Ok(None)
+        }
+    }
+}
+
+fn print_files(title: &str, files: &[String]) {
+    let file_cnt = files.len();
+    if file_cnt > 0 {
+        info!("{}", title);
+
+        for (idx, file) in files.iter().enumerate() {
+            let line = if idx == file_cnt - 1 {
                 "└─"
             } else {
                 "├─"
@@ -133,28 +252,110 @@ pub fn perform(opts: CodegenOptions) -> anyhow::Result<()> {
             println!("{} {}", line, file.dimmed());
         }
     }
+}
 
-    info!(
-        "Codegen completed successfully 🎉 {}",
-        format!("({}ms)", elapsed).dimmed()
-    );
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        time::{Duration, SystemTime},
+    };
 
-    Ok(())
-}
+    use craby_codegen::{parser::native_spec_parser::try_parse_schema, utils::spec_source};
+    use craby_common::{
+        config::{AndroidConfig, IosConfig, ProjectConfig},
+        utils::fs::FilePatterns,
+    };
 
-fn with_generated_comment(path: &Path, code: &str) -> String {
-    match path.extension() {
-        Some(ext) => match ext.to_str().unwrap() {
-            // Source files
-            "rs" | "cpp" | "hpp" | "mm" => format!("// {}\n{}\n", GENERATED_COMMENT, code),
-            // CMakeLists.txt
-            "txt" => format!("# {}\n{}\n", GENERATED_COMMENT, code),
-            _ => without_generated_comment(code),
-        },
-        None => without_generated_comment(code),
+    use crate::utils::temp_dir::TempDir;
+
+    use super::*;
+
+    fn parse_module(module_name: &str) -> Schema {
+        let src = spec_source(
+            module_name,
+            "export interface Spec extends NativeModule { add(a: number, b: number): number; }",
+        );
+
+        try_parse_schema(&src).unwrap().remove(0)
     }
-}
 
-fn without_generated_comment(code: &str) -> String {
-    format!("{}\n", code)
+    fn test_config(project_root: &Path) -> CompleteConfig {
+        CompleteConfig {
+            project: ProjectConfig {
+                name: "test_module".to_string(),
+                source_dir: None,
+                source_dirs: vec![],
+                include: vec![],
+                exclude: vec![],
+            },
+            project_root: project_root.to_path_buf(),
+            source_dirs: vec![],
+            spec_patterns: FilePatterns::new(&[], &[]).unwrap(),
+            android: AndroidConfig {
+                package_name: "rs.craby.testmodule".to_string(),
+                targets: None,
+            },
+            ios: IosConfig { targets: None },
+            generators: vec![],
+            format: Default::default(),
+            types: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_generate_changed_files() {
+        let project_root = TempDir::new("codegen-test");
+        let config = test_config(&project_root);
+        let opts = CodegenOptions {
+            project_root: project_root.to_path_buf(),
+            overwrite: true,
+            from_ir: None,
+            check: false,
+            watch: false,
+        };
+        let generate_modules = |module_names: &[&str]| {
+            let schemas = module_names.iter().map(|name| parse_module(name)).collect();
+            generate(&opts, &config, schemas, Instant::now()).unwrap();
+            Manifest::load(&project_root).unwrap()
+        };
+
+        let manifest = generate_modules(&["Calculator", "Converter"]);
+        let converter_files = manifest
+            .files
+            .keys()
+            .filter(|path| path.to_lowercase().contains("converter"))
+            .cloned()
+            .collect::<Vec<_>>();
+        assert!(!converter_files.is_empty());
+
+        // Unchanged files are not written again
+        let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        for path in manifest.files.keys() {
+            let file = File::options()
+                .write(true)
+                .open(project_root.join(path))
+                .unwrap();
+            file.set_modified(mtime).unwrap();
+        }
+
+        generate_modules(&["Calculator", "Converter"]);
+        for path in manifest.files.keys() {
+            let modified = fs::metadata(project_root.join(path))
+                .unwrap()
+                .modified()
+                .unwrap();
+            assert_eq!(modified, mtime, "{path}");
+        }
+
+        // Files of the removed module are deleted
+        let manifest = generate_modules(&["Calculator"]);
+        for path in &converter_files {
+            assert!(!project_root.join(path).exists(), "{path}");
+            assert!(!manifest.files.contains_key(path), "{path}");
+        }
+        for path in manifest.files.keys() {
+            assert!(project_root.join(path).exists(), "{path}");
+        }
+    }
 }
@@ -1,3 +1,9 @@
+pub use check::*;
+pub use generate::*;
 pub use handler::*;
+pub use watch::*;
 
+mod check;
+mod generate;
 mod handler;
+mod watch;
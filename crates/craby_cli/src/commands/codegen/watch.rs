@@ -0,0 +1,247 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use craby_codegen::{parse_schemas, types::Schema, SpecSource};
+use craby_common::{config::CompleteConfig, utils::fs::collect_matched_files};
+use log::{debug, error, info, warn};
+use notify::{EventKind, RecursiveMode, Watcher};
+use owo_colors::OwoColorize;
+
+use crate::commands::codegen::{generate, CodegenOptions};
+
+/// Events within this duration are handled at once (editors write a file in several steps).
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Watches the source directories and regenerates the files whenever the spec files change.
+///
+/// Parse errors are reported without stopping the watcher, the files are regenerated once the errors are fixed.
+pub fn watch(opts: &CodegenOptions, config: &CompleteConfig) -> anyhow::Result<()> {
+    let source_dirs = config
+        .source_dirs
+        .iter()
+        .map(|source_dir| Ok(source_dir.canonicalize()?))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut specs = SpecFiles::default();
+
+    for source_dir in &source_dirs {
+        for path in collect_matched_files(source_dir, &config.spec_patterns)? {
+            specs.update(source_dir, path);
+        }
+    }
+    specs.regenerate(|schemas| generate(opts, config, schemas, Instant::now()));
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for source_dir in &source_dirs {
+        watcher.watch(source_dir, RecursiveMode::Recursive)?;
+    }
+    info!(
+        "Watching for changes... {}",
+        format!("({})", config.source_dirs_display()).dimmed()
+    );
+
+    loop {
+        let mut events = vec![rx.recv()?];
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            events.push(event);
+        }
+
+        let changed_files = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => Some(event.paths),
+                Ok(_) => None,
+                Err(e) => {
+                    warn!("Failed to watch the files: {}", e);
+                    None
+                }
+            })
+            .flatten()
+            .filter_map(|path| {
+                let source_dir = source_dirs.iter().find(|source_dir| {
+                    path.strip_prefix(source_dir)
+                        .is_ok_and(|relative| config.spec_patterns.is_match(relative))
+                })?;
+                Some((source_dir, path))
+            })
+            .collect::<BTreeSet<_>>();
+
+        if changed_files.is_empty() {
+            continue;
+        }
+
+        for (source_dir, path) in changed_files {
+            info!("File changed: {}", path.display());
+            specs.update(source_dir, path);
+        }
+        specs.regenerate(|schemas| generate(opts, config, schemas, Instant::now()));
+    }
+}
+
+/// Parsed schemas of each spec file.
+#[derive(Default)]
+struct SpecFiles {
+    schemas: BTreeMap<PathBuf, Vec<Schema>>,
+    /// Spec files that failed to parse
+    errors: BTreeSet<PathBuf>,
+    /// Schema hashes of the last generated modules (key: module name)
+    hashes: Option<BTreeMap<String, String>>,
+}
+
+impl SpecFiles {
+    /// Re-parses the spec file (removed files are dropped).
+    fn update(&mut self, source_dir: &Path, path: PathBuf) {
+        self.errors.remove(&path);
+
+        let src = match fs::read_to_string(&path) {
+            Ok(src) => src,
+            Err(_) => {
+                debug!("Spec file removed: {}", path.display());
+                self.schemas.remove(&path);
+                return;
+            }
+        };
+
+        let source = SpecSource {
+            root: source_dir.to_path_buf(),
+            path: path.clone(),
+            src,
+        };
+
+        // The diagnostics are rendered by `parse_schemas`
+        match parse_schemas(&[source]) {
+            Ok(schemas) => {
+                self.schemas.insert(path, schemas);
+            }
+            Err(e) => {
+                error!("{}", e);
+                self.errors.insert(path);
+            }
+        }
+    }
+
+    /// Regenerates the files if any module has changed (returns `true` if `generate` is called).
+    ///
+    /// The outputs of the unchanged modules are not formatted nor written again (see [`Manifest::outputs`](crate::commands::codegen::Manifest::outputs)).
+    fn regenerate(&mut self, generate: impl FnOnce(Vec<Schema>) -> anyhow::Result<()>) -> bool {
+        if !self.errors.is_empty() {
+            warn!("Waiting for the errors to be fixed...");
+            return false;
+        }
+
+        let mut schemas = self.schemas.values().flatten().cloned().collect::<Vec<_>>();
+        schemas.sort_by_key(|schema| schema.module_name.to_lowercase());
+
+        if schemas.is_empty() {
+            warn!("No native module specification files found.");
+            return false;
+        }
+
+        let hashes = schemas
+            .iter()
+            .map(|schema| {
+                let hash = Schema::to_hash(std::slice::from_ref(schema));
+                (schema.module_name.clone(), hash)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        if let Some(prev_hashes) = &self.hashes {
+            if *prev_hashes == hashes {
+                debug!("Schemas are not changed");
+                return false;
+            }
+
+            let changed_modules = hashes
+                .iter()
+                .filter(|(name, hash)| prev_hashes.get(*name) != Some(hash))
+                .map(|(name, _)| name.as_str())
+                .chain(
+                    prev_hashes
+                        .keys()
+                        .filter(|name| !hashes.contains_key(*name))
+                        .map(String::as_str),
+                )
+                .collect::<Vec<_>>();
+            info!("Changed modules: {}", changed_modules.join(", "));
+        }
+
+        match generate(schemas) {
+            Ok(()) => self.hashes = Some(hashes),
+            Err(e) => error!("{}", e),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use craby_codegen::utils::spec_source;
+
+    use crate::utils::temp_dir::TempDir;
+
+    use super::*;
+
+    fn write_spec(path: &Path, module_name: &str, methods: &str) {
+        let body = format!("export interface Spec extends NativeModule {{ {methods} }}");
+        fs::write(path, spec_source(module_name, &body)).unwrap();
+    }
+
+    /// Returns the module names passed to `generate` (`None` if not regenerated).
+    fn regenerate(specs: &mut SpecFiles) -> Option<Vec<String>> {
+        let mut module_names = None;
+        specs.regenerate(|schemas| {
+            module_names = Some(
+                schemas
+                    .into_iter()
+                    .map(|schema| schema.module_name)
+                    .collect(),
+            );
+            Ok(())
+        });
+        module_names
+    }
+
+    #[test]
+    fn test_spec_files() {
+        let source_dir = TempDir::new("watch-test");
+        let calculator = source_dir.join("NativeCalculator.ts");
+        let converter = source_dir.join("NativeConverter.ts");
+        let mut specs = SpecFiles::default();
+
+        write_spec(
+            &calculator,
+            "Calculator",
+            "add(a: number, b: number): number;",
+        );
+        write_spec(&converter, "Converter", "convert(value: number): string;");
+        specs.update(&source_dir, calculator.clone());
+        specs.update(&source_dir, converter.clone());
+        assert_eq!(
+            regenerate(&mut specs),
+            Some(vec!["Calculator".to_string(), "Converter".to_string()])
+        );
+
+        // Unchanged schemas are not regenerated
+        specs.update(&source_dir, converter.clone());
+        assert_eq!(regenerate(&mut specs), None);
+
+        // Spec files with errors block the regeneration until fixed
+        fs::write(&converter, "export interface Spec extends {").unwrap();
+        specs.update(&source_dir, converter.clone());
+        assert_eq!(regenerate(&mut specs), None);
+
+        write_spec(&converter, "Converter", "convert(value: string): string;");
+        specs.update(&source_dir, converter.clone());
+        assert!(regenerate(&mut specs).is_some());
+
+        // Schemas of the removed spec files are dropped
+        fs::remove_file(&converter).unwrap();
+        specs.update(&source_dir, converter);
+        assert_eq!(regenerate(&mut specs), Some(vec!["Calculator".to_string()]));
+    }
+}
@@ -0,0 +1,94 @@
+use std::{fs, path::PathBuf};
+
+use craby_codegen::{
+    codegen,
+    diff::{diff_schemas, ChangeKind},
+    ir::SchemaIr,
+    parse_schemas,
+};
+use craby_common::config::load_config;
+use log::info;
+use owo_colors::OwoColorize;
+
+use crate::utils::git::read_spec_sources;
+
+pub struct DiffOptions {
+    pub project_root: PathBuf,
+    /// Schema IR file (`craby show --format json`) or git revision to compare with
+    pub baseline: String,
+    /// Fails on the changes that break the Rust implementations too
+    pub strict: bool,
+}
+
+pub fn perform(opts: DiffOptions) -> anyhow::Result<()> {
+    let config = load_config(&opts.project_root)?;
+    let schemas = codegen(craby_codegen::CodegenOptions {
+        project_root: &opts.project_root,
+        source_dirs: &config.source_dirs,
+        spec_patterns: &config.spec_patterns,
+    })?;
+
+    let ir_path = opts.project_root.join(&opts.baseline);
+    let base_schemas = if ir_path.is_file() {
+        info!(
+            "Comparing with schema IR... {}",
+            format!("({})", ir_path.display()).dimmed()
+        );
+        SchemaIr::from_json(&fs::read_to_string(&ir_path)?)?.modules
+    } else {
+        info!(
+            "Comparing with git revision... {}",
+            format!("({})", opts.baseline).dimmed()
+        );
+        let sources = read_spec_sources(
+            &opts.project_root,
+            &opts.baseline,
+            &config.source_dirs,
+            &config.spec_patterns,
+        )?;
+        parse_schemas(&sources)?
+    };
+
+    let changes = diff_schemas(&base_schemas, &schemas);
+    if changes.is_empty() {
+        info!("No schema changes");
+        return Ok(());
+    }
+
+    let mut module_name = None;
+    for change in &changes {
+        if module_name != Some(&change.module_name) {
+            module_name = Some(&change.module_name);
+            println!("{}", change.module_name.bold());
+        }
+
+        match change.kind {
+            ChangeKind::Breaking => println!("  {} {}", "breaking".red(), change.message),
+            ChangeKind::RustBreaking => {
+                println!("  {} {}", "rust-breaking".yellow(), change.message)
+            }
+            ChangeKind::Compatible => println!("  {} {}", "compatible".green(), change.message),
+        }
+    }
+    println!();
+
+    let count = |kind: ChangeKind| changes.iter().filter(|change| change.kind == kind).count();
+    let breaking_changes = count(ChangeKind::Breaking);
+    let rust_breaking_changes = count(ChangeKind::RustBreaking);
+
+    if breaking_changes > 0 {
+        anyhow::bail!("{} breaking change(s) found", breaking_changes);
+    }
+
+    if opts.strict && rust_breaking_changes > 0 {
+        anyhow::bail!("{} rust-breaking change(s) found", rust_breaking_changes);
+    }
+
+    info!(
+        "{} compatible change(s), {} rust-breaking change(s) found",
+        count(ChangeKind::Compatible),
+        rust_breaking_changes
+    );
+
+    Ok(())
+}
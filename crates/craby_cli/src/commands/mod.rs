@@ -1,6 +1,7 @@
 pub mod build;
 pub mod clean;
 pub mod codegen;
+pub mod diff;
 pub mod doctor;
 pub mod init;
 pub mod show;
@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
 
-use craby_codegen::codegen;
+use craby_codegen::{codegen, ir::SchemaIr, resolve_types};
 use craby_common::config::load_config;
 use log::info;
 use owo_colors::OwoColorize;
@@ -9,15 +9,45 @@ use crate::utils::schema::print_schema;
 
 pub struct ShowOptions {
     pub project_root: PathBuf,
+    pub format: ShowFormat,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ShowFormat {
+    /// Human-readable tree of the modules
+    #[default]
+    Text,
+    /// Schema IR of the modules (see [`SchemaIr`])
+    Json,
+}
+
+impl FromStr for ShowFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ShowFormat::Text),
+            "json" => Ok(ShowFormat::Json),
+            _ => anyhow::bail!("Unknown format: {} (expected: text, json)", s),
+        }
+    }
 }
 
 pub fn perform(opts: ShowOptions) -> anyhow::Result<()> {
     let config = load_config(&opts.project_root)?;
     let schemas = codegen(craby_codegen::CodegenOptions {
         project_root: &opts.project_root,
-        source_dir: &config.source_dir,
+        source_dirs: &config.source_dirs,
+        spec_patterns: &config.spec_patterns,
     })?;
 
+    if opts.format == ShowFormat::Json {
+        // Same type names as the generated code
+        let schemas = resolve_types(schemas, config.types.namespace)?;
+        println!("{}", SchemaIr::new(schemas).to_json()?);
+        return Ok(());
+    }
+
     let total_mods = schemas.len();
     info!("{} module(s) found\n", total_mods);
 
@@ -1,5 +1,11 @@
-use std::{fs, path::PathBuf, process::Command};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
+use craby_codegen::SpecSource;
+use craby_common::utils::fs::FilePatterns;
 use log::debug;
 
 use crate::utils::terminal::run_command;
@@ -47,3 +53,63 @@ pub fn clone_template() -> Result<PathBuf, anyhow::Error> {
 
     Ok(temp_dir)
 }
+
+/// Reads the spec files at the git revision (eg. `main`, `v1.0.0`).
+pub fn read_spec_sources(
+    project_root: &Path,
+    rev: &str,
+    source_dirs: &[PathBuf],
+    spec_patterns: &FilePatterns,
+) -> Result<Vec<SpecSource>, anyhow::Error> {
+    let mut sources = vec![];
+
+    for source_dir in source_dirs {
+        let relative_dir = source_dir.strip_prefix(project_root)?;
+        // Paths are relative to the project root
+        let files = git_output(
+            project_root,
+            &[
+                "ls-tree",
+                "-r",
+                "--name-only",
+                rev,
+                "--",
+                relative_dir.to_str().unwrap(),
+            ],
+        )?;
+
+        for file in files.lines() {
+            let path = project_root.join(file);
+            let is_match = path
+                .strip_prefix(source_dir)
+                .is_ok_and(|relative| spec_patterns.is_match(relative));
+
+            if !is_match {
+                continue;
+            }
+
+            debug!("Reading spec file at {}: {}", rev, file);
+            sources.push(SpecSource {
+                root: source_dir.clone(),
+                src: git_output(project_root, &["show", &format!("{}:./{}", rev, file)])?,
+                path,
+            });
+        }
+    }
+
+    Ok(sources)
+}
+
+fn git_output(cwd: &Path, args: &[&str]) -> Result<String, anyhow::Error> {
+    let output = Command::new("git").args(args).current_dir(cwd).output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
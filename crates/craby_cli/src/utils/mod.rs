@@ -3,5 +3,7 @@ pub mod file;
 pub mod git;
 pub mod log;
 pub mod schema;
+#[cfg(test)]
+pub mod temp_dir;
 pub mod template;
 pub mod terminal;
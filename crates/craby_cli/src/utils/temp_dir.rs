@@ -0,0 +1,31 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Temporary directory of the tests, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates the empty directory unique to the test (`name`) and the process.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("craby-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
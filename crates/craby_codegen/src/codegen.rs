@@ -1,54 +1,94 @@
-use std::{fs, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
-use craby_common::{constants::SPEC_FILE_PREFIX, utils::fs::collect_files};
+use craby_common::{
+    constants::{crate_dir, impl_mod_name},
+    utils::{
+        fs::{collect_matched_files, FilePatterns},
+        string::{pascal_case, snake_case},
+    },
+};
 use log::debug;
+use rustc_hash::{FxHashMap, FxHashSet};
+use syn::{spanned::Spanned, Attribute, ImplItem, Item, LitStr, Token};
 
 use crate::{
     parser::{
+        flow_spec_parser::{try_parse_flow_schema, FLOW_SPEC_EXTENSIONS},
         native_spec_parser::try_parse_schema,
-        types::ParseError,
+        types::{NativeObjectTypeAnnotation, ParseError, TypeAnnotation},
         utils::{render_report, RenderReportOptions},
     },
-    types::Schema,
+    types::{MethodAttrs, MethodAttrsMap, Schema},
 };
 
 pub struct CodegenOptions<'a> {
     pub project_root: &'a PathBuf,
-    pub source_dir: &'a PathBuf,
+    pub source_dirs: &'a [PathBuf],
+    pub spec_patterns: &'a FilePatterns,
 }
 
 pub fn codegen<'a>(opts: CodegenOptions<'a>) -> Result<Vec<Schema>, anyhow::Error> {
-    let srcs = collect_files(opts.source_dir, &|path: &PathBuf| {
-        path.extension().unwrap_or_default() == "ts"
-            && path
-                .file_name()
-                .unwrap()
-                .to_string_lossy()
-                .starts_with(SPEC_FILE_PREFIX)
-    })?;
+    // (source root, source file)
+    let mut srcs = vec![];
+    for source_dir in opts.source_dirs {
+        for path in collect_matched_files(source_dir, opts.spec_patterns)? {
+            srcs.push((source_dir, path));
+        }
+    }
+    srcs.sort_by(|(_, a), (_, b)| a.cmp(b));
+    srcs.dedup_by(|(_, a), (_, b)| a == b);
     debug!("{} source file(s) found", srcs.len());
 
     if srcs.is_empty() {
         anyhow::bail!("No native module specification files found.");
     }
 
-    let collected_schemas = srcs
+    let sources = srcs
+        .into_iter()
+        .map(|(source_dir, path)| {
+            Ok(SpecSource {
+                root: source_dir.clone(),
+                src: fs::read_to_string(&path)?,
+                path,
+            })
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+    parse_schemas(&sources)
+}
+
+/// Spec file to parse.
+pub struct SpecSource {
+    /// Source directory that contains the file
+    pub root: PathBuf,
+    pub path: PathBuf,
+    pub src: String,
+}
+
+/// Parses the spec files into the module schemas (sorted by the module name).
+pub fn parse_schemas(sources: &[SpecSource]) -> Result<Vec<Schema>, anyhow::Error> {
+    let collected_schemas = sources
         .iter()
-        .map(|path| {
-            let src = fs::read_to_string(path)?;
+        .map(|SpecSource { root, path, src }| {
             let src = src.as_str();
+            let is_flow = path
+                .extension()
+                .is_some_and(|ext| FLOW_SPEC_EXTENSIONS.iter().any(|flow_ext| ext == *flow_ext));
+            let result = if is_flow {
+                try_parse_flow_schema(src)
+            } else {
+                try_parse_schema(src)
+            };
 
-            match try_parse_schema(src) {
+            match result {
                 Ok(schemas) => Ok(schemas),
                 Err(ParseError::Oxc { diagnostics }) => {
-                    render_report(
-                        diagnostics,
-                        RenderReportOptions {
-                            project_root: opts.project_root,
-                            path,
-                            src,
-                        },
-                    );
+                    // Report the path relative to the source directory that contains the file
+                    render_report(diagnostics, RenderReportOptions { root, path, src });
                     anyhow::bail!("Failed to parse schema");
                 }
                 Err(ParseError::General(e)) => {
@@ -65,3 +105,536 @@ pub fn codegen<'a>(opts: CodegenOptions<'a>) -> Result<Vec<Schema>, anyhow::Erro
 
     Ok(schemas)
 }
+
+/// Resolves the type names of the modules, which share the same namespace in the generated code.
+///
+/// The same types declared in the modules are shared (compared by [`TypeAnnotation::to_id`]), and the different types
+/// of the same name are reported unless the type names are prefixed with the module name (`namespace`).
+pub fn resolve_types(
+    mut schemas: Vec<Schema>,
+    namespace: bool,
+) -> Result<Vec<Schema>, anyhow::Error> {
+    check_ffi_names(&schemas)?;
+
+    if namespace {
+        schemas.iter_mut().for_each(namespace_types);
+    }
+
+    // Key: type name, Value: (module name, type ID)
+    let mut types = FxHashMap::<String, (&str, Option<u64>)>::default();
+
+    for schema in &schemas {
+        let constants_type = schema.constants_type();
+        let decls = schema
+            .aliases
+            .iter()
+            .chain(&schema.enums)
+            .chain(&constants_type)
+            .map(|type_annotation| (type_name(type_annotation), Some(type_annotation.to_id())));
+        // Native objects are implemented by each module, so they cannot be shared
+        let objects = schema
+            .objects
+            .iter()
+            .map(|object| (object.name.as_str(), None));
+
+        for (name, id) in decls.chain(objects) {
+            match types.get(name) {
+                Some((module_name, prev_id)) if id.is_none() || *prev_id != id => {
+                    if *module_name == schema.module_name {
+                        anyhow::bail!(
+                            "Type name `{}` is used by different types of the `{}` module",
+                            name,
+                            module_name
+                        );
+                    } else {
+                        anyhow::bail!(
+                            "Type `{}` of the `{}` module conflicts with the type of the `{}` module. \
+                            Rename one of them or set `namespace = true` in the `[types]` section of craby.toml",
+                            name,
+                            schema.module_name,
+                            module_name
+                        );
+                    }
+                }
+                Some(..) => {}
+                None => drop(types.insert(name.to_string(), (&schema.module_name, id))),
+            }
+        }
+    }
+
+    Ok(schemas)
+}
+
+/// Returns an error if the modules or the methods of the different modules are converted into the same identifier
+/// of the generated code (eg. `Foo.barBaz` and `FooBar.baz` are both `foo_bar_baz` in the cxx bridge).
+fn check_ffi_names(schemas: &[Schema]) -> Result<(), anyhow::Error> {
+    // Key: (identifier kind, identifier), Value: name in the spec
+    let mut idents = FxHashMap::default();
+
+    for schema in schemas {
+        let module_name = &schema.module_name;
+        let module_idents = [
+            format!("Cxx{}Module", pascal_case(module_name)),
+            snake_case(module_name),
+        ];
+
+        for ident in module_idents {
+            insert_ident(&mut idents, "module", ident, module_name.clone())?;
+        }
+    }
+
+    for schema in schemas {
+        let owners = std::iter::once((schema.module_name.as_str(), &schema.methods)).chain(
+            schema
+                .objects
+                .iter()
+                .map(|object| (object.name.as_str(), &object.methods)),
+        );
+
+        for (owner, methods) in owners {
+            for method in methods {
+                let ident = format!("{}_{}", snake_case(owner), snake_case(&method.name));
+                let name = format!("{owner}.{}", method.name);
+                insert_ident(&mut idents, "FFI function", ident, name)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn insert_ident(
+    idents: &mut FxHashMap<(&'static str, String), String>,
+    kind: &'static str,
+    ident: String,
+    name: String,
+) -> Result<(), anyhow::Error> {
+    match idents.get(&(kind, ident.clone())) {
+        Some(prev_name) if *prev_name == name => anyhow::bail!("Duplicate {kind} `{name}`"),
+        Some(prev_name) => anyhow::bail!(
+            "`{}` and `{}` are converted into the same {} `{}`",
+            prev_name,
+            name,
+            kind,
+            ident
+        ),
+        None => drop(idents.insert((kind, ident), name)),
+    }
+
+    Ok(())
+}
+
+fn type_name(type_annotation: &TypeAnnotation) -> &str {
+    match type_annotation {
+        TypeAnnotation::Object(obj) => &obj.name,
+        TypeAnnotation::Enum(enum_type) => &enum_type.name,
+        _ => unreachable!(),
+    }
+}
+
+/// Prefixes the names of the types declared in the module with the module name (eg. `Options` to `CalculatorOptions`).
+fn namespace_types(schema: &mut Schema) {
+    let prefix = pascal_case(&schema.module_name);
+    let names = schema
+        .aliases
+        .iter()
+        .chain(&schema.enums)
+        .map(|type_annotation| type_name(type_annotation).to_string())
+        .chain(schema.objects.iter().map(|object| object.name.clone()))
+        .collect::<FxHashSet<_>>();
+    let rename =
+        |type_annotation: &mut TypeAnnotation| rename_type(type_annotation, &prefix, &names);
+
+    let methods = schema.methods.iter_mut().chain(
+        schema
+            .objects
+            .iter_mut()
+            .flat_map(|object| &mut object.methods),
+    );
+    for method in methods {
+        method
+            .params
+            .iter_mut()
+            .for_each(|param| rename(&mut param.type_annotation));
+        rename(&mut method.ret_type);
+    }
+
+    schema.aliases.iter_mut().for_each(rename);
+    schema.enums.iter_mut().for_each(rename);
+    schema
+        .signals
+        .iter_mut()
+        .filter_map(|signal| signal.payload_type.as_mut())
+        .for_each(rename);
+    schema
+        .constants
+        .iter_mut()
+        .for_each(|prop| rename(&mut prop.type_annotation));
+    schema
+        .objects
+        .iter_mut()
+        .for_each(|object| object.name = format!("{prefix}{}", object.name));
+}
+
+fn rename_type(type_annotation: &mut TypeAnnotation, prefix: &str, names: &FxHashSet<String>) {
+    let rename = |name: &mut String| {
+        if names.contains(name) {
+            *name = format!("{prefix}{name}");
+        }
+    };
+
+    match type_annotation {
+        TypeAnnotation::Object(obj) => {
+            rename(&mut obj.name);
+            obj.props
+                .iter_mut()
+                .for_each(|prop| rename_type(&mut prop.type_annotation, prefix, names));
+        }
+        TypeAnnotation::Enum(enum_type) => rename(&mut enum_type.name),
+        TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name }) => rename(name),
+        TypeAnnotation::Array(inner)
+        | TypeAnnotation::Promise(inner)
+        | TypeAnnotation::Nullable(inner) => rename_type(inner, prefix, names),
+        _ => {}
+    }
+}
+
+/// Loads the method attributes (`#[craby::method(...)]`) from the implementation sources (`crates/lib/src/<module>_impl.rs`).
+///
+/// Modules without the implementation source have no method attributes.
+pub fn load_method_attrs(
+    project_root: &Path,
+    schemas: &[Schema],
+) -> Result<BTreeMap<String, MethodAttrsMap>, anyhow::Error> {
+    let src_dir = crate_dir(project_root).join("src");
+    let mut method_attrs = BTreeMap::new();
+
+    for schema in schemas {
+        let path = src_dir.join(format!("{}.rs", impl_mod_name(&schema.module_name)));
+
+        if !path.try_exists()? {
+            continue;
+        }
+
+        let trait_name = format!("{}Spec", pascal_case(&schema.module_name));
+        let attrs = parse_method_attrs(&fs::read_to_string(&path)?, &trait_name).map_err(|e| {
+            anyhow::anyhow!("Invalid method attributes ({}): {}", path.display(), e)
+        })?;
+        debug!("Method attributes of {}: {:?}", schema.module_name, attrs);
+
+        if !attrs.is_empty() {
+            method_attrs.insert(schema.module_name.clone(), attrs);
+        }
+    }
+
+    Ok(method_attrs)
+}
+
+/// Parses the method attributes of the `impl <trait_name> for ...` block (key: Rust method name).
+fn parse_method_attrs(src: &str, trait_name: &str) -> syn::Result<MethodAttrsMap> {
+    let file = syn::parse_file(src)?;
+    let item_impl = file.items.iter().find_map(|item| match item {
+        Item::Impl(item_impl) => item_impl
+            .trait_
+            .as_ref()
+            .and_then(|(_, path, _)| path.segments.last())
+            .filter(|seg| seg.ident == trait_name)
+            .map(|_| item_impl),
+        _ => None,
+    });
+
+    let mut method_attrs = MethodAttrsMap::new();
+    let Some(item_impl) = item_impl else {
+        return Ok(method_attrs);
+    };
+
+    for item in &item_impl.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+
+        let mut attrs = MethodAttrs::default();
+        let mut found = false;
+
+        for attr in method.attrs.iter().filter(|attr| is_method_attr(attr)) {
+            parse_method_attr(attr, &mut attrs)?;
+            found = true;
+        }
+
+        if found {
+            method_attrs.insert(method.sig.ident.to_string(), attrs);
+        }
+    }
+
+    Ok(method_attrs)
+}
+
+fn is_method_attr(attr: &Attribute) -> bool {
+    let path = attr.path();
+
+    path.is_ident("method")
+        || (path.segments.len() == 2
+            && path.segments[0].ident == "craby"
+            && path.segments[1].ident == "method")
+}
+
+fn parse_method_attr(attr: &Attribute, attrs: &mut MethodAttrs) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("blocking") {
+            attrs.blocking = true;
+        } else if meta.path.is_ident("trace") {
+            attrs.trace = true;
+        } else if meta.path.is_ident("timeout") {
+            meta.input.parse::<Token![=]>()?;
+            let value: LitStr = meta.input.parse()?;
+            attrs.timeout = Some(
+                parse_duration(&value.value())
+                    .filter(|timeout| *timeout > 0)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            value.span(),
+                            format!("invalid duration: {}", value.value()),
+                        )
+                    })?,
+            );
+        } else {
+            return Err(syn::Error::new(
+                meta.path.span(),
+                "unknown method attribute",
+            ));
+        }
+
+        Ok(())
+    })
+}
+
+/// Parses the duration string (`ms`, `s` or `m`) into milliseconds.
+fn parse_duration(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (num, scale) = if let Some(num) = value.strip_suffix("ms") {
+        (num, 1)
+    } else if let Some(num) = value.strip_suffix('s') {
+        (num, 1_000)
+    } else if let Some(num) = value.strip_suffix('m') {
+        (num, 60_000)
+    } else {
+        return None;
+    };
+
+    num.trim().parse::<u64>().ok()?.checked_mul(scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        generators::{cxx_generator::CxxGenerator, rs_generator::RsGenerator, types::Generator},
+        types::CodegenContext,
+        utils::spec_source,
+    };
+
+    use super::*;
+
+    fn parse_module(module_name: &str, props: &str) -> Schema {
+        let src = spec_source(
+            module_name,
+            &format!(
+                "
+                export type Options = {{
+                    {props}
+                }};
+
+                export enum Mode {{
+                    Fast = 0,
+                    Slow = 1,
+                }}
+
+                export interface Spec extends NativeModule {{
+                    run(options: Options, mode: Mode): Options | null;
+                }}
+                "
+            ),
+        );
+
+        try_parse_schema(&src).unwrap().remove(0)
+    }
+
+    fn generated_code(schemas: Vec<Schema>) -> String {
+        let ctx = CodegenContext {
+            project_name: "test_module".to_string(),
+            root: PathBuf::from("."),
+            schemas,
+            android_package_name: "rs.craby.testmodule".to_string(),
+            method_attrs: BTreeMap::new(),
+        };
+
+        let rs_files = RsGenerator::new().generate(&ctx).unwrap();
+        let cxx_files = CxxGenerator::new().generate(&ctx).unwrap();
+        rs_files
+            .iter()
+            .chain(&cxx_files)
+            .map(|res| res.content.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_shared_types() {
+        let schemas = vec![
+            parse_module("Calculator", "precision: number;"),
+            parse_module("Converter", "precision: number;"),
+        ];
+        let code = generated_code(resolve_types(schemas, false).unwrap());
+
+        assert_eq!(code.matches("struct OptionsBridge {").count(), 1);
+        assert_eq!(code.matches("enum Mode {").count(), 1);
+        assert_eq!(
+            code.matches("struct Bridging<craby::testmodule::bridging::Options>")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_type_conflicts() {
+        let schemas = vec![
+            parse_module("Calculator", "precision: number;"),
+            parse_module("Converter", "unit: string;"),
+        ];
+        let err = resolve_types(schemas, false).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("Type `Options` of the `Converter` module conflicts"));
+    }
+
+    #[test]
+    fn test_module_type_conflicts() {
+        let mut schema = parse_module("Calculator", "precision: number;");
+        let other = parse_module("Calculator", "unit: string;");
+        schema.aliases.extend(other.aliases);
+        let err = resolve_types(vec![schema], false).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .starts_with("Type name `Options` is used by different types"));
+    }
+
+    #[test]
+    fn test_ffi_name_collisions() {
+        let cases = [
+            (
+                "Foo",
+                "barBaz(): void;",
+                "FooBar",
+                "baz(): void;",
+                "`Foo.barBaz` and `FooBar.baz` are converted into the same FFI function `foo_bar_baz`",
+            ),
+            (
+                "fooBar",
+                "foo(): void;",
+                "foo_bar",
+                "bar(): void;",
+                "`fooBar` and `foo_bar` are converted into the same module `CxxFooBarModule`",
+            ),
+            (
+                "Calculator",
+                "add(): void;",
+                "Calculator",
+                "sub(): void;",
+                "Duplicate module `Calculator`",
+            ),
+        ];
+
+        for (module_a, methods_a, module_b, methods_b, message) in cases {
+            let schemas = [(module_a, methods_a), (module_b, methods_b)]
+                .into_iter()
+                .map(|(module_name, methods)| {
+                    let src = spec_source(
+                        module_name,
+                        &format!("export interface Spec extends NativeModule {{ {methods} }}"),
+                    );
+                    try_parse_schema(&src).unwrap().remove(0)
+                })
+                .collect();
+
+            let err = resolve_types(schemas, false).unwrap_err();
+            assert_eq!(err.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn test_namespace_types() {
+        let schemas = vec![
+            parse_module("Calculator", "precision: number;"),
+            parse_module("Converter", "unit: string;"),
+        ];
+        let schemas = resolve_types(schemas, true).unwrap();
+        let method = &schemas[0].methods[0];
+
+        assert_eq!(type_name(&schemas[0].aliases[0]), "CalculatorOptions");
+        assert_eq!(type_name(&schemas[1].enums[0]), "ConverterMode");
+        assert_eq!(
+            type_name(&method.params[0].type_annotation),
+            "CalculatorOptions"
+        );
+        assert!(matches!(
+            &method.ret_type,
+            TypeAnnotation::Nullable(inner) if type_name(inner) == "CalculatorOptions"
+        ));
+
+        let code = generated_code(schemas);
+        assert!(code.contains("pub struct CalculatorOptions {"));
+        assert!(code.contains("pub struct ConverterOptions {"));
+    }
+
+    #[test]
+    fn test_parse_method_attrs() {
+        let src = r#"
+            impl CalculatorSpec for Calculator {
+                #[craby::method(blocking, timeout = "5s")]
+                fn add(&mut self, a: Number, b: Number) -> Promise<Number> { Ok(a + b) }
+
+                #[inline]
+                #[method(trace)]
+                fn subtract(&mut self, a: Number, b: Number) -> Number { a - b }
+
+                fn multiply(&mut self, a: Number, b: Number) -> Number { a * b }
+            }
+
+            impl OtherSpec for Calculator {
+                #[craby::method(trace)]
+                fn divide(&mut self, a: Number, b: Number) -> Number { a / b }
+            }
+        "#;
+
+        assert_eq!(
+            parse_method_attrs(src, "CalculatorSpec").unwrap(),
+            BTreeMap::from([
+                (
+                    "add".to_string(),
+                    MethodAttrs {
+                        blocking: true,
+                        timeout: Some(5_000),
+                        trace: false,
+                    },
+                ),
+                (
+                    "subtract".to_string(),
+                    MethodAttrs {
+                        blocking: false,
+                        timeout: None,
+                        trace: true,
+                    },
+                ),
+            ])
+        );
+        assert!(parse_method_attrs(src, "UnknownSpec").unwrap().is_empty());
+
+        let src = r#"
+            impl CalculatorSpec for Calculator {
+                #[craby::method(timeout = "5 seconds")]
+                fn add(&mut self, a: Number, b: Number) -> Promise<Number> { Ok(a + b) }
+            }
+        "#;
+        assert!(parse_method_attrs(src, "CalculatorSpec").is_err());
+    }
+}
@@ -3,13 +3,20 @@ pub const GENERATED_COMMENT: &str = "Auto generated by Craby. DO NOT EDIT.";
 pub mod specs {
     pub const NATIVE_MODULE_PKG: &str = "craby-modules";
     pub const NATIVE_MODULE_INTERFACE: &str = "NativeModule";
+    pub const NATIVE_OBJECT_INTERFACE: &str = "NativeObject";
     pub const NATIVE_MODULE_REGISTRY: &str = "NativeModuleRegistry";
     pub const SIGNAL_TYPE: &str = "Signal";
     pub const REGISTRY_GET: &str = "get";
     pub const REGISTRY_GET_ENFORCING: &str = "getEnforcing";
 
+    pub const RESERVED_TYPE_ARRAY: &str = "Array";
     pub const RESERVED_TYPE_ARRAY_BUFFER: &str = "ArrayBuffer";
     pub const RESERVED_TYPE_PROMISE: &str = "Promise";
+    pub const RESERVED_TYPE_READONLY: &str = "Readonly";
+    pub const RESERVED_TYPE_READONLY_ARRAY: &str = "ReadonlyArray";
+    pub const RESERVED_TYPE_PARTIAL: &str = "Partial";
+    pub const RESERVED_TYPE_PICK: &str = "Pick";
+    pub const RESERVED_TYPE_OMIT: &str = "Omit";
 
     /// `it_` is reserved for the `shared_ptr` of the module
     pub const RESERVED_ARG_NAME_MODULE: &str = "it_";
@@ -17,3 +24,122 @@ pub mod specs {
     /// `emit` is reserved for signals
     pub const RESERVED_METHOD_NAME_MODULE: &str = "emit";
 }
+
+/// Identifiers that the spec cannot use as is (reserved by the generated code or the target languages).
+pub mod reserved {
+    use super::specs::{RESERVED_ARG_NAME_MODULE, RESERVED_METHOD_NAME_MODULE};
+
+    /// Method names of the module trait (`new` and `id` are implemented by `#[craby_module]`)
+    pub const METHOD_NAMES: &[&str] = &[RESERVED_METHOD_NAME_MODULE, "new", "id"];
+
+    /// Parameter names of the generated functions
+    pub const PARAM_NAMES: &[&str] = &[RESERVED_ARG_NAME_MODULE];
+
+    /// Rust keywords (escaped as raw identifiers, eg. `r#type`)
+    pub const RUST_KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    /// Rust keywords that cannot be raw identifiers
+    pub const RUST_PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+    /// C++ keywords (escaped with the `_` suffix, eg. `delete_`)
+    pub const CXX_KEYWORDS: &[&str] = &[
+        "alignas",
+        "alignof",
+        "and",
+        "and_eq",
+        "asm",
+        "auto",
+        "bitand",
+        "bitor",
+        "bool",
+        "break",
+        "case",
+        "catch",
+        "char",
+        "char8_t",
+        "char16_t",
+        "char32_t",
+        "class",
+        "compl",
+        "concept",
+        "const",
+        "consteval",
+        "constexpr",
+        "constinit",
+        "const_cast",
+        "continue",
+        "co_await",
+        "co_return",
+        "co_yield",
+        "decltype",
+        "default",
+        "delete",
+        "do",
+        "double",
+        "dynamic_cast",
+        "else",
+        "enum",
+        "explicit",
+        "export",
+        "extern",
+        "false",
+        "float",
+        "for",
+        "friend",
+        "goto",
+        "if",
+        "inline",
+        "int",
+        "long",
+        "mutable",
+        "namespace",
+        "new",
+        "noexcept",
+        "not",
+        "not_eq",
+        "nullptr",
+        "operator",
+        "or",
+        "or_eq",
+        "private",
+        "protected",
+        "public",
+        "register",
+        "reinterpret_cast",
+        "requires",
+        "return",
+        "short",
+        "signed",
+        "sizeof",
+        "static",
+        "static_assert",
+        "static_cast",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "thread_local",
+        "throw",
+        "true",
+        "try",
+        "typedef",
+        "typeid",
+        "typename",
+        "union",
+        "unsigned",
+        "using",
+        "virtual",
+        "void",
+        "volatile",
+        "wchar_t",
+        "while",
+        "xor",
+        "xor_eq",
+    ];
+}
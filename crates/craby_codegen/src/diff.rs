@@ -0,0 +1,542 @@
+use std::fmt::Display;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    parser::types::{
+        EnumTypeAnnotation, Method, NativeObjectTypeAnnotation, ObjectTypeAnnotation, Prop, Signal,
+        TypeAnnotation,
+    },
+    types::Schema,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    /// Existing JS callers and Rust implementations keep working
+    Compatible,
+    /// Existing JS callers keep working, but the Rust implementations need to be updated (eg. new trait methods)
+    RustBreaking,
+    /// Existing JS callers need to be updated
+    Breaking,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    pub module_name: String,
+    pub message: String,
+}
+
+impl SchemaChange {
+    pub fn is_breaking(&self) -> bool {
+        self.kind == ChangeKind::Breaking
+    }
+}
+
+/// Compares the module schemas with the baseline and classifies each change.
+///
+/// Object and enum types are compared by the direction of the values:
+/// - Input (JS → Rust, eg. parameters): new required properties and removed enum members break the callers,
+///   and removed properties and new enum members break the Rust implementations (field access, exhaustive `match`).
+/// - Output (Rust → JS, eg. return values): removed properties and new enum members break the callers,
+///   and new properties and removed enum members break the Rust implementations (struct literals, enum variants).
+pub fn diff_schemas(base: &[Schema], current: &[Schema]) -> Vec<SchemaChange> {
+    let mut changes = vec![];
+
+    for base_schema in base {
+        let mut diff = ModuleDiff {
+            module_name: &base_schema.module_name,
+            changes: &mut changes,
+        };
+
+        match current
+            .iter()
+            .find(|schema| schema.module_name == base_schema.module_name)
+        {
+            Some(schema) => diff.diff(base_schema, schema),
+            None => diff.breaking("Module removed".to_string()),
+        }
+    }
+
+    for schema in current {
+        if !base
+            .iter()
+            .any(|base_schema| base_schema.module_name == schema.module_name)
+        {
+            changes.push(SchemaChange {
+                kind: ChangeKind::Compatible,
+                module_name: schema.module_name.clone(),
+                message: "Module added".to_string(),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Direction of the values of the type.
+#[derive(Debug, Default, Clone, Copy)]
+struct Usage {
+    /// Passed from JS to Rust
+    input: bool,
+    /// Returned from Rust to JS
+    output: bool,
+}
+
+struct ModuleDiff<'a> {
+    module_name: &'a str,
+    changes: &'a mut Vec<SchemaChange>,
+}
+
+impl ModuleDiff<'_> {
+    fn push(&mut self, kind: ChangeKind, message: String) {
+        self.changes.push(SchemaChange {
+            kind,
+            module_name: self.module_name.to_string(),
+            message,
+        });
+    }
+
+    fn breaking(&mut self, message: String) {
+        self.push(ChangeKind::Breaking, message);
+    }
+
+    fn rust_breaking(&mut self, message: String) {
+        self.push(ChangeKind::RustBreaking, message);
+    }
+
+    fn compatible(&mut self, message: String) {
+        self.push(ChangeKind::Compatible, message);
+    }
+
+    /// Pushes the change that breaks the JS callers or the Rust implementations.
+    fn classify(&mut self, breaks_js: bool, breaks_rust: bool, message: String) {
+        match (breaks_js, breaks_rust) {
+            (true, _) => self.breaking(message),
+            (false, true) => self.rust_breaking(message),
+            (false, false) => self.compatible(message),
+        }
+    }
+
+    fn diff(&mut self, base: &Schema, current: &Schema) {
+        self.diff_methods(&base.methods, &current.methods, None);
+        self.diff_signals(&base.signals, &current.signals);
+        self.diff_constants(&base.constants, &current.constants);
+
+        for base_object in &base.objects {
+            match current.objects.iter().find(|o| o.name == base_object.name) {
+                Some(object) => {
+                    self.diff_methods(&base_object.methods, &object.methods, Some(&object.name))
+                }
+                None => self.breaking(format!("Native object removed: `{}`", base_object.name)),
+            }
+        }
+        for object in &current.objects {
+            if !base.objects.iter().any(|o| o.name == object.name) {
+                self.compatible(format!("Native object added: `{}`", object.name));
+            }
+        }
+
+        // Types used by both versions are compared with the usage of both versions
+        let mut usages = FxHashMap::default();
+        collect_usages(base, &mut usages);
+        collect_usages(current, &mut usages);
+
+        for base_alias in base.aliases.iter().filter_map(|alias| alias.as_object()) {
+            if let Some(alias) = current
+                .aliases
+                .iter()
+                .filter_map(|alias| alias.as_object())
+                .find(|alias| alias.name == base_alias.name)
+            {
+                let usage = usages.get(&alias.name).copied().unwrap_or_default();
+                self.diff_object(base_alias, alias, usage);
+            }
+        }
+
+        for base_enum in base.enums.iter().filter_map(|e| e.as_enum()) {
+            if let Some(enum_type) = current
+                .enums
+                .iter()
+                .filter_map(|e| e.as_enum())
+                .find(|e| e.name == base_enum.name)
+            {
+                let usage = usages.get(&enum_type.name).copied().unwrap_or_default();
+                self.diff_enum(base_enum, enum_type, usage);
+            }
+        }
+    }
+
+    fn diff_methods(&mut self, base: &[Method], current: &[Method], object: Option<&str>) {
+        let name_of = |method: &Method| match object {
+            Some(object) => format!("{}.{}", object, method.name),
+            None => method.name.clone(),
+        };
+
+        for base_method in base {
+            let Some(method) = current.iter().find(|m| m.name == base_method.name) else {
+                self.breaking(format!("Method removed: `{}`", name_of(base_method)));
+                continue;
+            };
+
+            let name = name_of(method);
+            if base_method.params.len() != method.params.len() {
+                self.breaking(format!(
+                    "Parameter count changed: `{}` ({} → {})",
+                    name,
+                    base_method.params.len(),
+                    method.params.len()
+                ));
+            } else {
+                for (base_param, param) in base_method.params.iter().zip(&method.params) {
+                    if !is_same_type(&base_param.type_annotation, &param.type_annotation) {
+                        // `T` → `T | null` accepts the existing arguments, but the Rust parameter becomes `Option<T>`
+                        let widened =
+                            is_nullable_of(&param.type_annotation, &base_param.type_annotation);
+                        self.classify(
+                            !widened,
+                            true,
+                            format!(
+                                "Parameter type changed: `{}({})` ({} → {})",
+                                name,
+                                param.name,
+                                TypeDisplay(&base_param.type_annotation),
+                                TypeDisplay(&param.type_annotation)
+                            ),
+                        );
+                    }
+                }
+            }
+
+            if !is_same_type(&base_method.ret_type, &method.ret_type) {
+                self.breaking(format!(
+                    "Return type changed: `{}` ({} → {})",
+                    name,
+                    TypeDisplay(&base_method.ret_type),
+                    TypeDisplay(&method.ret_type)
+                ));
+            }
+        }
+
+        for method in current {
+            if !base.iter().any(|m| m.name == method.name) {
+                // New trait method must be implemented
+                self.rust_breaking(format!("Method added: `{}`", name_of(method)));
+            }
+        }
+    }
+
+    fn diff_signals(&mut self, base: &[Signal], current: &[Signal]) {
+        for base_signal in base {
+            let Some(signal) = current.iter().find(|s| s.name == base_signal.name) else {
+                self.breaking(format!("Signal removed: `{}`", base_signal.name));
+                continue;
+            };
+
+            let is_same = match (&base_signal.payload_type, &signal.payload_type) {
+                (Some(a), Some(b)) => is_same_type(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            };
+
+            if !is_same {
+                self.breaking(format!("Signal payload changed: `{}`", signal.name));
+            }
+        }
+
+        for signal in current {
+            if !base.iter().any(|s| s.name == signal.name) {
+                self.compatible(format!("Signal added: `{}`", signal.name));
+            }
+        }
+    }
+
+    fn diff_constants(&mut self, base: &[Prop], current: &[Prop]) {
+        for base_constant in base {
+            match current.iter().find(|c| c.name == base_constant.name) {
+                Some(constant) => {
+                    if !is_same_type(&base_constant.type_annotation, &constant.type_annotation) {
+                        self.breaking(format!(
+                            "Constant type changed: `{}` ({} → {})",
+                            constant.name,
+                            TypeDisplay(&base_constant.type_annotation),
+                            TypeDisplay(&constant.type_annotation)
+                        ));
+                    }
+                }
+                None => self.breaking(format!("Constant removed: `{}`", base_constant.name)),
+            }
+        }
+
+        for constant in current {
+            if !base.iter().any(|c| c.name == constant.name) {
+                // New field of the constants struct must be initialized
+                self.rust_breaking(format!("Constant added: `{}`", constant.name));
+            }
+        }
+    }
+
+    fn diff_object(
+        &mut self,
+        base: &ObjectTypeAnnotation,
+        current: &ObjectTypeAnnotation,
+        usage: Usage,
+    ) {
+        for base_prop in &base.props {
+            let Some(prop) = current.props.iter().find(|p| p.name == base_prop.name) else {
+                let message = format!("Property removed: `{}.{}`", base.name, base_prop.name);
+                self.classify(usage.output, usage.input, message);
+                continue;
+            };
+
+            if !is_same_type(&base_prop.type_annotation, &prop.type_annotation) {
+                self.breaking(format!(
+                    "Property type changed: `{}.{}` ({} → {})",
+                    current.name,
+                    prop.name,
+                    TypeDisplay(&base_prop.type_annotation),
+                    TypeDisplay(&prop.type_annotation)
+                ));
+            }
+        }
+
+        for prop in &current.props {
+            if base.props.iter().any(|p| p.name == prop.name) {
+                continue;
+            }
+
+            if usage.input && !prop.type_annotation.is_nullable() {
+                self.breaking(format!(
+                    "Required property added: `{}.{}`",
+                    current.name, prop.name
+                ));
+            } else {
+                let message = format!("Property added: `{}.{}`", current.name, prop.name);
+                self.classify(false, usage.output, message);
+            }
+        }
+    }
+
+    fn diff_enum(&mut self, base: &EnumTypeAnnotation, current: &EnumTypeAnnotation, usage: Usage) {
+        for base_member in &base.members {
+            let Some(member) = current.members.iter().find(|m| m.name == base_member.name) else {
+                let message = format!("Enum member removed: `{}.{}`", base.name, base_member.name);
+                self.classify(usage.input, usage.output, message);
+                continue;
+            };
+
+            if base_member.value != member.value {
+                self.breaking(format!(
+                    "Enum member value changed: `{}.{}`",
+                    current.name, member.name
+                ));
+            }
+        }
+
+        for member in &current.members {
+            if base.members.iter().any(|m| m.name == member.name) {
+                continue;
+            }
+
+            let message = format!("Enum member added: `{}.{}`", current.name, member.name);
+            self.classify(usage.output, usage.input, message);
+        }
+    }
+}
+
+/// Collects the direction of the object and enum types (key: type name).
+fn collect_usages(schema: &Schema, usages: &mut FxHashMap<String, Usage>) {
+    let methods = schema
+        .methods
+        .iter()
+        .chain(schema.objects.iter().flat_map(|object| &object.methods));
+
+    for method in methods {
+        for param in &method.params {
+            collect_usage(&param.type_annotation, true, usages);
+        }
+        collect_usage(&method.ret_type, false, usages);
+    }
+
+    for payload_type in schema
+        .signals
+        .iter()
+        .filter_map(|s| s.payload_type.as_ref())
+    {
+        collect_usage(payload_type, false, usages);
+    }
+
+    for constant in &schema.constants {
+        collect_usage(&constant.type_annotation, false, usages);
+    }
+}
+
+fn collect_usage(
+    type_annotation: &TypeAnnotation,
+    input: bool,
+    usages: &mut FxHashMap<String, Usage>,
+) {
+    let mut mark = |name: &str| {
+        let usage = usages.entry(name.to_string()).or_default();
+        match input {
+            true => usage.input = true,
+            false => usage.output = true,
+        }
+    };
+
+    match type_annotation {
+        TypeAnnotation::Object(obj) => {
+            mark(&obj.name);
+            for prop in &obj.props {
+                collect_usage(&prop.type_annotation, input, usages);
+            }
+        }
+        TypeAnnotation::Enum(enum_type) => mark(&enum_type.name),
+        TypeAnnotation::Array(inner)
+        | TypeAnnotation::Nullable(inner)
+        | TypeAnnotation::Promise(inner) => collect_usage(inner, input, usages),
+        _ => {}
+    }
+}
+
+/// Object, enum and native object types are compared by the name (the members are compared separately).
+/// Whether `nullable` is `T | null` of the `ty`.
+fn is_nullable_of(nullable: &TypeAnnotation, ty: &TypeAnnotation) -> bool {
+    matches!(nullable, TypeAnnotation::Nullable(inner) if is_same_type(inner, ty))
+}
+
+fn is_same_type(a: &TypeAnnotation, b: &TypeAnnotation) -> bool {
+    match (a, b) {
+        (TypeAnnotation::Object(a), TypeAnnotation::Object(b)) => a.name == b.name,
+        (TypeAnnotation::Enum(a), TypeAnnotation::Enum(b)) => a.name == b.name,
+        (TypeAnnotation::NativeObject(a), TypeAnnotation::NativeObject(b)) => a.name == b.name,
+        (TypeAnnotation::Array(a), TypeAnnotation::Array(b))
+        | (TypeAnnotation::Nullable(a), TypeAnnotation::Nullable(b))
+        | (TypeAnnotation::Promise(a), TypeAnnotation::Promise(b)) => is_same_type(a, b),
+        (a, b) => a == b,
+    }
+}
+
+/// TypeScript representation of the type (eg. `string[] | null`).
+struct TypeDisplay<'a>(&'a TypeAnnotation);
+
+impl Display for TypeDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            TypeAnnotation::Void => write!(f, "void"),
+            TypeAnnotation::Boolean => write!(f, "boolean"),
+            TypeAnnotation::Number => write!(f, "number"),
+            TypeAnnotation::String => write!(f, "string"),
+            TypeAnnotation::ArrayBuffer => write!(f, "ArrayBuffer"),
+            TypeAnnotation::Array(inner) => match &**inner {
+                TypeAnnotation::Nullable(..) => write!(f, "({})[]", TypeDisplay(inner)),
+                _ => write!(f, "{}[]", TypeDisplay(inner)),
+            },
+            TypeAnnotation::Nullable(inner) => write!(f, "{} | null", TypeDisplay(inner)),
+            TypeAnnotation::Promise(inner) => write!(f, "Promise<{}>", TypeDisplay(inner)),
+            TypeAnnotation::Object(ObjectTypeAnnotation { name, .. })
+            | TypeAnnotation::Enum(EnumTypeAnnotation { name, .. })
+            | TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name }) => {
+                write!(f, "{name}")
+            }
+            TypeAnnotation::Ref(type_ref) => write!(f, "{}", type_ref.name),
+            TypeAnnotation::Utility(utility) => write!(f, "{}<..>", utility.kind),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::native_spec_parser::try_parse_schema, utils::spec_source};
+
+    use super::*;
+
+    fn parse(spec: &str) -> Vec<Schema> {
+        try_parse_schema(&spec_source("TestModule", spec)).unwrap()
+    }
+
+    fn messages(changes: &[SchemaChange], kind: ChangeKind) -> Vec<&str> {
+        changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .map(|change| change.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_schemas() {
+        let base = parse(
+            "
+            enum Mode { A = 'a', B = 'b' }
+            enum Status { Ok = 0, Failed = 1 }
+            interface Options { mode: Mode; limit: number }
+            interface Result { status: Status; message: string }
+            export interface Spec extends NativeModule {
+                run(options: Options): Result;
+                stop(): void;
+                count(value: number): number;
+                limit(value: number): void;
+                onDone: Signal<Result>;
+            }
+            ",
+        );
+        let current = parse(
+            "
+            enum Mode { A = 'a', C = 'c' }
+            enum Status { Ok = 0, Failed = 1, Pending = 2 }
+            interface Options { mode: Mode; limit: number; timeout: number; label: string | null }
+            interface Result { status: Status; code: number }
+            export interface Spec extends NativeModule {
+                run(options: Options): Result;
+                count(value: string): number;
+                limit(value: number | null): void;
+                reset(): void;
+                onDone: Signal<Result>;
+                onError: Signal;
+            }
+            ",
+        );
+        let changes = diff_schemas(&base, &current);
+
+        assert_eq!(
+            messages(&changes, ChangeKind::Breaking),
+            vec![
+                "Parameter type changed: `count(value)` (number → string)",
+                "Method removed: `stop`",
+                "Required property added: `Options.timeout`",
+                "Property removed: `Result.message`",
+                "Enum member removed: `Mode.B`",
+                "Enum member added: `Status.Pending`",
+            ]
+        );
+        assert_eq!(
+            messages(&changes, ChangeKind::RustBreaking),
+            vec![
+                "Parameter type changed: `limit(value)` (number → number | null)",
+                "Method added: `reset`",
+                "Property added: `Result.code`",
+                "Enum member added: `Mode.C`",
+            ]
+        );
+        assert_eq!(
+            messages(&changes, ChangeKind::Compatible),
+            vec!["Signal added: `onError`", "Property added: `Options.label`"]
+        );
+    }
+
+    #[test]
+    fn test_diff_modules() {
+        let base = parse("export interface Spec extends NativeModule { foo(): void; }");
+
+        assert!(diff_schemas(
+            &base,
+            &parse("export interface Spec extends NativeModule { foo(): void; }")
+        )
+        .is_empty());
+        assert!(diff_schemas(&base, &[])
+            .iter()
+            .all(SchemaChange::is_breaking));
+        assert!(diff_schemas(&[], &base)
+            .iter()
+            .all(|change| !change.is_breaking()));
+    }
+}
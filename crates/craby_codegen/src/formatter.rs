@@ -0,0 +1,238 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use craby_common::config::FormatConfig;
+
+use crate::utils::run_with_input;
+
+/// Bundled clang-format style (same as the style of the C++ templates).
+pub const CLANG_FORMAT_STYLE: &str = "{BasedOnStyle: LLVM, ColumnLimit: 100, SortIncludes: Never}";
+
+/// Formats the generated files (`[format]` in `craby.toml`).
+///
+/// The formatters are deterministic, so the formatted files can be compared with the files on disk.
+pub struct Formatter {
+    rust: bool,
+    cxx: bool,
+    clang_format_style: Option<PathBuf>,
+    /// Output of `clang-format --version` (only if `cxx` is enabled)
+    clang_format_version: Option<String>,
+}
+
+impl Formatter {
+    pub fn new(project_root: &Path, config: &FormatConfig) -> Self {
+        Formatter {
+            rust: config.rust,
+            cxx: config.cxx,
+            clang_format_style: config
+                .clang_format_style
+                .as_ref()
+                .map(|style| project_root.join(style)),
+            clang_format_version: config.cxx.then(clang_format_version).flatten(),
+        }
+    }
+
+    /// Version of clang-format used to format the C++ code (the output depends on the version).
+    pub fn clang_format_version(&self) -> Option<&str> {
+        self.clang_format_version.as_deref()
+    }
+
+    /// Settings that affect the formatted code (the same code is always formatted the same with the same settings).
+    pub fn fingerprint(&self) -> String {
+        let style = match &self.clang_format_style {
+            Some(style_file) => fs::read_to_string(style_file).unwrap_or_default(),
+            None => CLANG_FORMAT_STYLE.to_string(),
+        };
+
+        format!(
+            "craby_codegen={} rust={} cxx={} clang_format={:?} style={}",
+            env!("CARGO_PKG_VERSION"),
+            self.rust,
+            self.cxx,
+            self.clang_format_version,
+            style
+        )
+    }
+
+    /// Formats the code by the file extension (the others are returned as is).
+    pub fn format(&self, path: &Path, code: &str) -> Result<String, anyhow::Error> {
+        let ext = path.extension().and_then(|ext| ext.to_str());
+
+        let formatted = match ext {
+            Some("rs") if self.rust => format_rs(code),
+            Some("cpp" | "hpp" | "h" | "mm") if self.cxx => {
+                format_cxx(path, code, self.clang_format_style.as_deref())
+            }
+            _ => return Ok(code.to_string()),
+        };
+
+        formatted.map_err(|e| anyhow::anyhow!("Failed to format {}: {}", path.display(), e))
+    }
+}
+
+/// Formats the Rust code with prettyplease.
+///
+/// Line comments are dropped by prettyplease, so the leading ones (eg. `// Hash: ...`) are kept as is
+/// and the others (eg. `// TODO` in the function bodies) are removed. Doc comments (`///`) are kept.
+pub fn format_rs(code: &str) -> Result<String, anyhow::Error> {
+    let header_len = code
+        .split_inclusive('\n')
+        .take_while(|line| line.starts_with("//") && !line.starts_with("///"))
+        .map(str::len)
+        .sum::<usize>();
+    let (header, body) = code.split_at(header_len);
+    let file = syn::parse_file(body)?;
+
+    Ok(format!(
+        "{}{}",
+        header,
+        prettyplease::unparse(&file).trim_end()
+    ))
+}
+
+/// Returns the output of `clang-format --version` (eg. `clang-format version 18.1.3`), `None` if not installed.
+pub fn clang_format_version() -> Option<String> {
+    let output = Command::new("clang-format")
+        .arg("--version")
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Formats the C++ code with clang-format (uses the bundled style if `style_file` is not set).
+pub fn format_cxx(
+    path: &Path,
+    code: &str,
+    style_file: Option<&Path>,
+) -> Result<String, anyhow::Error> {
+    let style = match style_file {
+        Some(style_file) => format!("--style=file:{}", style_file.display()),
+        None => format!("--style={}", CLANG_FORMAT_STYLE),
+    };
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    let output = run_with_input(
+        Command::new("clang-format")
+            .arg(style)
+            .arg(format!("--assume-filename={}", file_name))
+            .stderr(Stdio::piped()),
+        code.to_string(),
+    )
+    .map_err(|e| match e.kind() {
+        ErrorKind::NotFound => anyhow::anyhow!(
+            "clang-format not found. Please install clang-format or disable `format.cxx` in craby.toml"
+        ),
+        _ => e.into(),
+    })?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "clang-format exited with code {}\n{}",
+            output.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        );
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use craby_common::constants::HASH_COMMENT_PREFIX;
+
+    use crate::{
+        generators::{rs_generator::RsGenerator, types::GeneratorInvoker},
+        tests::get_codegen_context,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_format_rs() {
+        let code = indoc! {"
+            // Hash: 437ac39b257d2f99
+            #![allow(deprecated)]
+            use craby::prelude::*;
+            pub trait CalculatorSpec {
+                /// Adds the numbers.
+                  fn add(&mut self,a: Number,   b: Number) -> Number;
+            fn emit(&self) {
+            unimplemented!();
+                }
+            }"
+        };
+
+        let formatted = format_rs(code).unwrap();
+
+        assert_eq!(
+            formatted,
+            indoc! {"
+                // Hash: 437ac39b257d2f99
+                #![allow(deprecated)]
+                use craby::prelude::*;
+                pub trait CalculatorSpec {
+                    /// Adds the numbers.
+                    fn add(&mut self, a: Number, b: Number) -> Number;
+                    fn emit(&self) {
+                        unimplemented!();
+                    }
+                }"
+            }
+        );
+        assert_eq!(format_rs(&formatted).unwrap(), formatted);
+        assert!(format_rs("fn invalid(").is_err());
+    }
+
+    #[test]
+    fn test_formatter() {
+        let code = "fn   add() {}";
+        let formatter = Formatter::new(
+            Path::new("."),
+            &FormatConfig {
+                rust: true,
+                cxx: false,
+                clang_format_style: None,
+            },
+        );
+
+        assert_eq!(
+            formatter.format(Path::new("src/lib.rs"), code).unwrap(),
+            "fn add() {}"
+        );
+        // Disabled
+        assert_eq!(
+            formatter.format(Path::new("cpp/Module.cpp"), code).unwrap(),
+            code
+        );
+        // Unknown file types
+        assert_eq!(
+            formatter.format(Path::new("CMakeLists.txt"), code).unwrap(),
+            code
+        );
+    }
+
+    #[test]
+    fn test_format_generated_rs() {
+        let ctx = get_codegen_context();
+        let results = RsGenerator::new().invoke_generate(&ctx).unwrap();
+
+        for res in results {
+            let formatted = format_rs(&res.content).unwrap();
+            assert_eq!(
+                res.content.starts_with(HASH_COMMENT_PREFIX),
+                formatted.starts_with(HASH_COMMENT_PREFIX)
+            );
+        }
+    }
+}
@@ -295,6 +295,7 @@ impl AndroidTemplate {
     ///   # android
     ///   ReactAndroid::reactnative
     ///   ReactAndroid::jsi
+    ///   log
     ///   # my-app-lib
     ///   my-app-lib
     /// )
@@ -356,6 +357,7 @@ impl AndroidTemplate {
               # android
               ReactAndroid::reactnative
               ReactAndroid::jsi
+              log
               # {kebab_name}-lib
               {kebab_name}-lib
             )
@@ -516,7 +518,7 @@ impl AndroidGenerator {
 }
 
 impl Generator<AndroidTemplate> for AndroidGenerator {
-    fn cleanup(_: &CodegenContext) -> Result<(), anyhow::Error> {
+    fn cleanup(_: &CodegenContext, _: &[TemplateResult]) -> Result<(), anyhow::Error> {
         Ok(())
     }
 
@@ -8,8 +8,12 @@ use indoc::formatdoc;
 
 use crate::{
     constants::specs::RESERVED_ARG_NAME_MODULE,
-    platform::cxx::CxxMethod,
-    types::{CodegenContext, CxxModuleName, CxxNamespace, Schema},
+    parser::types::Doc,
+    platform::{
+        cxx::{cxx_ident, with_cxx_doc, CxxMethod},
+        rust::bridge_ident,
+    },
+    types::{CodegenContext, CxxModuleName, CxxNamespace, MethodAttrsMap, Schema},
     utils::indent_str,
 };
 
@@ -27,6 +31,8 @@ pub enum CxxFileType {
     UtilsHpp,
     /// CrabySignals.h
     SignalsH,
+    /// CrabyInvoker.h
+    InvokerH,
 }
 
 impl CxxTemplate {
@@ -40,13 +46,20 @@ impl CxxTemplate {
         &self,
         project_name: &str,
         schema: &Schema,
+        method_attrs: &MethodAttrsMap,
     ) -> Result<Vec<CxxMethod>, anyhow::Error> {
         let cxx_ns = CxxNamespace::from(project_name);
         let mod_name = CxxModuleName::from(&schema.module_name);
         let res = schema
             .methods
             .iter()
-            .map(|spec| spec.as_cxx_method(&cxx_ns, &mod_name))
+            .map(|spec| {
+                let attrs = method_attrs
+                    .get(&snake_case(&spec.name))
+                    .cloned()
+                    .unwrap_or_default();
+                spec.as_cxx_method(&cxx_ns, &mod_name, &attrs)
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(res)
@@ -60,15 +73,17 @@ impl CxxTemplate {
     ///        facebook::react::TurboModule &turboModule,
     ///        const facebook::jsi::Value args[], size_t count);
     /// ```
-    fn cxx_method_def(&self, name: &str) -> String {
-        let method_name = camel_case(name);
-        formatdoc! {
+    fn cxx_method_def(&self, name: &str, doc: &Option<Doc>) -> String {
+        let method_name = cxx_ident(&camel_case(name));
+        let method_def = formatdoc! {
             r#"
             static facebook::jsi::Value
             {method_name}(facebook::jsi::Runtime &rt,
                 facebook::react::TurboModule &turboModule,
                 const facebook::jsi::Value args[], size_t count);"#,
-        }
+        };
+
+        with_cxx_doc(doc, method_def)
     }
 
     /// Returns the complete cxx TurboModule source/header files.
@@ -148,11 +163,12 @@ impl CxxTemplate {
         &self,
         schema: &Schema,
         project_name: &str,
+        method_attrs: &MethodAttrsMap,
     ) -> Result<(String, String), anyhow::Error> {
         let cxx_ns = CxxNamespace::from(project_name);
         let cxx_mod = CxxModuleName::from(&schema.module_name);
         let project_ns = flat_case(project_name);
-        let cxx_methods = self.cxx_methods(project_name, schema)?;
+        let cxx_methods = self.cxx_methods(project_name, schema, method_attrs)?;
         let include_stmt = format!("#include \"{cxx_mod}.hpp\"");
 
         // Assign method metadata with function pointer to the TurboModule's method map
@@ -167,7 +183,7 @@ impl CxxTemplate {
 
         let mut method_defs = cxx_methods
             .iter()
-            .map(|method| self.cxx_method_def(&method.name))
+            .map(|method| self.cxx_method_def(&method.name, &method.doc))
             .collect::<Vec<_>>();
 
         // Functions implementations
@@ -196,7 +212,6 @@ impl CxxTemplate {
             let register_stmt = if let Some(ref signal_enum) = signal_enum_name {
                 formatdoc! {
                     r#"
-                    uintptr_t id = reinterpret_cast<uintptr_t>(this);
                     auto& manager = {cxx_ns}::signals::SignalManager::getInstance();
                     manager.registerDelegate(id,
                       [this](const std::string& name, void* signal) {{
@@ -212,7 +227,6 @@ impl CxxTemplate {
             let unregister_stmt = formatdoc! {
                 r#"
                 // Unregister from signal manager
-                uintptr_t id = reinterpret_cast<uintptr_t>(this);
                 auto& manager = {cxx_ns}::signals::SignalManager::getInstance();
                 manager.unregisterDelegate(id);"#,
             };
@@ -451,27 +465,128 @@ impl CxxTemplate {
             (String::from("// No signals"), String::from("// No signals"))
         };
 
+        // Schedule the tasks posted by `Invoker` on the JS thread
+        let register_invoker_stmt = formatdoc! {
+            r#"
+            auto& invokerManager = {cxx_ns}::invoker::InvokerManager::getInstance();
+            invokerManager.registerDelegate(id,
+              [this](bridging::JsTask* task) {{
+                callInvoker_->invokeAsync([task](jsi::Runtime &rt) {{
+                  {cxx_ns}::bridging::run_js_task(task);
+                }});
+              }}
+            );"#,
+        };
+
+        let unregister_invoker_stmt = formatdoc! {
+            r#"
+            // Unregister from invoker manager
+            auto& invokerManager = {cxx_ns}::invoker::InvokerManager::getInstance();
+            invokerManager.unregisterDelegate(id);"#,
+        };
+
+        // Host object classes of the native objects returned by the module
+        let (host_object_decls, host_object_impls) = schema
+            .objects
+            .iter()
+            .map(|object| object.as_cxx_host_object(&cxx_ns))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|host_object| (host_object.decl, host_object.impl_class))
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        // Module constants are exposed as the properties of the module
+        //
+        // ```cpp
+        // jsi::Value CxxMyTestModule::get(jsi::Runtime &rt, const jsi::PropNameID &propName) {
+        //   auto name = propName.utf8(rt);
+        //   if (name == "VERSION") {
+        //     return react::bridging::toJs(rt, constants_.version);
+        //   }
+        //   return TurboModule::get(rt, propName);
+        // }
+        // ```
+        let (constants_stmt, constants_def, constants_member) = match schema.constants_type() {
+            Some(constants_type) => {
+                let constants_name = &constants_type.as_object().unwrap().name;
+                let getters = schema
+                    .constants
+                    .iter()
+                    .map(|constant| {
+                        formatdoc! {
+                            r#"
+                            if (name == "{name}") {{
+                              return react::bridging::toJs(rt, constants_.{field});
+                            }}"#,
+                            name = constant.name,
+                            field = bridge_ident(&snake_case(&constant.name)),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                method_impls.insert(
+                    0,
+                    formatdoc! {
+                        r#"
+                        jsi::Value {cxx_mod}::get(jsi::Runtime &rt, const jsi::PropNameID &propName) {{
+                          auto name = propName.utf8(rt);
+                        {getters}
+                          return TurboModule::get(rt, propName);
+                        }}"#,
+                        getters = indent_str(&getters, 2),
+                    },
+                );
+
+                let constants_stmt = formatdoc! {
+                    r#"
+                    constants_ = {cxx_ns}::bridging::get{constants_name}(
+                      id,
+                      rust::Str(dataPath.data(), dataPath.size()));"#,
+                };
+                let constants_def = formatdoc! {
+                    r#"
+                    facebook::jsi::Value get(facebook::jsi::Runtime &rt,
+                                             const facebook::jsi::PropNameID &propName) override;"#,
+                };
+
+                (
+                    format!("{}\n", indent_str(&constants_stmt, 2)),
+                    format!("{}\n\n", indent_str(&constants_def, 2)),
+                    format!("\n  {cxx_ns}::bridging::{constants_name} constants_;"),
+                )
+            }
+            None => (String::new(), String::new(), String::new()),
+        };
+
         let rs_module_name = pascal_case(&schema.module_name);
-        let register_stmts = indent_str(&register_stmt, 2);
-        let unregister_stmts = indent_str(&unregister_stmt, 2);
+        let register_stmts = indent_str(&[register_stmt, register_invoker_stmt].join("\n\n"), 2);
+        let unregister_stmts =
+            indent_str(&[unregister_stmt, unregister_invoker_stmt].join("\n\n"), 2);
         let method_mapping_stmts = indent_str(&method_maps.join("\n"), 2);
         let method_impls = method_impls.join("\n\n");
+        let host_object_impls = host_object_impls
+            .into_iter()
+            .map(|code| format!("{code}\n\n"))
+            .collect::<String>();
         let cpp = formatdoc! {
             r#"
-            std::string {cxx_mod}::dataPath = std::string();
+            {host_object_impls}std::string {cxx_mod}::dataPath = std::string();
 
             {cxx_mod}::{cxx_mod}(
                 std::shared_ptr<react::CallInvoker> jsInvoker)
                 : TurboModule({cxx_mod}::kModuleName, jsInvoker) {{
-            {register_stmts}
               callInvoker_ = std::move(jsInvoker);
-              module_ = std::shared_ptr<{cxx_ns}::bridging::{rs_module_name}>(
+              uintptr_t id = reinterpret_cast<uintptr_t>(this);
+            {register_stmts}
+            {constants_stmt}  module_ = std::shared_ptr<{cxx_ns}::bridging::{rs_module_name}>(
                 {cxx_ns}::bridging::create{rs_module_name}(
-                  reinterpret_cast<uintptr_t>(this),
+                  id,
                   rust::Str(dataPath.data(), dataPath.size())).into_raw(),
                 []({cxx_ns}::bridging::{rs_module_name} *ptr) {{ rust::Box<{cxx_ns}::bridging::{rs_module_name}>::from_raw(ptr); }}
               );
               threadPool_ = std::make_shared<{cxx_ns}::utils::ThreadPool>(10);
+              timer_ = std::make_shared<{cxx_ns}::utils::Timer>();
             {method_mapping_stmts}
             }}
 
@@ -486,20 +601,29 @@ impl CxxTemplate {
 
               invalidated_.store(true);
               listenersMap_.clear();
+
+              // Cancel the tasks spawned by the module
+              uintptr_t id = reinterpret_cast<uintptr_t>(this);
+              {cxx_ns}::bridging::invalidateModule(id);
             
             {unregister_stmts}
 
-              // Shutdown thread pool
+              // Shutdown thread pool and timer
               threadPool_->shutdown();
+              timer_->shutdown();
             }}
             
             {method_impls}"#,
         };
 
         let method_defs = indent_str(&method_defs.join("\n\n"), 2);
+        let host_object_decls = host_object_decls
+            .into_iter()
+            .map(|code| format!("{code}\n\n"))
+            .collect::<String>();
         let hpp = formatdoc! {
             r#"
-            class JSI_EXPORT {cxx_mod} : public facebook::react::TurboModule {{
+            {host_object_decls}class JSI_EXPORT {cxx_mod} : public facebook::react::TurboModule {{
             public:
               static constexpr const char *kModuleName = "{turbo_module_name}";
               static std::string dataPath;
@@ -508,7 +632,7 @@ impl CxxTemplate {
               ~{cxx_mod}();
 
               void invalidate();
-            {method_defs}
+            {constants_def}{method_defs}
 
             protected:
               std::shared_ptr<facebook::react::CallInvoker> callInvoker_;
@@ -521,6 +645,7 @@ impl CxxTemplate {
                 std::unordered_map<size_t, std::shared_ptr<facebook::jsi::Function>>>
                 listenersMap_;
               std::shared_ptr<{cxx_ns}::utils::ThreadPool> threadPool_;
+              std::shared_ptr<{cxx_ns}::utils::Timer> timer_;{constants_member}
             }};"#,
             turbo_module_name = schema.module_name,
         };
@@ -603,12 +728,19 @@ impl CxxTemplate {
     /// } // namespace facebook
     /// ```
     fn cxx_bridging(&self, ctx: &CodegenContext) -> Result<String, anyhow::Error> {
-        let bridging_templates = ctx
-            .schemas
-            .iter()
-            .flat_map(|schema| schema.as_cxx_bridging_templates(&ctx.project_name))
-            .flatten()
-            .collect::<Vec<_>>();
+        // Types shared by the modules are specialized once
+        let mut bridging_templates = vec![];
+        for schema in &ctx.schemas {
+            for template in schema
+                .as_cxx_bridging_templates(&ctx.project_name)
+                .into_iter()
+                .flatten()
+            {
+                if !bridging_templates.contains(&template) {
+                    bridging_templates.push(template);
+                }
+            }
+        }
 
         let cxx_bridging = formatdoc! {
             r#"
@@ -844,13 +976,21 @@ impl CxxTemplate {
 
             #include "cxx.h"
             #include "ffi.rs.h"
+            #include <atomic>
+            #include <chrono>
             #include <condition_variable>
+            #include <cstdio>
             #include <functional>
+            #include <map>
             #include <mutex>
             #include <queue>
             #include <thread>
             #include <vector>
 
+            #ifdef __ANDROID__
+            #include <android/log.h>
+            #endif
+
             namespace craby {{
             namespace {flat_name} {{
             namespace utils {{
@@ -927,6 +1067,111 @@ impl CxxTemplate {
               return std::string(rs_err ? rs_err->what() : err.what());
             }}
 
+            class Trace {{
+            private:
+              const char *moduleName;
+              const char *methodName;
+              std::chrono::steady_clock::time_point start;
+
+            public:
+              Trace(const char *moduleName, const char *methodName)
+                  : moduleName(moduleName), methodName(methodName),
+                    start(std::chrono::steady_clock::now()) {{}}
+
+              ~Trace() {{
+                auto elapsed = std::chrono::duration<double, std::milli>(
+                                   std::chrono::steady_clock::now() - start)
+                                   .count();
+            #ifdef __ANDROID__
+                __android_log_print(ANDROID_LOG_DEBUG, "Craby", "%s.%s took %.3fms",
+                                    moduleName, methodName, elapsed);
+            #else
+                std::fprintf(stderr, "[Craby] %s.%s took %.3fms\n", moduleName,
+                             methodName, elapsed);
+            #endif
+              }}
+            }};
+
+            class Settlement {{
+            private:
+              std::atomic<bool> settled{{false}};
+
+            public:
+              // Returns `true` only for the first call
+              bool settle() {{
+                return !settled.exchange(true);
+              }}
+            }};
+
+            // Runs the scheduled tasks on a single thread (started on the first `schedule` call)
+            class Timer {{
+            private:
+              bool stop = false;
+              std::mutex mutex;
+              std::condition_variable condition;
+              std::multimap<std::chrono::steady_clock::time_point, std::function<void()>> tasks;
+              std::thread worker;
+
+              void run() {{
+                while (true) {{
+                  std::function<void()> task;
+
+                  {{
+                    std::unique_lock<std::mutex> lock(mutex);
+                    condition.wait(lock, [this] {{ return stop || !tasks.empty(); }});
+
+                    if (stop) {{
+                      return;
+                    }}
+
+                    auto next = tasks.begin();
+                    if (std::chrono::steady_clock::now() < next->first) {{
+                      condition.wait_until(lock, next->first);
+                      continue;
+                    }}
+
+                    task = std::move(next->second);
+                    tasks.erase(next);
+                  }}
+
+                  task();
+                }}
+              }}
+
+            public:
+              template <class F> void schedule(std::chrono::milliseconds delay, F &&f) {{
+                {{
+                  std::unique_lock<std::mutex> lock(mutex);
+                  if (stop) {{
+                    return;
+                  }}
+                  if (!worker.joinable()) {{
+                    worker = std::thread([this] {{ run(); }});
+                  }}
+                  tasks.emplace(std::chrono::steady_clock::now() + delay, std::forward<F>(f));
+                }}
+                condition.notify_one();
+              }}
+
+              void shutdown() {{
+                {{
+                  std::unique_lock<std::mutex> lock(mutex);
+                  stop = true;
+                  tasks.clear();
+                }}
+
+                condition.notify_all();
+
+                if (worker.joinable()) {{
+                  worker.join();
+                }}
+              }}
+
+              ~Timer() {{
+                shutdown();
+              }}
+            }};
+
             }} // namespace utils
             }} // namespace {flat_name}
             }} // namespace craby"#,
@@ -1106,6 +1351,120 @@ impl CxxTemplate {
           },
       })
   }
+
+    /// Generates the invoker manager header file for scheduling tasks on the JS thread.
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// #pragma once
+    ///
+    /// #include <cstdint>
+    /// #include <functional>
+    /// #include <mutex>
+    /// #include <unordered_map>
+    ///
+    /// namespace craby {
+    /// namespace mymodule {
+    /// namespace bridging {
+    ///   struct JsTask;
+    /// }
+    /// }
+    /// }
+    ///
+    /// namespace craby {
+    /// namespace mymodule {
+    /// namespace invoker {
+    ///
+    /// using Delegate = std::function<void(bridging::JsTask* task)>;
+    ///
+    /// class InvokerManager {
+    /// public:
+    ///   static InvokerManager& getInstance() {
+    ///     static InvokerManager instance;
+    ///     return instance;
+    ///   }
+    ///
+    ///   bool invoke(uintptr_t id, bridging::JsTask* task) const {
+    ///     // ...
+    ///   }
+    ///
+    ///   // ...
+    /// };
+    ///
+    /// } // namespace invoker
+    /// } // namespace mymodule
+    /// } // namespace craby
+    /// ```
+    fn cxx_invoker(&self, project_name: &str) -> Result<String, anyhow::Error> {
+        let flat_name = flat_case(project_name);
+
+        Ok(formatdoc! {
+            r#"
+            #pragma once
+
+            #include <cstdint>
+            #include <functional>
+            #include <mutex>
+            #include <unordered_map>
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace bridging {{
+              struct JsTask;
+            }}
+            }}
+            }}
+
+            namespace craby {{
+            namespace {flat_name} {{
+            namespace invoker {{
+
+            using Delegate = std::function<void(bridging::JsTask* task)>;
+
+            class InvokerManager {{
+            public:
+              static InvokerManager& getInstance() {{
+                static InvokerManager instance;
+                return instance;
+              }}
+
+              bool invoke(uintptr_t id, bridging::JsTask* task) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                auto it = delegates_.find(id);
+                if (it == delegates_.end()) {{
+                  return false;
+                }}
+
+                it->second(task);
+                return true;
+              }}
+
+              void registerDelegate(uintptr_t id, Delegate delegate) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                delegates_.insert_or_assign(id, delegate);
+              }}
+
+              void unregisterDelegate(uintptr_t id) const {{
+                std::lock_guard<std::mutex> lock(mutex_);
+                delegates_.erase(id);
+              }}
+
+            private:
+              InvokerManager() = default;
+              mutable std::unordered_map<uintptr_t, Delegate> delegates_;
+              mutable std::mutex mutex_;
+            }};
+
+            inline const InvokerManager& getInvokerManager() {{
+              return InvokerManager::getInstance();
+            }}
+
+            }} // namespace invoker
+            }} // namespace {flat_name}
+            }} // namespace craby"#,
+        })
+    }
 }
 
 impl Template for CxxTemplate {
@@ -1121,7 +1480,11 @@ impl Template for CxxTemplate {
                 .schemas
                 .iter()
                 .map(|schema| -> Result<Vec<TemplateResult>, anyhow::Error> {
-                    let (cpp, hpp) = self.cxx_mod(schema, &ctx.project_name)?;
+                    let (cpp, hpp) = self.cxx_mod(
+                        schema,
+                        &ctx.project_name,
+                        &ctx.method_attrs_of(&schema.module_name),
+                    )?;
                     let cxx_mod = CxxModuleName::from(&schema.module_name);
                     let cxx_base_path = cxx_dir(&ctx.root);
                     let files = vec![
@@ -1163,6 +1526,11 @@ impl Template for CxxTemplate {
                     Vec::default()
                 }
             }
+            CxxFileType::InvokerH => vec![TemplateResult {
+                path: cxx_bridge_include_dir(&ctx.root).join("CrabyInvoker.h"),
+                content: self.cxx_invoker(&ctx.project_name)?,
+                overwrite: true,
+            }],
         };
 
         Ok(res)
@@ -1182,7 +1550,7 @@ impl CxxGenerator {
 }
 
 impl Generator<CxxTemplate> for CxxGenerator {
-    fn cleanup(ctx: &CodegenContext) -> Result<(), anyhow::Error> {
+    fn cleanup(ctx: &CodegenContext, files: &[TemplateResult]) -> Result<(), anyhow::Error> {
         let cxx_dir = cxx_dir(&ctx.root);
 
         if cxx_dir.try_exists()? {
@@ -1192,6 +1560,7 @@ impl Generator<CxxTemplate> for CxxGenerator {
 
                 if file_name.starts_with("Cxx")
                     && (file_name.ends_with("Module.cpp") || file_name.ends_with("Module.hpp"))
+                    && !files.iter().any(|res| res.path == path)
                 {
                     fs::remove_file(&path)?;
                 }
@@ -1210,6 +1579,7 @@ impl Generator<CxxTemplate> for CxxGenerator {
             template.render(ctx, &CxxFileType::BridgingHpp)?,
             template.render(ctx, &CxxFileType::UtilsHpp)?,
             template.render(ctx, &CxxFileType::SignalsH)?,
+            template.render(ctx, &CxxFileType::InvokerH)?,
         ]
         .into_iter()
         .flatten()
@@ -1233,7 +1603,7 @@ impl GeneratorInvoker for CxxGenerator {
 mod tests {
     use insta::assert_snapshot;
 
-    use crate::tests::get_codegen_context;
+    use crate::{tests::get_codegen_context, types::MethodAttrs};
 
     use super::*;
 
@@ -1250,4 +1620,35 @@ mod tests {
 
         assert_snapshot!(result);
     }
+
+    #[test]
+    fn test_sync_method_attrs() {
+        let generator = CxxGenerator::new();
+
+        for (attrs, message) in [
+            (
+                MethodAttrs {
+                    blocking: true,
+                    ..Default::default()
+                },
+                "`blocking` method must return a Promise in the spec: numericMethod",
+            ),
+            (
+                MethodAttrs {
+                    timeout: Some(1000),
+                    ..Default::default()
+                },
+                "`timeout` is only supported for Promise methods: numericMethod",
+            ),
+        ] {
+            let mut ctx = get_codegen_context();
+            ctx.method_attrs
+                .get_mut("CrabyTest")
+                .unwrap()
+                .insert("numeric_method".to_string(), attrs);
+
+            let err = generator.generate(&ctx).unwrap_err();
+            assert_eq!(err.to_string(), message);
+        }
+    }
 }
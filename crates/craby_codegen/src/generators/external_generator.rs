@@ -0,0 +1,198 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use craby_common::config::GeneratorConfig;
+use log::debug;
+use serde::Deserialize;
+
+use crate::{
+    generators::types::TemplateResult, ir::SchemaIr, types::CodegenContext, utils::run_with_input,
+};
+
+use super::types::GeneratorInvoker;
+
+/// Generator implemented by an external executable (`[[generators]]` in `craby.toml`).
+///
+/// The executable runs in the project root, receives the schema IR on stdin and prints the files to generate on stdout.
+///
+/// ```json
+/// [{ "path": "src/telemetry.ts", "content": "...", "overwrite": true }]
+/// ```
+pub struct ExternalGenerator {
+    config: GeneratorConfig,
+}
+
+/// File printed by the external generator.
+#[derive(Deserialize)]
+struct ExternalFile {
+    /// Path relative to the project root
+    path: PathBuf,
+    content: String,
+    #[serde(default = "default_overwrite")]
+    overwrite: bool,
+}
+
+fn default_overwrite() -> bool {
+    true
+}
+
+impl ExternalGenerator {
+    pub fn new(config: GeneratorConfig) -> Self {
+        Self { config }
+    }
+
+    fn program(&self, project_root: &Path) -> PathBuf {
+        let command = Path::new(&self.config.command);
+
+        // Commands without a path (eg. `node`) are resolved by the `PATH`
+        if command.components().count() > 1 {
+            project_root.join(command)
+        } else {
+            command.to_path_buf()
+        }
+    }
+
+    /// Parses the files printed by the generator (paths must be relative to the project root).
+    fn parse_output(
+        &self,
+        project_root: &Path,
+        stdout: &[u8],
+    ) -> Result<Vec<TemplateResult>, anyhow::Error> {
+        let name = &self.config.name;
+        let files = serde_json::from_slice::<Vec<ExternalFile>>(stdout)
+            .map_err(|e| anyhow::anyhow!("Invalid output of the generator `{}`: {}", name, e))?;
+
+        files
+            .into_iter()
+            .map(|file| {
+                // Generators can only write the files in the project
+                if !file
+                    .path
+                    .components()
+                    .all(|component| matches!(component, Component::Normal(_)))
+                {
+                    anyhow::bail!(
+                        "Invalid path from the generator `{}`: {} (must be relative to the project root)",
+                        name,
+                        file.path.display()
+                    );
+                }
+
+                Ok(TemplateResult {
+                    path: project_root.join(file.path),
+                    content: file.content,
+                    overwrite: file.overwrite,
+                })
+            })
+            .collect()
+    }
+}
+
+impl GeneratorInvoker for ExternalGenerator {
+    fn invoke_generate(&self, ctx: &CodegenContext) -> Result<Vec<TemplateResult>, anyhow::Error> {
+        let name = &self.config.name;
+        let input = SchemaIr::new(ctx.schemas.clone()).to_json()?;
+
+        debug!("Running generator: {} ({})", name, self.config.command);
+        let output = run_with_input(
+            Command::new(self.program(&ctx.root))
+                .args(&self.config.args)
+                .current_dir(&ctx.root)
+                .stderr(Stdio::inherit()),
+            input,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to run the generator `{}`: {}", name, e))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Generator `{}` exited with code {}",
+                name,
+                output.status.code().unwrap_or(-1)
+            );
+        }
+
+        self.parse_output(&ctx.root, &output.stdout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use crate::tests::get_codegen_context;
+
+    use super::*;
+
+    fn generator(command: &str, args: Vec<String>) -> ExternalGenerator {
+        ExternalGenerator::new(GeneratorConfig {
+            name: "test".to_string(),
+            command: command.to_string(),
+            args,
+        })
+    }
+
+    #[cfg(unix)]
+    fn generate(script: &str) -> Result<Vec<TemplateResult>, anyhow::Error> {
+        generator("sh", vec!["-c".to_string(), script.to_string()])
+            .invoke_generate(&get_codegen_context())
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let root = Path::new("project");
+        let generator = generator("node", vec![]);
+        let res = generator
+            .parse_output(
+                root,
+                br#"[{ "path": "src/a.ts", "content": "a" }, { "path": "b.ts", "content": "b", "overwrite": false }]"#,
+            )
+            .unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].path, root.join("src").join("a.ts"));
+        assert_eq!(res[0].content, "a");
+        assert!(res[0].overwrite);
+        assert!(!res[1].overwrite);
+
+        assert!(generator.parse_output(root, b"invalid").is_err());
+        assert!(generator
+            .parse_output(root, br#"[{ "path": "../a.ts", "content": "" }]"#)
+            .unwrap_err()
+            .to_string()
+            .contains("must be relative to the project root"));
+        assert!(generator
+            .parse_output(root, br#"[{ "path": "/tmp/a.ts", "content": "" }]"#)
+            .is_err());
+
+        // Commands without a path are resolved by the `PATH`
+        assert_eq!(generator.program(root), PathBuf::from("node"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_external_generator() {
+        let res = generate(
+            r#"grep -q '"module_name": "CrabyTest"' && echo '[{ "path": "src/a.ts", "content": "a" }, { "path": "b.ts", "content": "b", "overwrite": false }]'"#,
+        )
+        .unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].path, PathBuf::from("./src/a.ts"));
+        assert_eq!(res[0].content, "a");
+        assert!(res[0].overwrite);
+        assert!(!res[1].overwrite);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_invalid_external_generator() {
+        assert!(generate("exit 1")
+            .unwrap_err()
+            .to_string()
+            .contains("exited with code 1"));
+        assert!(generate("echo 'invalid'").is_err());
+        assert!(generate(r#"echo '[{ "path": "../a.ts", "content": "" }]'"#).is_err());
+        assert!(generate(r#"echo '[{ "path": "/tmp/a.ts", "content": "" }]'"#).is_err());
+    }
+}
@@ -187,7 +187,7 @@ impl IosGenerator {
 }
 
 impl Generator<IosTemplate> for IosGenerator {
-    fn cleanup(ctx: &CodegenContext) -> Result<(), anyhow::Error> {
+    fn cleanup(ctx: &CodegenContext, files: &[TemplateResult]) -> Result<(), anyhow::Error> {
         let src_path = ios_base_path(&ctx.root).join("src");
 
         if src_path.try_exists()? {
@@ -195,7 +195,7 @@ impl Generator<IosTemplate> for IosGenerator {
                 let path = entry?.path();
                 let file_name = path.file_name().unwrap().to_string_lossy().to_string();
 
-                if file_name.ends_with(".mm") {
+                if file_name.ends_with(".mm") && !files.iter().any(|res| res.path == path) {
                     fs::remove_file(&path)?;
                 }
 
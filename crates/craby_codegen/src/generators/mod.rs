@@ -1,5 +1,6 @@
 pub mod android_generator;
 pub mod cxx_generator;
+pub mod external_generator;
 pub mod ios_generator;
 pub mod rs_generator;
 
@@ -5,11 +5,12 @@ use craby_common::{
     utils::string::{pascal_case, snake_case},
 };
 use indoc::formatdoc;
+use rustc_hash::FxHashSet;
 
 use crate::{
     common::IntoCode,
     generators::types::TemplateResult,
-    platform::rust::RsCxxBridge,
+    platform::rust::{with_rs_doc, RsCxxBridge},
     types::{CodegenContext, CxxNamespace, Schema},
     utils::indent_str,
 };
@@ -54,7 +55,8 @@ impl RsTemplate {
     /// ```rust,ignore
     /// #[cxx::bridge(namespace = "craby::mymodule::bridging")]
     /// pub mod bridging {
-    ///     struct MyStruct {
+    ///     #[cxx_name = "MyStruct"]
+    ///     struct MyStructBridge {
     ///         foo: String,
     ///         bar: f64,
     ///     }
@@ -93,6 +95,17 @@ impl RsTemplate {
             },
         );
 
+        // Types shared by the modules are defined once
+        let mut type_ids = FxHashSet::default();
+        let mut dedup = |defs: Vec<(u64, String)>| {
+            defs.into_iter()
+                .filter(|(id, _)| type_ids.insert(*id))
+                .map(|(_, def)| def)
+                .collect::<Vec<_>>()
+        };
+        let struct_defs = dedup(struct_defs);
+        let enum_defs = dedup(enum_defs);
+
         let cxx_extern_stmts = indent_str(&[impl_types, cxx_externs].concat().join("\n\n"), 4);
         let cxx_extern = formatdoc! {
             r#"
@@ -177,6 +190,35 @@ impl RsTemplate {
             String::new()
         };
 
+        let cxx_invoker_manager = formatdoc! {
+            r#"
+            extern "Rust" {{
+                type JsTask;
+
+                unsafe fn run_js_task(task: *mut JsTask);
+            }}
+
+            #[namespace = "{cxx_ns}::invoker"]
+            unsafe extern "C++" {{
+                include!("CrabyInvoker.h");
+
+                type InvokerManager;
+
+                unsafe fn invoke(self: &InvokerManager, id: usize, task: *mut JsTask) -> bool;
+
+                #[rust_name = "get_invoker_manager"]
+                fn getInvokerManager() -> &'static InvokerManager;
+            }}"#,
+        };
+
+        let cxx_task_ffi = formatdoc! {
+            r#"
+            extern "Rust" {{
+                #[cxx_name = "invalidateModule"]
+                fn invalidate_module(id: usize);
+            }}"#,
+        };
+
         let code = indent_str(
             &[
                 struct_defs.join("\n\n"),
@@ -184,6 +226,8 @@ impl RsTemplate {
                 cxx_extern,
                 signal_ffi,
                 cxx_signal_manager,
+                cxx_invoker_manager,
+                cxx_task_ffi,
             ]
             .iter()
             .filter(|s| !s.is_empty())
@@ -208,7 +252,7 @@ impl RsTemplate {
     ///
     /// ```rust,ignore
     /// fn create_my_module(id: usize, data_path: &str) -> Box<MyModule> {
-    ///     let ctx = Context::new(id, data_path);
+    ///     let ctx = Context::new(id, data_path).with_invoker(Invoker::new(id, invoke_js_task));
     ///     Box::new(MyModule::new(ctx))
     /// }
     ///
@@ -226,12 +270,74 @@ impl RsTemplate {
             .collect::<Vec<_>>()
     }
 
+    /// Generates the JS thread task implementations for `Invoker`.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// pub struct JsTask(craby::invoker::Task);
+    ///
+    /// unsafe fn run_js_task(task: *mut JsTask) {
+    ///     // ...
+    /// }
+    ///
+    /// fn invoke_js_task(id: usize, task: craby::invoker::Task) -> bool {
+    ///     // ...
+    /// }
+    /// ```
+    fn rs_invoker_impl(&self) -> String {
+        formatdoc! {
+            r#"
+            pub struct JsTask(craby::invoker::Task);
+
+            unsafe fn run_js_task(task: *mut JsTask) {{
+                if !task.is_null() {{
+                    let task = Box::from_raw(task);
+                    let _ = craby::catch_panic!((task.0)());
+                }}
+            }}
+
+            fn invoke_js_task(id: usize, task: craby::invoker::Task) -> bool {{
+                let task = Box::into_raw(Box::new(JsTask(task)));
+                let invoked = unsafe {{ get_invoker_manager().invoke(id, task) }};
+
+                if !invoked {{
+                    unsafe {{ drop(Box::from_raw(task)) }};
+                }}
+
+                invoked
+            }}"#,
+        }
+    }
+
+    /// Generates the teardown implementation for the tasks spawned by `Context`.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// fn invalidate_module(id: usize) {
+    ///     craby::task::invalidate(id);
+    /// }
+    /// ```
+    fn rs_task_impl(&self) -> String {
+        formatdoc! {
+            r#"
+            fn invalidate_module(id: usize) {{
+                craby::task::invalidate(id);
+            }}"#,
+        }
+    }
+
     /// Generate the traits code for the given schema.
     ///
     /// ```rust,ignore
     /// pub trait MyModuleSpec {
     ///     fn multiply(&mut self, a: f64, b: f64) -> f64;
     /// }
+    ///
+    /// pub trait DecoderSpec: Send {
+    ///     fn decode(&mut self, data: ArrayBuffer) -> String;
+    /// }
     /// ```
     fn rs_spec(&self, schema: &Schema) -> Result<String, anyhow::Error> {
         let trait_name = pascal_case(&format!("{}Spec", schema.module_name));
@@ -240,10 +346,16 @@ impl RsTemplate {
             .iter()
             .map(|spec| -> Result<String, anyhow::Error> {
                 let sig = spec.try_into_impl_sig()?;
-                Ok(format!("{sig};"))
+                Ok(with_rs_doc(&spec.doc, format!("{sig};"), true))
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        // Module constants are evaluated once before the module is created
+        if let Some(constants_type) = schema.constants_type() {
+            let constants_name = &constants_type.as_object().unwrap().name;
+            methods.insert(0, format!("fn constants(ctx: &Context) -> {constants_name};"));
+        }
+
         let signal_enum = if !schema.signals.is_empty() {
             let signal_enum_name = format!("{}Signal", schema.module_name);
             let (signal_members, pattern_matches, pattern_matches_with_data) = schema
@@ -255,13 +367,14 @@ impl RsTemplate {
                     // Create enum variant based on payload type
                     let enum_member = if let Some(payload_type) = &signal.payload_type {
                         // Convert payload_type to Rust type
-                        match payload_type.as_rs_type() {
+                        match payload_type.as_rs_impl_type() {
                             Ok(rs_type) => format!("{member_name}({}),", rs_type.into_code()),
                             Err(_) => format!("{member_name},"), // Create without payload if conversion fails
                         }
                     } else {
                         format!("{member_name},")
                     };
+                    let enum_member = with_rs_doc(&signal.doc, enum_member, true);
                     
                     let enum_pattern_match = formatdoc! {
                         r#"{signal_enum_name}::{member_name} => {{
@@ -347,9 +460,16 @@ impl RsTemplate {
             }}"#
         };
 
+        let object_traits = schema
+            .objects
+            .iter()
+            .map(|object| object.try_into_rs_spec())
+            .collect::<Result<Vec<_>, _>>()?;
+
         let content = [Some(spec_trait), signal_enum]
             .into_iter()
             .flatten()
+            .chain(object_traits)
             .collect::<Vec<_>>()
             .join("\n\n");
 
@@ -387,7 +507,7 @@ impl RsTemplate {
     fn rs_impl(&self, schema: &Schema) -> Result<String, anyhow::Error> {
         let struct_name = pascal_case(&schema.module_name);
         let trait_name = pascal_case(&format!("{}Spec", schema.module_name));
-        let methods = schema
+        let mut methods = schema
             .methods
             .iter()
             .map(|spec| -> Result<String, anyhow::Error> {
@@ -403,6 +523,19 @@ impl RsTemplate {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        if let Some(constants_type) = schema.constants_type() {
+            methods.insert(
+                0,
+                formatdoc! {
+                    r#"
+                    fn constants(_ctx: &Context) -> {constants_name} {{
+                        unimplemented!();
+                    }}"#,
+                    constants_name = constants_type.as_object().unwrap().name,
+                },
+            );
+        }
+
         let method_impls = indent_str(&methods.join("\n\n"), 4);
         let content = formatdoc! {
             r#"
@@ -424,6 +557,19 @@ impl RsTemplate {
         Ok(content)
     }
 
+    /// Returns the attribute that allows the usages of the `@deprecated` specs in the generated code.
+    ///
+    /// ```rust,ignore
+    /// #![allow(deprecated)]
+    /// ```
+    fn allow_deprecated_attr(&self, schemas: &[Schema]) -> &'static str {
+        if schemas.iter().any(Schema::has_deprecated) {
+            "#![allow(deprecated)]\n"
+        } else {
+            ""
+        }
+    }
+
     /// Generate the `lib.rs` file for the given code generation results.
     ///
     /// ```rust,ignore
@@ -502,12 +648,13 @@ impl RsTemplate {
                             .unwrap_or_else(|_| "String".to_string());
                         let function_name = format!("get_{}_payload", snake_case(&signal.name));
                         let signal_variant = pascal_case(&signal.name);
+                        let payload = payload_type.as_rs_conversion("payload.clone()");
                         
                         formatdoc! {
                             r#"
                             fn {function_name}(s: &{signal_enum_name}) -> {payload_type_name} {{
                                 match s {{
-                                    {signal_enum_name}::{signal_variant}(payload) => (*payload).clone(),
+                                    {signal_enum_name}::{signal_variant}(payload) => {payload},
                                     _ => panic!("Invalid signal type for {function_name}"),
                                 }}
                             }}"#,
@@ -532,12 +679,21 @@ impl RsTemplate {
             vec![]
         };
         
+        let mut bridge_impls = BTreeMap::new();
+        for schema in &ctx.schemas {
+            schema.try_collect_bridge_impls(&mut bridge_impls)?;
+        }
+
+        let type_impls = bridge_impls.into_values().collect::<Vec<_>>().join("\n\n");
+        let invoker_impls = self.rs_invoker_impl();
+        let task_impls = self.rs_task_impl();
         let impl_mods = impl_mods.join("\n");
         let cxx_impls = cxx_impls.join("\n\n");
         let signal_impls = signal_payload_impls.join("\n\n");
+        let allow_attr = self.allow_deprecated_attr(&ctx.schemas);
         let content = formatdoc! {
             r#"
-            #[rustfmt::skip]
+            {allow_attr}#[rustfmt::skip]
             use craby::prelude::*;
 
             {impl_mods}
@@ -549,6 +705,12 @@ impl RsTemplate {
 
             {cxx_impls}
 
+            {type_impls}
+
+            {invoker_impls}
+
+            {task_impls}
+
             {signal_impls}"#,
         };
 
@@ -565,26 +727,32 @@ impl RsTemplate {
     /// pub trait MyModuleSpec {
     ///     fn multiply(&mut self, a: f64, b: f64) -> f64;
     /// }
+    ///
+    /// #[derive(Clone, Default)]
+    /// pub struct MyStruct {
+    ///     pub foo: Option<Number>,
+    /// }
     /// ```
     pub fn generated_rs(&self, schemas: &[Schema]) -> Result<String, anyhow::Error> {
         let mut spec_codes = Vec::with_capacity(schemas.len());
         let mut type_aliases = BTreeMap::new();
 
         for schema in schemas {
-            // Collect the type implementations
-            schema.try_collect_type_impls(&mut type_aliases)?;
+            // Collect the user-facing types
+            schema.try_collect_impl_types(&mut type_aliases)?;
             spec_codes.push(self.rs_spec(schema)?);
         }
 
         let hash = Schema::to_hash(schemas);
         let hash_comment = format!("{HASH_COMMENT_PREFIX} {hash}");
         let type_impls = type_aliases.into_values().collect::<Vec<_>>();
+        let allow_attr = self.allow_deprecated_attr(schemas);
 
         let content = [
             vec![formatdoc! {
                 r#"
                 {hash_comment}
-                #[rustfmt::skip]
+                {allow_attr}#[rustfmt::skip]
                 use craby::prelude::*;
 
                 use crate::ffi::bridging::*;"#,
@@ -656,7 +824,7 @@ impl RsGenerator {
 }
 
 impl Generator<RsTemplate> for RsGenerator {
-    fn cleanup(_: &CodegenContext) -> Result<(), anyhow::Error> {
+    fn cleanup(_: &CodegenContext, _: &[TemplateResult]) -> Result<(), anyhow::Error> {
         Ok(())
     }
 
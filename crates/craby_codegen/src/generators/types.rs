@@ -16,7 +16,8 @@ pub trait Generator<T>
 where
     T: Template,
 {
-    fn cleanup(ctx: &CodegenContext) -> Result<(), anyhow::Error>;
+    /// Removes the files of the generator that are no longer generated (eg. files of the removed modules).
+    fn cleanup(ctx: &CodegenContext, files: &[TemplateResult]) -> Result<(), anyhow::Error>;
     fn generate(&self, ctx: &CodegenContext) -> Result<Vec<TemplateResult>, anyhow::Error>;
     fn template_ref(&self) -> &T;
 }
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{parser::types::TypeAnnotation, types::Schema};
+
+/// Version of the schema IR format.
+///
+/// Bump it on every breaking change of the [`Schema`] structure.
+pub const SCHEMA_IR_VERSION: u32 = 1;
+
+/// Machine-readable schema of the native modules.
+///
+/// Written by `craby show --format json` and read by `craby codegen --from-ir <file>`.
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "modules": [
+///     {
+///       "module_name": "Calculator",
+///       "aliases": [],
+///       "enums": [],
+///       "methods": [
+///         {
+///           "name": "add",
+///           "params": [
+///             { "name": "a", "type_annotation": "Number" },
+///             { "name": "b", "type_annotation": "Number" }
+///           ],
+///           "ret_type": "Number"
+///         }
+///       ],
+///       "signals": []
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaIr {
+    pub version: u32,
+    pub modules: Vec<Schema>,
+}
+
+impl SchemaIr {
+    pub fn new(modules: Vec<Schema>) -> Self {
+        SchemaIr {
+            version: SCHEMA_IR_VERSION,
+            modules,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parses and validates the schema IR.
+    pub fn from_json(raw: &str) -> Result<Self, anyhow::Error> {
+        let value = serde_json::from_str::<serde_json::Value>(raw)
+            .map_err(|e| anyhow::anyhow!("Invalid schema IR: {}", e))?;
+
+        // Check the version first to report the incompatible IR instead of the structural errors
+        match value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == SCHEMA_IR_VERSION as u64 => {}
+            Some(version) => anyhow::bail!(
+                "Unsupported schema IR version: {} (expected: {})",
+                version,
+                SCHEMA_IR_VERSION
+            ),
+            None => anyhow::bail!("Invalid schema IR: `version` is required"),
+        }
+
+        let ir = serde_json::from_value::<SchemaIr>(value)
+            .map_err(|e| anyhow::anyhow!("Invalid schema IR: {}", e))?;
+
+        for schema in &ir.modules {
+            if let Some(alias) = schema
+                .aliases
+                .iter()
+                .find(|alias| alias.as_object().is_none())
+            {
+                anyhow::bail!("Invalid alias type of {}: {:?}", schema.module_name, alias);
+            }
+
+            if let Some(enum_type) = schema
+                .enums
+                .iter()
+                .find(|enum_type| !matches!(enum_type, TypeAnnotation::Enum(..)))
+            {
+                anyhow::bail!(
+                    "Invalid enum type of {}: {:?}",
+                    schema.module_name,
+                    enum_type
+                );
+            }
+        }
+
+        Ok(ir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::get_codegen_context, types::Schema};
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let ctx = get_codegen_context();
+        let json = SchemaIr::new(ctx.schemas).to_json().unwrap();
+        let ir = SchemaIr::from_json(&json).unwrap();
+
+        assert_eq!(ir.to_json().unwrap(), json);
+        assert_eq!(
+            Schema::to_hash(&ir.modules),
+            Schema::to_hash(&get_codegen_context().schemas)
+        );
+    }
+
+    #[test]
+    fn test_invalid_ir() {
+        let unresolved = r#"{
+            "version": 1,
+            "modules": [{
+                "module_name": "Test",
+                "aliases": [],
+                "enums": [],
+                "methods": [{ "name": "foo", "params": [], "ret_type": { "Ref": { "name": "Foo" } } }],
+                "signals": []
+            }]
+        }"#;
+
+        assert!(SchemaIr::from_json(unresolved).is_err());
+        assert!(SchemaIr::from_json(r#"{ "version": 0, "modules": [] }"#).is_err());
+        assert!(SchemaIr::from_json(r#"{ "modules": [] }"#).is_err());
+        assert!(SchemaIr::from_json(r#"{ "version": 1, "modules": [] }"#).is_ok());
+    }
+}
@@ -2,7 +2,11 @@ mod codegen;
 pub use codegen::*;
 
 pub mod constants;
+pub mod diff;
+pub mod formatter;
 pub mod generators;
+pub mod ir;
+pub mod merge;
 pub mod parser;
 pub mod types;
 pub mod utils;
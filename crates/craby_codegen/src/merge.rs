@@ -0,0 +1,379 @@
+use std::ops::Range;
+
+use quote::ToTokens;
+use syn::{spanned::Spanned, FnArg, ImplItem, ImplItemFn, Item, ItemImpl, ReturnType, Signature};
+
+/// Methods of the spec trait that are not in the stubs (injected by `#[craby_module]` or provided by the trait).
+const TRAIT_METHODS: [&str; 3] = ["new", "id", "emit"];
+
+/// Aliases of the `craby::prelude` types (`Nullable<Number>` is the same as `Option<f64>`).
+const TYPE_ALIASES: [(&str, &str); 6] = [
+    ("Boolean", "bool"),
+    ("Number", "f64"),
+    ("ArrayBuffer", "Vec < u8 >"),
+    ("Array", "Vec"),
+    ("Nullable", "Option"),
+    ("Void", "()"),
+];
+
+/// Result of merging the generated implementation stub into the existing implementation.
+#[derive(Debug)]
+pub struct ImplMerge {
+    pub content: String,
+    /// Methods added with `unimplemented!()` stubs
+    pub added: Vec<String>,
+    /// Methods whose signatures have been replaced (the bodies are kept)
+    pub updated: Vec<String>,
+    /// Methods commented out because they are no longer in the spec
+    pub removed: Vec<String>,
+}
+
+impl ImplMerge {
+    pub fn is_changed(&self) -> bool {
+        !self.added.is_empty() || !self.updated.is_empty() || !self.removed.is_empty()
+    }
+}
+
+/// Merges the spec trait implementation of the generated stub into the existing source.
+///
+/// Only the methods of the trait implementation are edited, everything else in the source (comments, formatting,
+/// method bodies and other items) is kept as is.
+///
+/// Returns `None` if the stub or the source does not have the trait implementation.
+pub fn merge_impl(src: &str, stub: &str) -> Result<Option<ImplMerge>, anyhow::Error> {
+    let stub_file = syn::parse_file(stub)?;
+    let Some((trait_name, stub_impl)) = find_trait_impl(&stub_file.items, None) else {
+        return Ok(None);
+    };
+
+    let file = syn::parse_file(src)?;
+    let Some((_, src_impl)) = find_trait_impl(&file.items, Some(&trait_name)) else {
+        return Ok(None);
+    };
+
+    let stub_fns = impl_fns(stub_impl);
+    let src_fns = impl_fns(src_impl);
+    let mut edits: Vec<(Range<usize>, String)> = vec![];
+    let mut merge = ImplMerge {
+        content: String::new(),
+        added: vec![],
+        updated: vec![],
+        removed: vec![],
+    };
+
+    for src_fn in &src_fns {
+        let name = src_fn.sig.ident.to_string();
+
+        match stub_fns
+            .iter()
+            .find(|stub_fn| stub_fn.sig.ident == src_fn.sig.ident)
+        {
+            Some(stub_fn) => {
+                if sig_key(&stub_fn.sig) != sig_key(&src_fn.sig) {
+                    let sig = updated_sig(src, &src_fn.sig, stub, &stub_fn.sig);
+                    edits.push((src_fn.sig.span().byte_range(), sig));
+                    merge.updated.push(name);
+                }
+            }
+            None if TRAIT_METHODS.contains(&name.as_str()) => {}
+            None => {
+                let range = line_range(src, src_fn.span().byte_range());
+                edits.push((range.clone(), comment_out(&name, &src[range])));
+                merge.removed.push(name);
+            }
+        }
+    }
+
+    let new_fns = stub_fns
+        .iter()
+        .filter(|stub_fn| {
+            !src_fns
+                .iter()
+                .any(|src_fn| src_fn.sig.ident == stub_fn.sig.ident)
+        })
+        .map(|stub_fn| {
+            merge.added.push(stub_fn.sig.ident.to_string());
+            &stub[line_range(stub, stub_fn.span().byte_range())]
+        })
+        .collect::<Vec<_>>();
+
+    if !new_fns.is_empty() {
+        let close = src_impl.brace_token.span.close().byte_range().start;
+        let line_start = src[..close].rfind('\n').map_or(0, |i| i + 1);
+        let new_fns = new_fns.join("\n\n");
+
+        let edit = if src[line_start..close].trim().is_empty() {
+            match src_fns.is_empty() {
+                true => (line_start..line_start, format!("{}\n", new_fns)),
+                false => (line_start..line_start, format!("\n{}\n", new_fns)),
+            }
+        } else {
+            (close..close, format!("\n{}\n", new_fns))
+        };
+        edits.push(edit);
+    }
+
+    // Apply from the end of the source to keep the ranges valid
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    let mut content = src.to_string();
+    for (range, replacement) in edits {
+        content.replace_range(range, &replacement);
+    }
+    merge.content = content;
+
+    Ok(Some(merge))
+}
+
+fn find_trait_impl<'a>(
+    items: &'a [Item],
+    trait_name: Option<&str>,
+) -> Option<(String, &'a ItemImpl)> {
+    items.iter().find_map(|item| match item {
+        Item::Impl(item_impl) => {
+            let (_, path, _) = item_impl.trait_.as_ref()?;
+            let name = path.segments.last()?.ident.to_string();
+
+            match trait_name {
+                Some(trait_name) if trait_name != name => None,
+                _ => Some((name, item_impl)),
+            }
+        }
+        _ => None,
+    })
+}
+
+fn impl_fns(item_impl: &ItemImpl) -> Vec<&ImplItemFn> {
+    item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(item_fn) => Some(item_fn),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Signature without the parameter names (renamed parameters, eg. `_arg`, are not changes).
+fn sig_key(sig: &Signature) -> String {
+    let inputs = sig
+        .inputs
+        .iter()
+        .map(|input| match input {
+            FnArg::Receiver(receiver) => receiver.to_token_stream().to_string(),
+            FnArg::Typed(pat_type) => type_key(&pat_type.ty.to_token_stream().to_string()),
+        })
+        .collect::<Vec<_>>();
+    let output = match &sig.output {
+        ReturnType::Default => "()".to_string(),
+        ReturnType::Type(_, ty) => type_key(&ty.to_token_stream().to_string()),
+    };
+
+    format!(
+        "{} {}({}) -> {}",
+        sig.unsafety.to_token_stream(),
+        sig.generics.to_token_stream(),
+        inputs.join(", "),
+        output
+    )
+}
+
+fn type_key(ty: &str) -> String {
+    ty.split_whitespace()
+        .map(|token| {
+            TYPE_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == token)
+                .map_or(token, |(_, ty)| ty)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Signature of the stub with the parameter patterns of the existing method (eg. `mut arg`).
+fn updated_sig(src: &str, src_sig: &Signature, stub: &str, stub_sig: &Signature) -> String {
+    let offset = stub_sig.span().byte_range().start;
+    let mut sig = stub[stub_sig.span().byte_range()].to_string();
+
+    if src_sig.inputs.len() != stub_sig.inputs.len() {
+        return sig;
+    }
+
+    let mut pats = src_sig
+        .inputs
+        .iter()
+        .zip(&stub_sig.inputs)
+        .filter_map(|inputs| match inputs {
+            (FnArg::Typed(src_arg), FnArg::Typed(stub_arg)) => Some((
+                stub_arg.pat.span().byte_range(),
+                &src[src_arg.pat.span().byte_range()],
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    pats.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    for (range, pat) in pats {
+        sig.replace_range(range.start - offset..range.end - offset, pat);
+    }
+
+    sig
+}
+
+/// Extends the range to the whole lines.
+fn line_range(src: &str, range: Range<usize>) -> Range<usize> {
+    let start = src[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let end = src[range.end..]
+        .find('\n')
+        .map_or(src.len(), |i| range.end + i);
+
+    start..end
+}
+
+/// ```rust,ignore
+/// // TODO: `foo` has been removed from the spec.
+/// // fn foo(&mut self) -> Number {
+/// //     1.0
+/// // }
+/// ```
+fn comment_out(name: &str, code: &str) -> String {
+    let indent = &code[..code.len() - code.trim_start().len()];
+    let lines = code
+        .lines()
+        .map(|line| match line.strip_prefix(indent) {
+            Some(line) => format!("{indent}// {line}").trim_end().to_string(),
+            None => format!("{indent}// {}", line.trim_start()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("{indent}// TODO: `{name}` has been removed from the spec.\n{lines}")
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const STUB: &str = indoc! {r#"
+        use craby::{prelude::*, throw};
+
+        pub struct Calculator {
+            ctx: Context,
+        }
+
+        #[craby_module]
+        impl CalculatorSpec for Calculator {
+            fn add(&mut self, a: Number, b: Number) -> Number {
+                unimplemented!();
+            }
+
+            fn divide(&mut self, a: Number, b: Number) -> Result<Number, Error> {
+                unimplemented!();
+            }
+
+            fn sqrt(&mut self, value: Number) -> Number {
+                unimplemented!();
+            }
+        }
+    "#};
+
+    #[test]
+    fn test_merge_impl() {
+        let src = indoc! {r#"
+            use craby::{prelude::*, throw};
+
+            pub struct Calculator {
+                ctx: Context,
+            }
+
+            #[craby_module]
+            impl CalculatorSpec for Calculator {
+                fn new(ctx: Context) -> Self {
+                    Calculator { ctx }
+                }
+
+                // Keeps the comments
+                fn add(&mut self, a: f64, _b: Number) -> Number {
+                    a + b
+                }
+
+                fn divide(&mut self, mut a: Number, b: Number) -> Number {
+                    a / b
+                }
+
+                fn subtract(&mut self, a: Number, b: Number) -> Number {
+                    a - b
+                }
+            }
+
+            fn helper() {}
+        "#};
+
+        let merge = merge_impl(src, STUB).unwrap().unwrap();
+
+        assert_eq!(merge.added, vec!["sqrt"]);
+        assert_eq!(merge.updated, vec!["divide"]);
+        assert_eq!(merge.removed, vec!["subtract"]);
+        assert_eq!(
+            merge.content,
+            indoc! {r#"
+                use craby::{prelude::*, throw};
+
+                pub struct Calculator {
+                    ctx: Context,
+                }
+
+                #[craby_module]
+                impl CalculatorSpec for Calculator {
+                    fn new(ctx: Context) -> Self {
+                        Calculator { ctx }
+                    }
+
+                    // Keeps the comments
+                    fn add(&mut self, a: f64, _b: Number) -> Number {
+                        a + b
+                    }
+
+                    fn divide(&mut self, mut a: Number, b: Number) -> Result<Number, Error> {
+                        a / b
+                    }
+
+                    // TODO: `subtract` has been removed from the spec.
+                    // fn subtract(&mut self, a: Number, b: Number) -> Number {
+                    //     a - b
+                    // }
+
+                    fn sqrt(&mut self, value: Number) -> Number {
+                        unimplemented!();
+                    }
+                }
+
+                fn helper() {}
+            "#}
+        );
+
+        // Already merged
+        assert!(!merge_impl(&merge.content, STUB)
+            .unwrap()
+            .unwrap()
+            .is_changed());
+    }
+
+    #[test]
+    fn test_merge_empty_impl() {
+        let src = "impl CalculatorSpec for Calculator {\n}\n";
+        let merge = merge_impl(src, STUB).unwrap().unwrap();
+
+        assert_eq!(merge.added, vec!["add", "divide", "sqrt"]);
+        assert!(merge
+            .content
+            .starts_with("impl CalculatorSpec for Calculator {\n    fn add("));
+    }
+
+    #[test]
+    fn test_merge_without_trait_impl() {
+        assert!(merge_impl("pub(crate) mod ffi;", STUB).unwrap().is_none());
+        assert!(merge_impl(STUB, "pub(crate) mod ffi;").unwrap().is_none());
+    }
+}
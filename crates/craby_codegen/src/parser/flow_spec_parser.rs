@@ -0,0 +1,666 @@
+use oxc::{
+    diagnostics::{LabeledSpan, OxcDiagnostic},
+    span::Span,
+};
+
+use crate::{
+    parser::{native_spec_parser::try_parse_schema, types::ParseError},
+    types::Schema,
+};
+
+/// File extensions of the Flow spec files
+pub const FLOW_SPEC_EXTENSIONS: [&str; 3] = ["js", "jsx", "flow"];
+
+/// Parses the Flow spec into the schemas.
+///
+/// The Flow source is lowered into the equivalent TypeScript source, so the Flow specs produce the same schemas as the TypeScript ones.
+/// The diagnostics are mapped back to the Flow source.
+///
+/// ```js
+/// // @flow
+/// import type { NativeModule } from 'craby-modules';
+/// import { NativeModuleRegistry } from 'craby-modules';
+///
+/// export interface Spec extends NativeModule {
+///   +multiply: (a: number, b: number) => number;
+///   +find: (id: string) => ?string;
+/// }
+///
+/// export default (NativeModuleRegistry.getEnforcing<Spec>('Calculator'): Spec);
+/// ```
+pub fn try_parse_flow_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
+    let lowered = lower(src).map_err(|diagnostic| ParseError::Oxc {
+        diagnostics: vec![diagnostic],
+    })?;
+
+    try_parse_schema(&lowered.code).map_err(|e| match e {
+        ParseError::Oxc { diagnostics } => ParseError::Oxc {
+            diagnostics: diagnostics
+                .into_iter()
+                .map(|diagnostic| lowered.remap(diagnostic))
+                .collect(),
+        },
+        e => e,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    Ident,
+    Punct,
+    Literal,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    start: usize,
+    end: usize,
+}
+
+/// Multi-character punctuators (longest first)
+const PUNCTS: [&str; 4] = ["...", "=>", "{|", "|}"];
+
+/// Tokens that can be followed by the maybe type (eg. `a: ?T`, `Array<?T>`, `() => ?T`)
+const MAYBE_TYPE_PREV: [&str; 9] = [":", "<", ",", "(", "=", "|", "&", "=>", "["];
+
+/// Tokens that can be followed by the member of the object type or interface
+const MEMBER_PREV: [&str; 4] = ["{", "{|", ",", ";"];
+
+/// Splits the source into the tokens (whitespaces and comments are skipped).
+fn tokenize(src: &str) -> Vec<Token<'_>> {
+    let bytes = src.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80;
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let b = bytes[i];
+
+        let kind = if b.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if src[i..].starts_with("//") {
+            i = src[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            continue;
+        } else if src[i..].starts_with("/*") {
+            i = src[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+            continue;
+        } else if matches!(b, b'\'' | b'"' | b'`') {
+            i += 1;
+            while i < bytes.len() && bytes[i] != b {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(bytes.len());
+            TokenKind::Literal
+        } else if b.is_ascii_digit() {
+            while i < bytes.len() && (is_ident(bytes[i]) || bytes[i] == b'.') {
+                i += 1;
+            }
+            TokenKind::Literal
+        } else if is_ident(b) {
+            while i < bytes.len() && is_ident(bytes[i]) {
+                i += 1;
+            }
+            TokenKind::Ident
+        } else {
+            i += PUNCTS
+                .iter()
+                .find(|punct| src[i..].starts_with(*punct))
+                .map_or(1, |punct| punct.len());
+            TokenKind::Punct
+        };
+
+        tokens.push(Token {
+            kind,
+            text: &src[start..i],
+            start,
+            end: i,
+        });
+    }
+
+    tokens
+}
+
+#[derive(Debug)]
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Edit {
+    fn replace(start: usize, end: usize, text: impl Into<String>) -> Self {
+        Edit {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+
+    fn remove(start: usize, end: usize) -> Self {
+        Edit::replace(start, end, "")
+    }
+
+    fn insert(at: usize, text: impl Into<String>) -> Self {
+        Edit::replace(at, at, text)
+    }
+}
+
+/// Replaced range of the Flow source and the lowered source
+#[derive(Debug)]
+struct Mapping {
+    flow_start: usize,
+    flow_end: usize,
+    ts_start: usize,
+    ts_end: usize,
+}
+
+#[derive(Debug)]
+struct Lowered {
+    code: String,
+    mappings: Vec<Mapping>,
+}
+
+impl Lowered {
+    /// Converts the offset of the lowered source to the offset of the Flow source.
+    fn to_original(&self, offset: usize) -> usize {
+        let mut anchor = (0, 0);
+
+        for mapping in &self.mappings {
+            if offset < mapping.ts_start {
+                break;
+            }
+            if offset < mapping.ts_end {
+                return mapping.flow_start;
+            }
+            anchor = (mapping.ts_end, mapping.flow_end);
+        }
+
+        anchor.1 + (offset - anchor.0)
+    }
+
+    fn remap(&self, mut diagnostic: OxcDiagnostic) -> OxcDiagnostic {
+        if let Some(labels) = diagnostic.labels.as_mut() {
+            for label in labels.iter_mut() {
+                let start = self.to_original(label.offset());
+                let end = self.to_original(label.offset() + label.len()).max(start);
+                let text = label.label().map(str::to_string);
+
+                *label = if label.primary() {
+                    LabeledSpan::new_primary_with_span(text, (start, end - start))
+                } else {
+                    LabeledSpan::new_with_span(text, (start, end - start))
+                };
+            }
+        }
+
+        diagnostic
+    }
+}
+
+struct Lowering<'a> {
+    tokens: Vec<Token<'a>>,
+    edits: Vec<Edit>,
+    /// Token ranges of the type declarations (`interface`, `type`)
+    type_ranges: Vec<(usize, usize)>,
+}
+
+/// Lowers the Flow source into the TypeScript source.
+fn lower(src: &str) -> Result<Lowered, OxcDiagnostic> {
+    let mut lowering = Lowering {
+        tokens: tokenize(src),
+        edits: vec![],
+        type_ranges: vec![],
+    };
+
+    lowering.collect_type_ranges();
+    lowering.collect_edits();
+
+    apply_edits(src, lowering.edits)
+}
+
+/// Applies the edits to the Flow source.
+///
+/// Returns an error if the edits overlap (the lowered source would be broken).
+fn apply_edits(src: &str, mut edits: Vec<Edit>) -> Result<Lowered, OxcDiagnostic> {
+    edits.sort_by_key(|edit| (edit.start, edit.end));
+
+    let mut code = String::with_capacity(src.len());
+    let mut mappings = Vec::with_capacity(edits.len());
+    let mut pos = 0;
+
+    for edit in edits {
+        if edit.start < pos {
+            return Err(OxcDiagnostic::error(
+                "Internal error: Failed to lower the Flow syntax (overlapping edits)",
+            )
+            .with_label(Span::new(edit.start as u32, edit.end as u32)));
+        }
+
+        code.push_str(&src[pos..edit.start]);
+        let ts_start = code.len();
+        code.push_str(&edit.text);
+
+        mappings.push(Mapping {
+            flow_start: edit.start,
+            flow_end: edit.end,
+            ts_start,
+            ts_end: code.len(),
+        });
+        pos = edit.end;
+    }
+    code.push_str(&src[pos..]);
+
+    Ok(Lowered { code, mappings })
+}
+
+impl<'a> Lowering<'a> {
+    fn text(&self, i: usize) -> &'a str {
+        self.tokens.get(i).map_or("", |token| token.text)
+    }
+
+    fn is_ident(&self, i: usize) -> bool {
+        self.tokens
+            .get(i)
+            .is_some_and(|token| token.kind == TokenKind::Ident)
+    }
+
+    fn is_in_type(&self, i: usize) -> bool {
+        self.type_ranges
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&i))
+    }
+
+    /// Returns the index of the closing token that matches the opening token at `i`.
+    fn matching(&self, i: usize) -> Option<usize> {
+        let (opens, closes): (&[&str], &[&str]) = match self.text(i) {
+            "(" => (&["("], &[")"]),
+            "[" => (&["["], &["]"]),
+            "{" | "{|" => (&["{", "{|"], &["}", "|}"]),
+            "<" => (&["<"], &[">"]),
+            _ => return None,
+        };
+        let mut depth = 0;
+
+        for (j, token) in self.tokens.iter().enumerate().skip(i) {
+            if opens.contains(&token.text) {
+                depth += 1;
+            } else if closes.contains(&token.text) {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(j);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the index of the last token of the type that starts at `i`.
+    ///
+    /// eg. `Foo`, `Array<T>`, `T[]`, `{| a: T |}`, `(a: T) => U`
+    fn type_end(&self, i: usize) -> Option<usize> {
+        let token = self.tokens.get(i)?;
+        let mut end = match token.text {
+            "?" => self.type_end(i + 1)?,
+            "(" => {
+                let close = self.matching(i)?;
+                if self.text(close + 1) == "=>" {
+                    return self.type_end(close + 2);
+                }
+                close
+            }
+            "{" | "{|" | "[" => self.matching(i)?,
+            _ if token.kind == TokenKind::Ident => {
+                let mut end = i;
+                while self.text(end + 1) == "." && self.is_ident(end + 2) {
+                    end += 2;
+                }
+                if self.text(end + 1) == "<" {
+                    end = self.matching(end + 1)?;
+                }
+                end
+            }
+            _ if token.kind == TokenKind::Literal => i,
+            _ => return None,
+        };
+
+        while self.text(end + 1) == "[" && self.text(end + 2) == "]" {
+            end += 2;
+        }
+
+        Some(end)
+    }
+
+    /// Returns the index of the last token of the union type that starts at `i` (eg. `| A | B`).
+    fn union_type_end(&self, i: usize) -> Option<usize> {
+        let start = if self.text(i) == "|" { i + 1 } else { i };
+        let mut end = self.type_end(start)?;
+
+        while matches!(self.text(end + 1), "|" | "&") {
+            end = self.type_end(end + 2)?;
+        }
+
+        Some(end)
+    }
+
+    fn collect_type_ranges(&mut self) {
+        for i in 0..self.tokens.len() {
+            let is_stmt = !matches!(self.text(i.wrapping_sub(1)), "." | "import");
+            let range_end = match self.text(i) {
+                // `interface Foo extends Bar { ... }`
+                "interface" if is_stmt && self.is_ident(i + 1) => (i..self.tokens.len())
+                    .find(|j| matches!(self.text(*j), "{" | "{|"))
+                    .and_then(|open| self.matching(open)),
+                // `type Foo = ...`
+                "type" if is_stmt && self.is_ident(i + 1) && self.text(i + 2) == "=" => {
+                    self.union_type_end(i + 3)
+                }
+                _ => None,
+            };
+
+            if let Some(end) = range_end {
+                self.type_ranges.push((i, end));
+            }
+        }
+    }
+
+    fn collect_edits(&mut self) {
+        for i in 0..self.tokens.len() {
+            let token = self.tokens[i];
+            let prev = self.text(i.wrapping_sub(1));
+            let next = self.text(i + 1);
+
+            match token.text {
+                // Exact object type: `{| a: T |}` -> `{ a: T }`
+                "{|" => self.edits.push(Edit::replace(token.start, token.end, "{")),
+                "|}" => self.edits.push(Edit::replace(token.start, token.end, "}")),
+                // `$ReadOnlyArray<T>` -> `Array<T>`
+                "$ReadOnlyArray" if next == "<" => {
+                    self.edits
+                        .push(Edit::replace(token.start, token.end, "Array"));
+                }
+                // `$ReadOnly<T>` -> `T`
+                "$ReadOnly" if next == "<" => {
+                    if let Some(close) = self.matching(i + 1) {
+                        let close = self.tokens[close];
+                        self.edits
+                            .push(Edit::remove(token.start, self.tokens[i + 1].end));
+                        self.edits.push(Edit::remove(close.start, close.end));
+                    }
+                }
+                // Maybe type: `?T` -> `T | null`
+                "?" if MAYBE_TYPE_PREV.contains(&prev) => {
+                    if let Some(end) = self.type_end(i + 1) {
+                        self.edits.push(Edit::remove(token.start, token.end));
+                        self.edits
+                            .push(Edit::insert(self.tokens[end].end, " | null"));
+                    }
+                }
+                // Variance: `+foo: T` -> `readonly foo: T`, `-foo: T` -> `foo: T`
+                "+" | "-"
+                    if MEMBER_PREV.contains(&prev)
+                        && self.is_ident(i + 1)
+                        && self.is_in_type(i) =>
+                {
+                    if token.text == "+" && !self.is_function_prop(i + 1) {
+                        self.edits
+                            .push(Edit::replace(token.start, token.end, "readonly "));
+                    } else {
+                        self.edits.push(Edit::remove(token.start, token.end));
+                    }
+                }
+                "export" if next == "default" && self.text(i + 2) == "(" => self.lower_cast(i + 2),
+                "enum" if !matches!(prev, ".") && self.is_ident(i + 1) => self.lower_enum(i),
+                _ => {}
+            }
+
+            let is_member = MEMBER_PREV.contains(&prev)
+                || (matches!(prev, "+" | "-")
+                    && MEMBER_PREV.contains(&self.text(i.wrapping_sub(2))));
+            if is_member && self.is_in_type(i) && self.is_function_prop(i) {
+                self.lower_function_prop(i);
+            }
+        }
+    }
+
+    /// Returns `true` if the member at `i` is a function typed property (eg. `foo: (a: T) => U`).
+    fn is_function_prop(&self, i: usize) -> bool {
+        let colon = if self.text(i + 1) == "?" {
+            i + 2
+        } else {
+            i + 1
+        };
+
+        self.is_ident(i)
+            && self.text(colon) == ":"
+            && self.text(colon + 1) == "("
+            && self
+                .matching(colon + 1)
+                .is_some_and(|close| self.text(close + 1) == "=>")
+    }
+
+    /// Function typed property to method signature: `foo: (a: T) => U` -> `foo(a: T): U`
+    fn lower_function_prop(&mut self, i: usize) {
+        let colon = if self.text(i + 1) == "?" {
+            i + 2
+        } else {
+            i + 1
+        };
+        let Some(close) = self.matching(colon + 1) else {
+            return;
+        };
+        let (close, arrow) = (self.tokens[close], self.tokens[close + 1]);
+
+        self.edits.push(Edit::remove(
+            self.tokens[colon].start,
+            self.tokens[colon + 1].start,
+        ));
+        self.edits.push(Edit::replace(close.end, arrow.end, ":"));
+    }
+
+    /// Type cast of the default export: `export default (expr: Spec);` -> `export default (expr);`
+    fn lower_cast(&mut self, open: usize) {
+        let Some(close) = self.matching(open) else {
+            return;
+        };
+        let mut depth = 0;
+        let mut colon = None;
+
+        for j in open + 1..close {
+            match self.text(j) {
+                "(" | "[" | "{" | "<" => depth += 1,
+                ")" | "]" | "}" | ">" => depth -= 1,
+                ":" if depth == 0 => colon = Some(j),
+                _ => {}
+            }
+        }
+
+        if let Some(colon) = colon {
+            self.edits.push(Edit::remove(
+                self.tokens[colon].start,
+                self.tokens[close].start,
+            ));
+        }
+    }
+
+    /// Flow enum: `enum Foo of string { A, B }` -> `enum Foo { A = 'A', B = 'B' }`
+    fn lower_enum(&mut self, i: usize) {
+        let mut open = i + 2;
+        let mut repr = None;
+
+        if self.text(open) == "of" {
+            repr = Some(self.text(open + 1));
+            self.edits.push(Edit::remove(
+                self.tokens[open].start,
+                self.tokens[open + 2].start,
+            ));
+            open += 2;
+        }
+
+        let Some(close) = self.matching(open).filter(|_| self.text(open) == "{") else {
+            return;
+        };
+
+        for j in open + 1..close {
+            let token = self.tokens[j];
+            let prev = self.text(j - 1);
+
+            if token.text == "..." {
+                // Unknown members
+                self.edits.push(Edit::remove(token.start, token.end));
+            } else if matches!(prev, "{" | ",")
+                && token.kind == TokenKind::Ident
+                && matches!(self.text(j + 1), "," | "}")
+                && matches!(repr, None | Some("string"))
+            {
+                // Members without the initializer are the string members that have the member name as the value
+                self.edits
+                    .push(Edit::insert(token.end, format!(" = '{}'", token.text)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lower() {
+        let src = "
+        export type Options = $ReadOnly<{| timeout: ?number, tags: $ReadOnlyArray<string> |}>;
+
+        enum State of string { On = 'on', Off }
+
+        export interface Spec extends NativeModule {
+            +VERSION: string;
+            +find: (id: string, options: Options) => ?Array<?string>;
+            -reset: () => void;
+        }
+
+        export default (NativeModuleRegistry.getEnforcing<Spec>('MyModule'): Spec);
+        ";
+
+        let expected = "
+        export type Options = { timeout: number | null, tags: Array<string> };
+
+        enum State { On = 'on', Off = 'Off' }
+
+        export interface Spec extends NativeModule {
+            readonly VERSION: string;
+            find(id: string, options: Options): Array<string | null> | null;
+            reset(): void;
+        }
+
+        export default (NativeModuleRegistry.getEnforcing<Spec>('MyModule'));
+        ";
+
+        assert_eq!(lower(src).unwrap().code, expected);
+    }
+
+    #[test]
+    fn test_overlapping_edits() {
+        let edit = |start, end, text: &str| Edit {
+            start,
+            end,
+            text: text.to_string(),
+        };
+
+        let lowered = apply_edits("a: ?T", vec![edit(3, 4, ""), edit(0, 1, "b")]).unwrap();
+        assert_eq!(lowered.code, "b: T");
+
+        let diagnostic =
+            apply_edits("a: ?T", vec![edit(0, 4, "b: "), edit(3, 5, "T")]).unwrap_err();
+        let label = &diagnostic.labels.as_ref().unwrap()[0];
+        assert_eq!((label.offset(), label.len()), (3, 2));
+    }
+
+    #[test]
+    fn test_same_schema() {
+        let ts_src = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export type Options = {
+            timeout: number | null;
+            tags: string[];
+        };
+
+        export enum State {
+            On = 'On',
+            Off = 'Off',
+        }
+
+        export interface Spec extends NativeModule {
+            /** Version of the module */
+            readonly VERSION: string;
+            /** Finds the item. */
+            find(id: string, options: Options): Promise<string | null>;
+            getState(): State;
+            onChanged: Signal<State>;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+
+        let flow_src = "
+        // @flow
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export type Options = $ReadOnly<{|
+            timeout: ?number,
+            tags: $ReadOnlyArray<string>,
+        |}>;
+
+        export enum State {
+            On,
+            Off,
+        }
+
+        export interface Spec extends NativeModule {
+            /** Version of the module */
+            +VERSION: string;
+            /** Finds the item. */
+            +find: (id: string, options: Options) => Promise<?string>;
+            +getState: () => State;
+            onChanged: Signal<State>;
+        }
+
+        export default (NativeModuleRegistry.getEnforcing<Spec>('MyModule'): Spec);
+        ";
+
+        let ts_schemas = try_parse_schema(ts_src).unwrap();
+        let flow_schemas = try_parse_flow_schema(flow_src).unwrap();
+
+        assert_eq!(Schema::to_hash(&flow_schemas), Schema::to_hash(&ts_schemas));
+    }
+
+    #[test]
+    fn test_diagnostic_span() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            +find?: (id: string) => ?string;
+        }
+
+        export default (NativeModuleRegistry.getEnforcing<Spec>('MyModule'): Spec);
+        ";
+
+        let Err(ParseError::Oxc { diagnostics }) = try_parse_flow_schema(src) else {
+            panic!("Expected diagnostics");
+        };
+        let label = diagnostics[0].labels.as_ref().unwrap()[0].clone();
+
+        assert_eq!(
+            &src[label.offset()..label.offset() + label.len()],
+            "find?: (id: string) => ?string;"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod flow_spec_parser;
 pub mod native_spec_parser;
 pub mod types;
 pub mod utils;
@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+
+use craby_common::utils::string::{camel_case, pascal_case, snake_case};
 use log::debug;
 use oxc::{
     allocator::Allocator,
@@ -6,12 +9,17 @@ use oxc::{
     diagnostics::OxcDiagnostic,
     parser::Parser,
     semantic::{Scoping, SemanticBuilder, SymbolId},
+    span::GetSpan,
 };
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
-    constants::specs::*,
+    constants::{reserved, specs::*},
     parser::{types::*, utils::error},
+    platform::{
+        cxx::cxx_ident,
+        rust::{bridge_ident, rs_ident},
+    },
     types::Schema,
 };
 
@@ -29,14 +37,18 @@ const INVALID_UNION_TYPE: &str = "Union types only allow nullable type (eg. `T |
 const INVALID_MIXED_ENUM_MEMBER: &str =
     "Enum member type must be single type (eg. only `number` or `string`)";
 const INVALID_REGISTRY_METHOD: &str = "Invalid NativeModuleRegistry method";
-const INVALID_RESERVED_ARG_NAME_ID: &str = "Reserved argument name `it_` is not allowed";
-const INVALID_RESERVED_METHOD_NAME_ID: &str = "Reserved method name `emit` is not allowed";
+const INVALID_NATIVE_OBJECT: &str = "Native object only allows method signatures";
+const INVALID_BASE_TYPE: &str = "Interface can only extend object types";
+const INVALID_TYPE_PARAM: &str =
+    "Type parameter constraints, defaults and modifiers are not supported";
 
 pub struct NativeModuleAnalyzer<'a> {
     pub diagnostics: Vec<OxcDiagnostic>,
     scoping: &'a Scoping,
     /// Symbol ID of `NativeModule` identifier's reference
     mod_type_sym_id: Option<SymbolId>,
+    /// Symbol ID of `NativeObject` identifier's reference
+    mod_object_sym_id: Option<SymbolId>,
     /// Symbol ID of `Signal` identifier's reference
     mod_signal_sym_id: Option<SymbolId>,
     /// Symbol ID of `NativeModuleRegistry` identifier's reference
@@ -47,8 +59,22 @@ pub struct NativeModuleAnalyzer<'a> {
     mods: FxHashMap<SymbolId, String>,
     /// Declarations collected from the source code
     decls: FxHashMap<SymbolId, TypeAnnotation>,
+    /// Base types of the interfaces (`interface A extends B, C {}`)
+    bases: FxHashMap<SymbolId, Vec<TypeAnnotation>>,
+    /// Type parameters of the generic declarations (`type Page<T> = {}`)
+    generics: FxHashMap<SymbolId, Vec<SymbolId>>,
     /// NativeModule specs collected from the source code
     specs: FxHashMap<SymbolId, Spec>,
+    /// Native objects collected from the source code
+    objects: FxHashMap<SymbolId, NativeObject>,
+    /// JSDoc comments (key: start of the token that the comment is attached to)
+    docs: FxHashMap<u32, Doc>,
+    /// Names of the enclosing declarations, used to name the inline type literals (eg. `['move', 'Arg0']`)
+    scope: Vec<String>,
+    /// `@typeName` of the property being collected, applied to its inline type literal
+    type_name: Option<String>,
+    /// Spans of the type names (declared, inline and generic instance types) to report the name conflicts
+    type_spans: RefCell<FxHashMap<String, Vec<Span>>>,
 }
 
 impl<'a> NativeModuleAnalyzer<'a> {
@@ -57,15 +83,65 @@ impl<'a> NativeModuleAnalyzer<'a> {
             scoping,
             diagnostics: vec![],
             mod_type_sym_id: None,
+            mod_object_sym_id: None,
             mod_signal_sym_id: None,
             mod_reg_sym_id: None,
             mod_ns_sym_id: None,
             specs: FxHashMap::default(),
             mods: FxHashMap::default(),
             decls: FxHashMap::default(),
+            bases: FxHashMap::default(),
+            generics: FxHashMap::default(),
+            objects: FxHashMap::default(),
+            docs: FxHashMap::default(),
+            scope: vec![],
+            type_name: None,
+            type_spans: RefCell::default(),
         }
     }
 
+    /// Collects the JSDoc comments of the source code.
+    pub fn with_docs(mut self, src: &str, comments: &[Comment]) -> Self {
+        self.docs = comments
+            .iter()
+            .filter(|comment| comment.is_jsdoc())
+            .filter_map(|comment| {
+                let content = comment.content_span().source_text(src);
+                Doc::parse(content).map(|doc| (comment.attached_to, doc))
+            })
+            .collect();
+        self
+    }
+
+    fn doc_of(&self, span: Span) -> Option<Doc> {
+        self.docs
+            .get(&span.start)
+            .filter(|doc| !doc.is_empty())
+            .cloned()
+    }
+
+    fn type_name_of(&self, span: Span) -> Option<String> {
+        self.docs
+            .get(&span.start)
+            .and_then(|doc| doc.type_name.clone())
+    }
+
+    fn record_type_name(&self, name: &str, span: Span) {
+        self.type_spans
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_default()
+            .push(span);
+    }
+
+    /// Runs `f` with the name pushed to the scope of the inline type literals.
+    fn with_scope<T>(&mut self, name: impl Into<String>, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.scope.push(name.into());
+        let ret = f(self);
+        self.scope.pop();
+        ret
+    }
+
     fn collect_mod(&mut self, it: &CallExpression<'a>) {
         if !self.is_reg_call(it) {
             return;
@@ -84,18 +160,43 @@ impl<'a> NativeModuleAnalyzer<'a> {
     fn collect_spec(&mut self, it: &TSInterfaceDeclaration<'a>) {
         let mut methods = vec![];
         let mut signals = vec![];
+        let mut constants = vec![];
+        // Methods and signals are the methods of the same C++ TurboModule
+        let mut member_spans = vec![];
+        let mut constant_spans = vec![];
 
         for sig in &it.body.body {
             match sig {
                 TSSignature::TSMethodSignature(method_sig) => {
                     match self.try_into_method(method_sig) {
-                        Ok(method) => methods.push(method),
+                        Ok(method) => {
+                            methods.push(method);
+                            member_spans.push(method_sig.span);
+                        }
                         Err(e) => return self.diagnostics.push(e),
                     }
                 }
                 TSSignature::TSPropertySignature(prop_sig) => {
                     match self.try_into_signal(prop_sig) {
-                        Ok(signal) => signals.push(signal),
+                        Ok(signal) => {
+                            signals.push(signal);
+                            member_spans.push(prop_sig.span);
+                        }
+                        // `readonly` properties (except signals) are the module constants
+                        Err(_) if prop_sig.readonly => {
+                            if prop_sig.optional {
+                                return self.collect_error(INVALID_OPTIONAL_PROP, prop_sig.span);
+                            }
+
+                            let spec_name = it.id.name.as_str();
+                            match self.with_scope(spec_name, |this| this.try_into_prop(prop_sig)) {
+                                Ok(prop) => {
+                                    constants.push(prop);
+                                    constant_spans.push(prop_sig.span);
+                                }
+                                Err(e) => return self.diagnostics.push(e),
+                            }
+                        }
                         Err(e) => return self.diagnostics.push(e),
                     }
                 }
@@ -103,6 +204,24 @@ impl<'a> NativeModuleAnalyzer<'a> {
             };
         }
 
+        let members = methods
+            .iter()
+            .map(|method| (IdentKind::Method, method.name.as_str()))
+            .chain(
+                signals
+                    .iter()
+                    .map(|signal| (IdentKind::Signal, signal.name.as_str())),
+            );
+        let constants_idents = constants
+            .iter()
+            .map(|prop| (IdentKind::Prop, prop.name.as_str()));
+
+        if let Err(e) = check_collisions(members.zip(member_spans))
+            .and_then(|_| check_collisions(constants_idents.zip(constant_spans)))
+        {
+            return self.diagnostics.push(e);
+        }
+
         let name = it.id.name.to_string();
         self.specs.insert(
             it.id.symbol_id(),
@@ -110,24 +229,93 @@ impl<'a> NativeModuleAnalyzer<'a> {
                 name,
                 methods,
                 signals,
+                constants,
             },
         );
     }
 
-    fn collect_interface_type(&mut self, it: &TSInterfaceDeclaration<'a>) {
+    fn collect_native_object(&mut self, it: &TSInterfaceDeclaration<'a>) {
         if let Err(e) = self.try_assert_reserved_type(&it.id.name) {
             return self.collect_error(&e.to_string(), it.span);
         };
 
-        if !it.extends.is_empty() {
-            return self.collect_error(INVALID_SPEC, it.span);
+        let mut methods = vec![];
+        let mut spans = vec![];
+        for sig in &it.body.body {
+            match sig {
+                TSSignature::TSMethodSignature(method_sig) => {
+                    let object_name = it.id.name.as_str();
+                    match self.with_scope(object_name, |this| this.try_into_method(method_sig)) {
+                        Ok(method) => {
+                            methods.push(method);
+                            spans.push(method_sig.span);
+                        }
+                        Err(e) => return self.diagnostics.push(e),
+                    }
+                }
+                _ => return self.collect_error(INVALID_NATIVE_OBJECT, it.span),
+            }
+        }
+
+        let idents = methods
+            .iter()
+            .map(|method| (IdentKind::Method, method.name.as_str()));
+        if let Err(e) = check_collisions(idents.zip(spans)) {
+            return self.diagnostics.push(e);
         }
 
         let id = it.id.symbol_id();
         let name = it.id.name.to_string();
 
+        self.record_type_name(&name, it.id.span);
+        self.decls.insert(
+            id,
+            TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name: name.clone() }),
+        );
+        self.objects.insert(id, NativeObject { name, methods });
+    }
+
+    fn collect_interface_type(&mut self, it: &TSInterfaceDeclaration<'a>) {
+        if let Err(e) = self.try_assert_reserved_type(&it.id.name) {
+            return self.collect_error(&e.to_string(), it.span);
+        };
+
+        let id = it.id.symbol_id();
+        let name = it.id.name.to_string();
+
+        match self.try_into_type_params(it.type_parameters.as_deref()) {
+            Ok(params) if params.is_empty() => {}
+            Ok(params) => drop(self.generics.insert(id, params)),
+            Err(e) => return self.diagnostics.push(e),
+        }
+
+        // Collect base types (flattened into the properties while resolving the references)
+        let mut bases = vec![];
+        for heritage in &it.extends {
+            let Expression::Identifier(ident_ref) = &heritage.expression else {
+                return self.collect_error(INVALID_SPEC, heritage.span);
+            };
+
+            let base = self.with_scope(&name, |this| {
+                this.try_into_type_ref(ident_ref, heritage.type_arguments.as_deref())
+            });
+
+            match base {
+                Ok(base @ (TypeAnnotation::Ref(..) | TypeAnnotation::Utility(..))) => {
+                    bases.push(base)
+                }
+                Ok(..) => return self.collect_error(INVALID_BASE_TYPE, heritage.span),
+                Err(e) => return self.collect_error(&e.to_string(), heritage.span),
+            }
+        }
+
+        if !bases.is_empty() {
+            self.bases.insert(id, bases);
+        }
+
         // Collect type alias
         let mut props = vec![];
+        let mut spans = vec![];
         for sig in &it.body.body {
             match sig {
                 TSSignature::TSPropertySignature(prop_sig) => {
@@ -135,8 +323,11 @@ impl<'a> NativeModuleAnalyzer<'a> {
                         return self.collect_error(INVALID_OPTIONAL_PROP, prop_sig.span);
                     }
 
-                    match self.try_into_prop(prop_sig) {
-                        Ok(prop) => props.push(prop),
+                    match self.with_scope(&name, |this| this.try_into_prop(prop_sig)) {
+                        Ok(prop) => {
+                            props.push(prop);
+                            spans.push(prop_sig.span);
+                        }
                         Err(e) => return self.diagnostics.push(e),
                     }
                 }
@@ -144,6 +335,14 @@ impl<'a> NativeModuleAnalyzer<'a> {
             }
         }
 
+        let idents = props
+            .iter()
+            .map(|prop| (IdentKind::Prop, prop.name.as_str()));
+        if let Err(e) = check_collisions(idents.zip(spans)) {
+            return self.diagnostics.push(e);
+        }
+
+        self.record_type_name(&name, it.id.span);
         self.decls.insert(
             id,
             TypeAnnotation::Object(ObjectTypeAnnotation { name, props }),
@@ -155,47 +354,50 @@ impl<'a> NativeModuleAnalyzer<'a> {
             return self.collect_error(&e.to_string(), it.span);
         };
 
-        if let Some(params) = &it.type_parameters {
-            if !params.params.is_empty() {
-                return self.collect_error("Type parameters are not supported", it.span);
-            }
-        }
-
         let id = it.id.symbol_id();
         let name = it.id.name.to_string();
 
-        match &it.type_annotation {
-            TSType::TSTypeLiteral(type_lit) => {
-                let props = type_lit
-                    .members
-                    .iter()
-                    .map(|member| match member {
-                        TSSignature::TSPropertySignature(prop_sig) => {
-                            if prop_sig.optional {
-                                Err(error(INVALID_OPTIONAL_PROP, prop_sig.span))
-                            } else {
-                                self.try_into_prop(prop_sig)
-                            }
-                        }
-                        _ => Err(error(INVALID_SPEC, type_lit.span)),
-                    })
-                    .collect::<Result<Vec<Prop>, OxcDiagnostic>>();
-
-                match props {
-                    Ok(props) => {
-                        self.decls.insert(
-                            id,
-                            TypeAnnotation::Object(ObjectTypeAnnotation { name, props }),
-                        );
+        match self.try_into_type_params(it.type_parameters.as_deref()) {
+            Ok(params) if params.is_empty() => {}
+            Ok(params) => drop(self.generics.insert(id, params)),
+            Err(e) => return self.diagnostics.push(e),
+        }
+
+        // `Readonly<T>` is the same as `T`
+        let mut ts_type = &it.type_annotation;
+        while let Some(inner) = Self::as_readonly(ts_type) {
+            ts_type = inner;
+        }
+
+        let type_annotation = match ts_type {
+            TSType::TSTypeLiteral(type_lit) => self
+                .try_into_object(type_lit, name)
+                .map(TypeAnnotation::Object),
+            TSType::TSUnionType(union_type) => self
+                .with_scope(&name, |this| this.try_into_nullable(union_type))
+                .map_err(|e| error(&e.to_string(), it.span)),
+            TSType::TSTypeReference(..) => {
+                match self.with_scope(&name, |this| this.try_into_type_annotation(ts_type)) {
+                    Ok(TypeAnnotation::Utility(mut utility)) => {
+                        // Evaluated object type is named after the alias
+                        utility.name = Some(name);
+                        Ok(TypeAnnotation::Utility(utility))
                     }
-                    Err(e) => self.diagnostics.push(e),
+                    // Alias of the other type (eg. `type UserPage = Page<User>`)
+                    Ok(type_ref @ TypeAnnotation::Ref(..)) => Ok(type_ref),
+                    Ok(..) => Err(error(INVALID_SPEC, it.span)),
+                    Err(e) => Err(error(&e.to_string(), it.span)),
                 }
             }
-            TSType::TSUnionType(union_type) => match self.try_into_nullable(union_type) {
-                Ok(type_annotation) => drop(self.decls.insert(id, type_annotation)),
-                Err(e) => self.diagnostics.push(error(&e.to_string(), it.span)),
-            },
-            _ => self.collect_error(INVALID_SPEC, it.span),
+            _ => Err(error(INVALID_SPEC, it.span)),
+        };
+
+        match type_annotation {
+            Ok(type_annotation) => {
+                self.record_type_name(&it.id.name, it.id.span);
+                self.decls.insert(id, type_annotation);
+            }
+            Err(e) => self.diagnostics.push(e),
         }
     }
 
@@ -204,7 +406,28 @@ impl<'a> NativeModuleAnalyzer<'a> {
         let mut prev_num_raw_val = 0;
         let mut member_type = None;
 
+        let names = it
+            .body
+            .members
+            .iter()
+            .map(|member| member.id.static_name())
+            .collect::<Vec<_>>();
+        let idents = names
+            .iter()
+            .map(|name| (IdentKind::EnumMember, name.as_ref()));
+        let spans = it.body.members.iter().map(|member| member.span);
+        if let Err(e) = check_collisions(idents.zip(spans)) {
+            return self.diagnostics.push(e);
+        }
+
         for (idx, member) in it.body.members.iter().enumerate() {
+            if let Err(diagnostic) =
+                check_ident(IdentKind::EnumMember, &member.id.static_name(), member.span)
+            {
+                self.diagnostics.push(diagnostic);
+                continue;
+            }
+
             match &member.initializer {
                 Some(expr) => match expr {
                     Expression::NumericLiteral(num_lit) => {
@@ -226,6 +449,7 @@ impl<'a> NativeModuleAnalyzer<'a> {
                             members.push(EnumMember {
                                 name: member.id.static_name().to_string(),
                                 value: EnumMemberValue::Number(raw),
+                                doc: self.doc_of(member.span),
                             });
                         }
                     }
@@ -241,6 +465,7 @@ impl<'a> NativeModuleAnalyzer<'a> {
                         members.push(EnumMember {
                             name: member.id.static_name().to_string(),
                             value: EnumMemberValue::String(str_lit.value.into_string()),
+                            doc: self.doc_of(member.span),
                         });
                     }
                     _ => self.collect_error(INVALID_SPEC, it.span),
@@ -257,11 +482,13 @@ impl<'a> NativeModuleAnalyzer<'a> {
                     members.push(EnumMember {
                         name: member.id.static_name().to_string(),
                         value: EnumMemberValue::Number(prev_num_raw_val + idx),
+                        doc: self.doc_of(member.span),
                     });
                 }
             };
         }
 
+        self.record_type_name(&it.id.name, it.id.span);
         self.decls.insert(
             it.id.symbol_id(),
             TypeAnnotation::Enum(EnumTypeAnnotation {
@@ -358,16 +585,23 @@ impl<'a> NativeModuleAnalyzer<'a> {
                     Ok(name) => name,
                     Err(e) => return Err(error(&e.to_string(), prop_sig.span)),
                 };
+                check_ident(IdentKind::Prop, &prop_name, prop_sig.span)?;
 
-                let type_annotation =
-                    match self.try_into_type_annotation(&type_annotation.type_annotation) {
-                        Ok(type_annotation) => type_annotation,
-                        Err(e) => return Err(error(&e.to_string(), prop_sig.span)),
-                    };
+                self.type_name = self.type_name_of(prop_sig.span);
+                let type_annotation = self.with_scope(&prop_name, |this| {
+                    this.try_into_type_annotation(&type_annotation.type_annotation)
+                });
+                self.type_name = None;
+
+                let type_annotation = match type_annotation {
+                    Ok(type_annotation) => type_annotation,
+                    Err(e) => return Err(error(&e.to_string(), prop_sig.span)),
+                };
 
                 Ok(Prop {
                     name: prop_name,
                     type_annotation,
+                    doc: self.doc_of(prop_sig.span),
                 })
             }
             _ => Err(error(INVALID_SPEC, prop_sig.span)),
@@ -388,15 +622,14 @@ impl<'a> NativeModuleAnalyzer<'a> {
             _ => return Err(error(INVALID_SPEC, sig.span)),
         };
 
-        if method_name == RESERVED_METHOD_NAME_MODULE {
-            return Err(error(INVALID_RESERVED_METHOD_NAME_ID, sig.span));
-        }
+        check_ident(IdentKind::Method, &method_name, sig.span)?;
 
         let params = sig
             .params
             .items
             .iter()
-            .map(|param| {
+            .enumerate()
+            .map(|(index, param)| {
                 if !param.decorators.is_empty() {
                     return Err(error(INVALID_SPEC, param.span));
                 }
@@ -411,9 +644,7 @@ impl<'a> NativeModuleAnalyzer<'a> {
                     .get_identifier_name()
                     .ok_or_else(|| error(INVALID_SPEC, param.span))?;
 
-                if param_name == RESERVED_ARG_NAME_MODULE {
-                    return Err(error(INVALID_RESERVED_ARG_NAME_ID, param.span));
-                }
+                check_ident(IdentKind::Param, &param_name, param.span)?;
 
                 let param_type_annotation = param
                     .pattern
@@ -421,7 +652,13 @@ impl<'a> NativeModuleAnalyzer<'a> {
                     .as_ref()
                     .ok_or_else(|| error(INVALID_SPEC, param.span))?;
 
-                match self.try_into_type_annotation(&param_type_annotation.type_annotation) {
+                let type_annotation = self.with_scope(&method_name, |this| {
+                    this.with_scope(format!("Arg{index}"), |this| {
+                        this.try_into_type_annotation(&param_type_annotation.type_annotation)
+                    })
+                });
+
+                match type_annotation {
                     Ok(type_annotation) => Ok(Param {
                         name: param_name.to_string(),
                         type_annotation,
@@ -431,16 +668,29 @@ impl<'a> NativeModuleAnalyzer<'a> {
             })
             .collect::<Result<Vec<Param>, OxcDiagnostic>>()?;
 
+        let idents = params
+            .iter()
+            .map(|param| (IdentKind::Param, param.name.as_str()));
+        let spans = sig.params.items.iter().map(|param| param.span);
+        check_collisions(idents.zip(spans))?;
+
         let ret_type = sig
             .return_type
             .as_ref()
             .ok_or_else(|| error(INVALID_SPEC, sig.span))?;
 
-        match self.try_into_type_annotation(&ret_type.type_annotation) {
+        let type_annotation = self.with_scope(&method_name, |this| {
+            this.with_scope("Return", |this| {
+                this.try_into_type_annotation(&ret_type.type_annotation)
+            })
+        });
+
+        match type_annotation {
             Ok(type_annotation) => Ok(Method {
                 name: method_name,
                 params,
                 ret_type: type_annotation,
+                doc: self.doc_of(sig.span),
             }),
             Err(e) => Err(error(&e.to_string(), sig.span)),
         }
@@ -455,6 +705,7 @@ impl<'a> NativeModuleAnalyzer<'a> {
             PropertyKey::StaticIdentifier(ident) => ident.name.to_string(),
             _ => return Err(error(INVALID_SPEC, sig.span)),
         };
+        check_ident(IdentKind::Signal, &event_name, sig.span)?;
 
         match &sig.type_annotation.as_ref().unwrap().type_annotation {
             TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
@@ -464,10 +715,13 @@ impl<'a> NativeModuleAnalyzer<'a> {
                         .get_reference(ident_ref.reference_id())
                         .symbol_id();
 
-                    if sym_id == self.mod_signal_sym_id {
+                    if self.mod_signal_sym_id.is_some() && sym_id == self.mod_signal_sym_id {
                         let payload_type = if let Some(type_args) = &type_ref.type_arguments {
                             if let Some(first_arg) = type_args.params.first() {
-                                self.try_into_type_annotation(first_arg).ok()
+                                self.with_scope(format!("{event_name}Payload"), |this| {
+                                    this.try_into_type_annotation(first_arg)
+                                })
+                                .ok()
                             } else {
                                 None
                             }
@@ -477,6 +731,7 @@ impl<'a> NativeModuleAnalyzer<'a> {
                         Ok(Signal {
                             name: event_name,
                             payload_type,
+                            doc: self.doc_of(sig.span),
                         })
                     } else {
                         Err(error(INVALID_SPEC, sig.span))
@@ -488,6 +743,40 @@ impl<'a> NativeModuleAnalyzer<'a> {
         }
     }
 
+    /// Collects the properties of the type literal into the object type of the given name.
+    fn try_into_object(
+        &mut self,
+        type_lit: &TSTypeLiteral<'a>,
+        name: String,
+    ) -> Result<ObjectTypeAnnotation, OxcDiagnostic> {
+        // Nested type literals are named after this object (eg. `FooBar` for `Foo.bar`)
+        let scope = std::mem::replace(&mut self.scope, vec![name.clone()]);
+        let props = type_lit
+            .members
+            .iter()
+            .map(|member| match member {
+                TSSignature::TSPropertySignature(prop_sig) => {
+                    if prop_sig.optional {
+                        Err(error(INVALID_OPTIONAL_PROP, prop_sig.span))
+                    } else {
+                        self.try_into_prop(prop_sig)
+                    }
+                }
+                _ => Err(error(INVALID_SPEC, type_lit.span)),
+            })
+            .collect::<Result<Vec<Prop>, OxcDiagnostic>>();
+        self.scope = scope;
+
+        let props = props?;
+        let idents = props
+            .iter()
+            .map(|prop| (IdentKind::Prop, prop.name.as_str()));
+        let spans = type_lit.members.iter().map(|member| member.span());
+        check_collisions(idents.zip(spans))?;
+
+        Ok(ObjectTypeAnnotation { name, props })
+    }
+
     fn try_into_prop_name(&self, key: &PropertyKey) -> Result<String, anyhow::Error> {
         match key {
             PropertyKey::StaticIdentifier(ident) => Ok(ident.name.to_string()),
@@ -509,30 +798,182 @@ impl<'a> NativeModuleAnalyzer<'a> {
                 Ok(TypeAnnotation::Array(Box::new(type_annotation)))
             }
             TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
-                TSTypeName::IdentifierReference(ident_ref) => match ident_ref.name.as_str() {
-                    RESERVED_TYPE_ARRAY_BUFFER => Ok(TypeAnnotation::ArrayBuffer),
-                    RESERVED_TYPE_PROMISE => match &type_ref.type_arguments {
-                        Some(type_args) if type_args.params.len() == 1 => {
-                            let resolved_type = type_args.params.first().unwrap();
-                            let resolved_type = self.try_into_type_annotation(resolved_type)?;
-                            Ok(TypeAnnotation::Promise(Box::new(resolved_type)))
-                        }
-                        _ => anyhow::bail!("Invalid promise type"),
-                    },
-                    _ => Ok(TypeAnnotation::Ref(RefTypeAnnotation {
-                        ref_id: ident_ref.reference_id(),
-                        name: ident_ref.name.to_string(),
-                    })),
-                },
+                TSTypeName::IdentifierReference(ident_ref) => {
+                    self.try_into_type_ref(ident_ref, type_ref.type_arguments.as_deref())
+                }
                 _ => anyhow::bail!(INVALID_TYPE_REFERENCE),
             },
             TSType::TSUnionType(union_type) => self.try_into_nullable(union_type),
-            TSType::TSTypeLiteral { .. } => anyhow::bail!(INVALID_TYPE_LITERAL),
+            TSType::TSTypeLiteral(type_lit) => {
+                let type_name = self.type_name.take();
+                let name = self
+                    .type_name_of(type_lit.span)
+                    .or(type_name)
+                    .unwrap_or_else(|| self.scope.iter().map(|name| pascal_case(name)).collect());
+
+                if name.is_empty() {
+                    anyhow::bail!(INVALID_TYPE_LITERAL);
+                }
+
+                self.record_type_name(&name, type_lit.span);
+
+                let obj = self
+                    .try_into_object(type_lit, name)
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+                Ok(TypeAnnotation::Object(obj))
+            }
             TSType::TSFunctionType { .. } => anyhow::bail!(INVALID_FUNC_PARAM),
             _ => anyhow::bail!(INVALID_SPEC),
         }
     }
 
+    fn try_into_type_ref(
+        &mut self,
+        ident_ref: &IdentifierReference<'a>,
+        type_args: Option<&TSTypeParameterInstantiation<'a>>,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        let type_params = type_args.map(|type_args| type_args.params.as_slice());
+
+        match (ident_ref.name.as_str(), type_params) {
+            (RESERVED_TYPE_ARRAY_BUFFER, None) => Ok(TypeAnnotation::ArrayBuffer),
+            // `Array<T>` and `ReadonlyArray<T>` are the same as `T[]`
+            (RESERVED_TYPE_ARRAY | RESERVED_TYPE_READONLY_ARRAY, Some([element_type])) => {
+                let element_type = self.try_into_type_annotation(element_type)?;
+                Ok(TypeAnnotation::Array(Box::new(element_type)))
+            }
+            (RESERVED_TYPE_ARRAY | RESERVED_TYPE_READONLY_ARRAY, _) => {
+                anyhow::bail!("Invalid array type")
+            }
+            (RESERVED_TYPE_PROMISE, Some([resolved_type])) => {
+                let resolved_type = self.try_into_type_annotation(resolved_type)?;
+                Ok(TypeAnnotation::Promise(Box::new(resolved_type)))
+            }
+            (RESERVED_TYPE_PROMISE, _) => anyhow::bail!("Invalid promise type"),
+            // `Readonly<T>` is the same as `T`
+            (RESERVED_TYPE_READONLY, Some([type_param])) => {
+                self.try_into_type_annotation(type_param)
+            }
+            (RESERVED_TYPE_READONLY, _) => anyhow::bail!("Invalid readonly type"),
+            (RESERVED_TYPE_PARTIAL, Some([type_param])) => {
+                self.try_into_utility(UtilityType::Partial, type_param, None, ident_ref.span)
+            }
+            (RESERVED_TYPE_PICK, Some([type_param, keys])) => {
+                self.try_into_utility(UtilityType::Pick, type_param, Some(keys), ident_ref.span)
+            }
+            (RESERVED_TYPE_OMIT, Some([type_param, keys])) => {
+                self.try_into_utility(UtilityType::Omit, type_param, Some(keys), ident_ref.span)
+            }
+            (RESERVED_TYPE_PARTIAL | RESERVED_TYPE_PICK | RESERVED_TYPE_OMIT, _) => {
+                anyhow::bail!("Invalid {} type", ident_ref.name)
+            }
+            (_, type_params) => {
+                let type_args = type_params
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|type_param| self.try_into_type_annotation(type_param))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(TypeAnnotation::Ref(RefTypeAnnotation {
+                    ref_id: ident_ref.reference_id(),
+                    name: ident_ref.name.to_string(),
+                    type_args,
+                    span: ident_ref.span,
+                }))
+            }
+        }
+    }
+
+    fn try_into_type_params(
+        &self,
+        params: Option<&TSTypeParameterDeclaration<'a>>,
+    ) -> Result<Vec<SymbolId>, OxcDiagnostic> {
+        let Some(params) = params else {
+            return Ok(vec![]);
+        };
+
+        params
+            .params
+            .iter()
+            .map(|param| {
+                if param.constraint.is_some()
+                    || param.default.is_some()
+                    || param.r#in
+                    || param.out
+                    || param.r#const
+                {
+                    Err(error(INVALID_TYPE_PARAM, param.span))
+                } else {
+                    Ok(param.name.symbol_id())
+                }
+            })
+            .collect()
+    }
+
+    /// Utility types are evaluated while resolving the references.
+    fn try_into_utility(
+        &mut self,
+        kind: UtilityType,
+        type_param: &TSType<'a>,
+        keys: Option<&TSType<'a>>,
+        span: Span,
+    ) -> Result<TypeAnnotation, anyhow::Error> {
+        let type_annotation = self.try_into_type_annotation(type_param)?;
+        if !matches!(
+            type_annotation,
+            TypeAnnotation::Ref(..) | TypeAnnotation::Object(..) | TypeAnnotation::Utility(..)
+        ) {
+            anyhow::bail!("{} type only supports object types", kind);
+        }
+
+        let keys = match keys {
+            Some(keys) => Self::try_into_keys(keys)
+                .ok_or_else(|| anyhow::anyhow!("{} keys must be string literals", kind))?,
+            None => vec![],
+        };
+
+        Ok(TypeAnnotation::Utility(UtilityTypeAnnotation {
+            span,
+            name: None,
+            kind,
+            type_annotation: Box::new(type_annotation),
+            keys,
+        }))
+    }
+
+    /// Property names of the string literal (union) type (eg. `'a' | 'b'`)
+    fn try_into_keys(ts_type: &TSType<'a>) -> Option<Vec<String>> {
+        match ts_type {
+            TSType::TSLiteralType(lit_type) => match &lit_type.literal {
+                TSLiteral::StringLiteral(lit) => Some(vec![lit.value.to_string()]),
+                _ => None,
+            },
+            TSType::TSUnionType(union_type) => union_type
+                .types
+                .iter()
+                .map(Self::try_into_keys)
+                .collect::<Option<Vec<_>>>()
+                .map(|keys| keys.concat()),
+            _ => None,
+        }
+    }
+
+    fn as_readonly<'b>(ts_type: &'b TSType<'a>) -> Option<&'b TSType<'a>> {
+        match ts_type {
+            TSType::TSTypeReference(type_ref) => {
+                match (&type_ref.type_name, &type_ref.type_arguments) {
+                    (TSTypeName::IdentifierReference(ident_ref), Some(type_args))
+                        if ident_ref.name == RESERVED_TYPE_READONLY
+                            && type_args.params.len() == 1 =>
+                    {
+                        type_args.params.first()
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     fn try_into_nullable(
         &mut self,
         union_type: &TSUnionType<'a>,
@@ -557,21 +998,35 @@ impl<'a> NativeModuleAnalyzer<'a> {
 
     /// Check the specification interface extends `NativeModule` interface of 'craby-modules' package.
     fn is_spec(&self, it: &TSInterfaceDeclaration<'a>) -> bool {
+        self.extends_interface(it, self.mod_type_sym_id, NATIVE_MODULE_INTERFACE)
+    }
+
+    /// Check the interface extends `NativeObject` interface of 'craby-modules' package.
+    fn is_native_object(&self, it: &TSInterfaceDeclaration<'a>) -> bool {
+        self.extends_interface(it, self.mod_object_sym_id, NATIVE_OBJECT_INTERFACE)
+    }
+
+    fn extends_interface(
+        &self,
+        it: &TSInterfaceDeclaration<'a>,
+        interface_sym_id: Option<SymbolId>,
+        interface_name: &str,
+    ) -> bool {
         it.extends.iter().any(|ex| {
             if let Some(ref_id) = ex.expression.get_identifier_reference() {
-                // Check if the expression is `NativeModule` of 'craby-modules' package
+                // Check if the expression is the interface of 'craby-modules' package
                 // eg. `import type { NativeModule } from 'craby-modules';`
                 let sym_id = self
                     .scoping
                     .get_reference(ref_id.reference_id())
                     .symbol_id();
-                sym_id == self.mod_type_sym_id
+                interface_sym_id.is_some() && sym_id == interface_sym_id
             } else if let Some(member_expr) = ex.expression.get_member_expr() {
                 // Check if the expression is `Namespace.NativeModule` of 'craby-modules' package
                 // eg. `import * as Namespace from 'craby-modules'`
                 if let Expression::Identifier(ident) = member_expr.object() {
                     let sym_id = self.scoping.get_reference(ident.reference_id()).symbol_id();
-                    member_expr.static_property_name() == Some(NATIVE_MODULE_INTERFACE)
+                    member_expr.static_property_name() == Some(interface_name)
                         && self
                             .mod_ns_sym_id
                             .zip(sym_id)
@@ -642,6 +1097,7 @@ impl<'a> NativeModuleAnalyzer<'a> {
         _decls: &FxHashMap<SymbolId, TypeAnnotation>,
         types: &mut FxHashSet<TypeAnnotation>,
         enums: &mut FxHashSet<TypeAnnotation>,
+        objects: &mut FxHashSet<String>,
     ) {
         match type_annotation {
             obj_type @ TypeAnnotation::Object(obj) => {
@@ -653,132 +1109,485 @@ impl<'a> NativeModuleAnalyzer<'a> {
                         _decls,
                         types,
                         enums,
+                        objects,
                     );
                 }
             }
             enum_type @ TypeAnnotation::Enum(..) => {
                 enums.insert(enum_type.clone());
             }
-            TypeAnnotation::Nullable(base_type) => {
-                NativeModuleAnalyzer::collect_types(base_type, _scoping, _decls, types, enums);
+            TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name }) => {
+                objects.insert(name.clone());
+            }
+            TypeAnnotation::Nullable(base_type) | TypeAnnotation::Array(base_type) => {
+                NativeModuleAnalyzer::collect_types(
+                    base_type, _scoping, _decls, types, enums, objects,
+                );
             }
             TypeAnnotation::Promise(resolved_type) => {
-                NativeModuleAnalyzer::collect_types(resolved_type, _scoping, _decls, types, enums);
+                NativeModuleAnalyzer::collect_types(
+                    resolved_type,
+                    _scoping,
+                    _decls,
+                    types,
+                    enums,
+                    objects,
+                );
             }
             _ => {}
         }
     }
 
-    fn resolve_refs(
-        type_annotation: &mut TypeAnnotation,
-        scoping: &Scoping,
-        decls: &FxHashMap<SymbolId, TypeAnnotation>,
-    ) {
-        match type_annotation {
-            TypeAnnotation::Ref(RefTypeAnnotation { ref_id, .. }) => {
-                match scoping.get_reference(*ref_id).symbol_id() {
-                    Some(sym_id) => {
-                        match decls.get(&sym_id) {
-                            Some(resolved) => {
-                                let mut resolved = resolved.clone();
-                                NativeModuleAnalyzer::resolve_refs(&mut resolved, scoping, decls);
-                                *type_annotation = resolved;
-                            }
-                            _ => unreachable!(
-                                "Symbol not found (ref: {:?}, sym: {:?})",
-                                ref_id, sym_id
-                            ),
-                        };
-                    }
-                    _ => unreachable!("Unknown type reference (ref: {:?})", ref_id),
-                }
-            }
-            TypeAnnotation::Object(obj) => {
-                for prop in &mut obj.props {
-                    NativeModuleAnalyzer::resolve_refs(&mut prop.type_annotation, scoping, decls);
-                }
-            }
-            TypeAnnotation::Nullable(base_type) => {
-                NativeModuleAnalyzer::resolve_refs(base_type, scoping, decls);
-            }
-            TypeAnnotation::Promise(t) => {
-                NativeModuleAnalyzer::resolve_refs(&mut *t, scoping, decls);
-            }
-            _ => {}
+    /// Resolves the type references of the method and collects the types used by the method.
+    fn resolve_method(
+        &self,
+        method: &mut Method,
+        types: &mut FxHashSet<TypeAnnotation>,
+        enums: &mut FxHashSet<TypeAnnotation>,
+        objects: &mut FxHashSet<String>,
+    ) -> Result<(), anyhow::Error> {
+        for param in &mut method.params {
+            self.resolve_refs(&mut param.type_annotation)?;
+            NativeModuleAnalyzer::collect_types(
+                &param.type_annotation,
+                self.scoping,
+                &self.decls,
+                types,
+                enums,
+                objects,
+            );
         }
+
+        // Resolve type annotation of return value
+        self.resolve_refs(&mut method.ret_type)?;
+        NativeModuleAnalyzer::collect_types(
+            &method.ret_type,
+            self.scoping,
+            &self.decls,
+            types,
+            enums,
+            objects,
+        );
+
+        Ok(())
     }
 
-    fn try_assert_reserved_type(&self, name: &Atom<'a>) -> Result<(), anyhow::Error> {
-        match name.as_str() {
-            RESERVED_TYPE_ARRAY_BUFFER | RESERVED_TYPE_PROMISE => {
-                anyhow::bail!("Cannot use reserved type: {}", name.as_str())
+    /// Constants are plain values, and the names should not conflict with the methods and signals.
+    fn try_assert_constant(
+        constant: &Prop,
+        methods: &[Method],
+        signals: &[Signal],
+    ) -> Result<(), anyhow::Error> {
+        match &constant.type_annotation {
+            TypeAnnotation::Void | TypeAnnotation::Promise(..) => {
+                anyhow::bail!("Invalid constant type: {}", constant.name)
+            }
+            type_annotation if type_annotation.contains_native_object() => {
+                anyhow::bail!("Native object cannot be a constant: {}", constant.name)
             }
             _ => {}
         }
 
-        if name.starts_with("Nullable") {
-            anyhow::bail!("Nullable prefix is not allowed: {}", name.as_str());
+        let is_conflict = methods.iter().any(|method| method.name == constant.name)
+            || signals.iter().any(|signal| signal.name == constant.name);
+
+        if is_conflict {
+            anyhow::bail!("Duplicate member name of the module: {}", constant.name);
         }
 
         Ok(())
     }
 
-    fn try_into_schema(self) -> Result<Vec<Schema>, anyhow::Error> {
-        let mut schemas = Vec::with_capacity(self.specs.len());
-
-        for (id, spec) in self.specs {
-            let mut types = FxHashSet::default();
-            let mut enums = FxHashSet::default();
-            let module_name = self
-                .mods
-                .get(&id)
-                .ok_or(anyhow::anyhow!("NativeModule name not found"))?;
+    /// Native objects can only be returned from the methods.
+    fn try_assert_native_object_usage(method: &Method) -> Result<(), anyhow::Error> {
+        if method
+            .params
+            .iter()
+            .any(|param| param.type_annotation.contains_native_object())
+        {
+            anyhow::bail!(
+                "Native object cannot be used as a parameter: {}",
+                method.name
+            );
+        }
 
-            let mut methods = spec
-                .methods
-                .into_iter()
-                .map(|mut method| {
-                    for param in &mut method.params {
-                        NativeModuleAnalyzer::resolve_refs(
-                            &mut param.type_annotation,
-                            self.scoping,
-                            &self.decls,
-                        );
+        match &method.ret_type {
+            TypeAnnotation::NativeObject(..) => Ok(()),
+            ret_type if ret_type.contains_native_object() => anyhow::bail!(
+                "Native object can only be returned directly (eg. not in `Promise`, array, nullable or object): {}",
+                method.name
+            ),
+            _ => Ok(()),
+        }
+    }
 
-                        NativeModuleAnalyzer::collect_types(
-                            &param.type_annotation,
-                            self.scoping,
-                            &self.decls,
-                            &mut types,
-                            &mut enums,
-                        );
-                    }
+    fn resolve_refs(&self, type_annotation: &mut TypeAnnotation) -> Result<(), anyhow::Error> {
+        self.resolve_refs_with(type_annotation, &mut vec![])
+    }
 
-                    // Resolve type annotation of return value
-                    NativeModuleAnalyzer::resolve_refs(
-                        &mut method.ret_type,
-                        self.scoping,
-                        &self.decls,
+    /// Resolves the type references in place.
+    ///
+    /// `visiting` holds the symbols being resolved to detect circular references.
+    fn resolve_refs_with(
+        &self,
+        type_annotation: &mut TypeAnnotation,
+        visiting: &mut Vec<SymbolId>,
+    ) -> Result<(), anyhow::Error> {
+        match type_annotation {
+            TypeAnnotation::Ref(RefTypeAnnotation {
+                ref_id,
+                name,
+                type_args,
+                span,
+            }) => {
+                let (sym_id, decl) = self
+                    .scoping
+                    .get_reference(*ref_id)
+                    .symbol_id()
+                    .and_then(|sym_id| self.decls.get(&sym_id).map(|decl| (sym_id, decl)))
+                    .ok_or_else(|| anyhow::anyhow!("Unknown type reference: {}", name))?;
+
+                let type_params = self.generics.get(&sym_id).cloned().unwrap_or_default();
+                if type_params.len() != type_args.len() {
+                    anyhow::bail!(
+                        "`{}` expects {} type argument(s), but got {}",
+                        name,
+                        type_params.len(),
+                        type_args.len()
                     );
+                }
 
-                    NativeModuleAnalyzer::collect_types(
-                        &method.ret_type,
-                        self.scoping,
-                        &self.decls,
-                        &mut types,
-                        &mut enums,
-                    );
+                for type_arg in type_args.iter_mut() {
+                    self.resolve_refs_with(type_arg, visiting)?;
+                }
 
-                    method
-                })
-                .collect::<Vec<Method>>();
+                if visiting.contains(&sym_id) {
+                    anyhow::bail!("Circular type reference: {}", name);
+                }
 
-            let mut signals = spec
+                let mut resolved = decl.clone();
+                let mut bases = self.bases.get(&sym_id).cloned().unwrap_or_default();
+
+                // Monomorphize the generic declaration (eg. `Page<User>` → `PageUser`)
+                let rename = if type_params.is_empty() {
+                    // Object type is named after the alias (eg. `type UserPage = Page<User>`)
+                    matches!(decl, TypeAnnotation::Ref(..)).then(|| name.clone())
+                } else {
+                    let instance_name = type_args
+                        .iter()
+                        .map(Self::type_arg_name)
+                        .collect::<Result<String, _>>()
+                        .map(|args| format!("{name}{args}"))?;
+                    let bindings = type_params
+                        .into_iter()
+                        .zip(type_args.iter().cloned())
+                        .collect::<FxHashMap<_, _>>();
+
+                    for type_annotation in std::iter::once(&mut resolved).chain(bases.iter_mut()) {
+                        self.substitute(type_annotation, &bindings, name, &instance_name)?;
+                    }
+
+                    self.record_type_name(&instance_name, *span);
+                    Some(instance_name)
+                };
+
+                visiting.push(sym_id);
+                self.inherit(&mut resolved, &bases, visiting)?;
+                self.resolve_refs_with(&mut resolved, visiting)?;
+                visiting.pop();
+
+                if let (Some(rename), TypeAnnotation::Object(obj)) = (rename, &mut resolved) {
+                    obj.name = rename;
+                }
+
+                *type_annotation = resolved;
+            }
+            TypeAnnotation::Utility(utility) => {
+                let mut target = (*utility.type_annotation).clone();
+                self.resolve_refs_with(&mut target, visiting)?;
+
+                let Some(ObjectTypeAnnotation { name, props }) = target.as_object() else {
+                    anyhow::bail!("{} type only supports object types", utility.kind);
+                };
+
+                let name = utility.name.clone().unwrap_or_else(|| {
+                    let name = utility
+                        .keys
+                        .iter()
+                        .fold(format!("{}{name}", utility.kind), |name, key| {
+                            name + &pascal_case(key)
+                        });
+                    self.record_type_name(&name, utility.span);
+                    name
+                });
+
+                if let Some(key) = utility
+                    .keys
+                    .iter()
+                    .find(|key| !props.iter().any(|prop| &prop.name == *key))
+                {
+                    anyhow::bail!("Unknown property of {}: {}", name, key);
+                }
+
+                let props = props
+                    .iter()
+                    .filter(|prop| match utility.kind {
+                        UtilityType::Partial => true,
+                        UtilityType::Pick => utility.keys.contains(&prop.name),
+                        UtilityType::Omit => !utility.keys.contains(&prop.name),
+                    })
+                    .cloned()
+                    .map(|mut prop| {
+                        if utility.kind == UtilityType::Partial
+                            && !prop.type_annotation.is_nullable()
+                        {
+                            prop.type_annotation =
+                                TypeAnnotation::Nullable(Box::new(prop.type_annotation));
+                        }
+                        prop
+                    })
+                    .collect();
+
+                *type_annotation = TypeAnnotation::Object(ObjectTypeAnnotation { name, props });
+            }
+            TypeAnnotation::Object(obj) => {
+                for prop in &mut obj.props {
+                    self.resolve_refs_with(&mut prop.type_annotation, visiting)?;
+                }
+            }
+            TypeAnnotation::Nullable(base_type) | TypeAnnotation::Array(base_type) => {
+                self.resolve_refs_with(base_type, visiting)?;
+            }
+            TypeAnnotation::Promise(t) => {
+                self.resolve_refs_with(&mut *t, visiting)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the type parameters of the generic declaration with the type arguments.
+    ///
+    /// Inline type literals of the declaration are renamed after the instance (eg. `PageMeta` → `PageUserMeta`).
+    fn substitute(
+        &self,
+        type_annotation: &mut TypeAnnotation,
+        bindings: &FxHashMap<SymbolId, TypeAnnotation>,
+        generic_name: &str,
+        instance_name: &str,
+    ) -> Result<(), anyhow::Error> {
+        match type_annotation {
+            TypeAnnotation::Ref(type_ref) => {
+                let sym_id = self.scoping.get_reference(type_ref.ref_id).symbol_id();
+
+                match sym_id.and_then(|sym_id| bindings.get(&sym_id)) {
+                    Some(_) if !type_ref.type_args.is_empty() => anyhow::bail!(
+                        "Type parameter cannot have type arguments: {}",
+                        type_ref.name
+                    ),
+                    Some(type_arg) => *type_annotation = type_arg.clone(),
+                    None => {
+                        for type_arg in &mut type_ref.type_args {
+                            self.substitute(type_arg, bindings, generic_name, instance_name)?;
+                        }
+                    }
+                }
+            }
+            TypeAnnotation::Object(obj) => {
+                if let Some(suffix) = obj.name.strip_prefix(generic_name) {
+                    obj.name = format!("{instance_name}{suffix}");
+                }
+
+                for prop in &mut obj.props {
+                    self.substitute(
+                        &mut prop.type_annotation,
+                        bindings,
+                        generic_name,
+                        instance_name,
+                    )?;
+                }
+            }
+            TypeAnnotation::Utility(utility) => {
+                self.substitute(
+                    &mut utility.type_annotation,
+                    bindings,
+                    generic_name,
+                    instance_name,
+                )?;
+            }
+            TypeAnnotation::Array(inner)
+            | TypeAnnotation::Nullable(inner)
+            | TypeAnnotation::Promise(inner) => {
+                self.substitute(inner, bindings, generic_name, instance_name)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Name of the type argument used to name the generic type instance (eg. `User[]` → `UserArray`).
+    fn type_arg_name(type_arg: &TypeAnnotation) -> Result<String, anyhow::Error> {
+        let name = match type_arg {
+            TypeAnnotation::Boolean => "Boolean".to_string(),
+            TypeAnnotation::Number => "Number".to_string(),
+            TypeAnnotation::String => "String".to_string(),
+            TypeAnnotation::ArrayBuffer => "ArrayBuffer".to_string(),
+            TypeAnnotation::Array(element_type) => {
+                format!("{}Array", Self::type_arg_name(element_type)?)
+            }
+            TypeAnnotation::Nullable(base_type) => {
+                format!("Nullable{}", Self::type_arg_name(base_type)?)
+            }
+            TypeAnnotation::Object(ObjectTypeAnnotation { name, .. })
+            | TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => name.clone(),
+            _ => anyhow::bail!("Unsupported type argument: {:?}", type_arg),
+        };
+
+        Ok(name)
+    }
+
+    /// Flattens the properties of the base types into the object type.
+    ///
+    /// Own properties override the inherited ones with the same name.
+    fn inherit(
+        &self,
+        type_annotation: &mut TypeAnnotation,
+        bases: &[TypeAnnotation],
+        visiting: &mut Vec<SymbolId>,
+    ) -> Result<(), anyhow::Error> {
+        let TypeAnnotation::Object(obj) = type_annotation else {
+            return Ok(());
+        };
+
+        let mut props: Vec<Prop> = vec![];
+        for base in bases {
+            let mut base = base.clone();
+            self.resolve_refs_with(&mut base, visiting)?;
+
+            let Some(base) = base.as_object() else {
+                anyhow::bail!("{}: {}", INVALID_BASE_TYPE, obj.name);
+            };
+
+            for prop in &base.props {
+                props.retain(|p| p.name != prop.name);
+                props.push(prop.clone());
+            }
+        }
+
+        props.retain(|p| !obj.props.iter().any(|prop| prop.name == p.name));
+        props.append(&mut obj.props);
+        obj.props = props;
+
+        // The inherited properties have no spans in the current interface
+        let idents = obj
+            .props
+            .iter()
+            .map(|prop| ((IdentKind::Prop, prop.name.as_str()), Span::default()));
+        if let Err(e) = check_collisions(idents) {
+            anyhow::bail!("{}: {}", e.message, obj.name);
+        }
+
+        Ok(())
+    }
+
+    fn try_assert_reserved_type(&self, name: &Atom<'a>) -> Result<(), anyhow::Error> {
+        match name.as_str() {
+            RESERVED_TYPE_ARRAY
+            | RESERVED_TYPE_ARRAY_BUFFER
+            | RESERVED_TYPE_PROMISE
+            | RESERVED_TYPE_READONLY
+            | RESERVED_TYPE_READONLY_ARRAY
+            | RESERVED_TYPE_PARTIAL
+            | RESERVED_TYPE_PICK
+            | RESERVED_TYPE_OMIT => {
+                anyhow::bail!("Cannot use reserved type: {}", name.as_str())
+            }
+            _ => {}
+        }
+
+        if name.starts_with("Nullable") {
+            anyhow::bail!("Nullable prefix is not allowed: {}", name.as_str());
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if the different types of the module have the same name
+    /// (eg. `interface MoveArg0` and the inline type literal of `move(arg0: { .. })`).
+    fn check_type_names(&self, schema: &Schema) -> Result<(), OxcDiagnostic> {
+        let mut types = FxHashMap::<&str, Option<u64>>::default();
+        let aliases = schema
+            .aliases
+            .iter()
+            .map(|alias| (alias.as_object().unwrap().name.as_str(), alias.to_id()));
+        let enums = schema.enums.iter().map(|enum_type| {
+            (
+                enum_type.as_enum().unwrap().name.as_str(),
+                enum_type.to_id(),
+            )
+        });
+        let decls = aliases.chain(enums).map(|(name, id)| (name, Some(id)));
+        // Native objects cannot share the name with any other type
+        let objects = schema
+            .objects
+            .iter()
+            .map(|object| (object.name.as_str(), None));
+
+        for (name, id) in decls.chain(objects) {
+            match types.insert(name, id) {
+                Some(prev_id) if prev_id.is_none() || prev_id != id => {
+                    let mut spans = self
+                        .type_spans
+                        .borrow()
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_default();
+                    spans.sort_by_key(|span| span.start);
+                    spans.dedup();
+
+                    return Err(OxcDiagnostic::error(format!(
+                        "Type name `{name}` is used by different types of the `{}` module",
+                        schema.module_name
+                    ))
+                    .with_labels(spans));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn try_into_schema(&mut self) -> Result<Vec<Schema>, anyhow::Error> {
+        let specs = std::mem::take(&mut self.specs);
+        let mut schemas = Vec::with_capacity(specs.len());
+
+        for (id, spec) in specs {
+            let mut types = FxHashSet::default();
+            let mut enums = FxHashSet::default();
+            let module_name = self
+                .mods
+                .get(&id)
+                .ok_or(anyhow::anyhow!("NativeModule name not found"))?;
+
+            let mut objects = FxHashSet::default();
+            let mut methods = spec
+                .methods
+                .into_iter()
+                .map(|mut method| {
+                    self.resolve_method(&mut method, &mut types, &mut enums, &mut objects)?;
+                    Ok(method)
+                })
+                .collect::<Result<Vec<Method>, anyhow::Error>>()?;
+
+            let mut signals = spec
                 .signals
                 .into_iter()
                 .map(|mut signal| {
                     if let Some(ref mut payload_type) = signal.payload_type {
-                        NativeModuleAnalyzer::resolve_refs(payload_type, self.scoping, &self.decls);
+                        self.resolve_refs(payload_type)?;
 
                         NativeModuleAnalyzer::collect_types(
                             payload_type,
@@ -786,11 +1595,89 @@ impl<'a> NativeModuleAnalyzer<'a> {
                             &self.decls,
                             &mut types,
                             &mut enums,
+                            &mut objects,
                         );
                     }
-                    signal
+                    Ok(signal)
+                })
+                .collect::<Result<Vec<Signal>, anyhow::Error>>()?;
+
+            let constants = spec
+                .constants
+                .into_iter()
+                .map(|mut constant| {
+                    self.resolve_refs(&mut constant.type_annotation)?;
+                    NativeModuleAnalyzer::collect_types(
+                        &constant.type_annotation,
+                        self.scoping,
+                        &self.decls,
+                        &mut types,
+                        &mut enums,
+                        &mut objects,
+                    );
+                    Ok(constant)
                 })
-                .collect::<Vec<Signal>>();
+                .collect::<Result<Vec<Prop>, anyhow::Error>>()?;
+
+            for constant in &constants {
+                NativeModuleAnalyzer::try_assert_constant(constant, &methods, &signals)?;
+            }
+
+            // Collect the native objects returned by the methods (including the ones returned by native objects)
+            let mut native_objects: Vec<NativeObject> = vec![];
+            let mut pending = objects.into_iter().collect::<Vec<_>>();
+            while let Some(name) = pending.pop() {
+                if native_objects.iter().any(|object| object.name == name) {
+                    continue;
+                }
+
+                let mut object = self
+                    .objects
+                    .values()
+                    .find(|object| object.name == name)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Native object not found: {}", name))?;
+
+                let mut nested_objects = FxHashSet::default();
+                for method in &mut object.methods {
+                    self.resolve_method(method, &mut types, &mut enums, &mut nested_objects)?;
+
+                    if let TypeAnnotation::Promise(..) = method.ret_type {
+                        anyhow::bail!(
+                            "Promise is not supported in native object methods: {}.{}",
+                            object.name,
+                            method.name
+                        );
+                    }
+                }
+
+                pending.extend(nested_objects);
+                native_objects.push(object);
+            }
+
+            for method in methods.iter().chain(
+                native_objects
+                    .iter()
+                    .flat_map(|object| object.methods.iter()),
+            ) {
+                NativeModuleAnalyzer::try_assert_native_object_usage(method)?;
+            }
+
+            if let Some(signal) = signals.iter().find(|signal| {
+                signal
+                    .payload_type
+                    .as_ref()
+                    .is_some_and(|payload_type| payload_type.contains_native_object())
+            }) {
+                anyhow::bail!("Native object cannot be a signal payload: {}", signal.name);
+            }
+
+            if let Some(alias) = types.iter().find(|alias| alias.contains_native_object()) {
+                anyhow::bail!(
+                    "Native object cannot be used as a property: {}",
+                    alias.as_object().unwrap().name
+                );
+            }
 
             let mut aliases = types.into_iter().collect::<Vec<_>>();
             let mut enums = enums.into_iter().collect::<Vec<_>>();
@@ -800,6 +1687,10 @@ impl<'a> NativeModuleAnalyzer<'a> {
             enums.sort_by_key(|v| v.as_enum().unwrap().name.to_lowercase());
             methods.sort_by_key(|v| v.name.to_lowercase());
             signals.sort_by_key(|v| v.name.to_lowercase());
+            native_objects.sort_by_key(|v| v.name.to_lowercase());
+            native_objects
+                .iter_mut()
+                .for_each(|object| object.methods.sort_by_key(|v| v.name.to_lowercase()));
 
             schemas.push(Schema {
                 module_name: module_name.to_owned(),
@@ -807,6 +1698,8 @@ impl<'a> NativeModuleAnalyzer<'a> {
                 enums,
                 methods,
                 signals,
+                objects: native_objects,
+                constants,
             });
         }
 
@@ -839,6 +1732,7 @@ impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
 
                     match imported_name.as_str() {
                         NATIVE_MODULE_INTERFACE => self.mod_type_sym_id = Some(symbol_id),
+                        NATIVE_OBJECT_INTERFACE => self.mod_object_sym_id = Some(symbol_id),
                         NATIVE_MODULE_REGISTRY => self.mod_reg_sym_id = Some(symbol_id),
                         SIGNAL_TYPE => self.mod_signal_sym_id = Some(symbol_id),
                         _ => {}
@@ -860,6 +1754,9 @@ impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
         if self.is_spec(it) {
             // Collect module spec
             self.collect_spec(it);
+        } else if self.is_native_object(it) {
+            // Collect native object (host object)
+            self.collect_native_object(it);
         } else {
             // Collect user defined type (interface)
             self.collect_interface_type(it);
@@ -890,6 +1787,115 @@ impl<'a> Visit<'a> for NativeModuleAnalyzer<'a> {
     }
 }
 
+/// Kind of the spec identifier that is converted into the identifiers of the generated code.
+#[derive(Clone, Copy)]
+enum IdentKind {
+    Method,
+    Param,
+    Prop,
+    Signal,
+    EnumMember,
+}
+
+/// Returns an error if the identifier is reserved by the generated code or cannot be escaped.
+///
+/// Keywords are escaped in the generated code (eg. `r#type` in Rust, `delete_` in C++), except the Rust keywords that
+/// cannot be raw identifiers (eg. `self`) and the signal and enum member names that are used as is.
+fn check_ident(kind: IdentKind, name: &str, span: Span) -> Result<(), OxcDiagnostic> {
+    match kind {
+        IdentKind::Method if reserved::METHOD_NAMES.contains(&name) => {
+            let message = format!("Reserved method name `{name}` is not allowed");
+            return Err(error(&message, span));
+        }
+        IdentKind::Param if reserved::PARAM_NAMES.contains(&name) => {
+            let message = format!("Reserved argument name `{name}` is not allowed");
+            return Err(error(&message, span));
+        }
+        _ => {}
+    }
+
+    // Rust and C++ identifiers of the generated code (`None` if the C++ identifier is escaped)
+    let (rs_ident, cxx_ident) = match kind {
+        IdentKind::Method | IdentKind::Param | IdentKind::Prop => (snake_case(name), None),
+        IdentKind::Signal => (pascal_case(name), Some(camel_case(name))),
+        IdentKind::EnumMember => (name.to_string(), Some(name.to_string())),
+    };
+
+    let rs_keyword = reserved::RUST_PATH_KEYWORDS.contains(&rs_ident.as_str())
+        || (cxx_ident.is_some() && reserved::RUST_KEYWORDS.contains(&rs_ident.as_str()));
+    if rs_keyword {
+        let message = format!("Reserved keyword `{rs_ident}` in Rust is not allowed");
+        return Err(error(&message, span));
+    }
+
+    if let Some(cxx_ident) =
+        cxx_ident.filter(|ident| reserved::CXX_KEYWORDS.contains(&ident.as_str()))
+    {
+        let message = format!("Reserved keyword `{cxx_ident}` in C++ is not allowed");
+        return Err(error(&message, span));
+    }
+
+    Ok(())
+}
+
+/// Returns the identifiers of the generated code that the spec identifier is converted into (with the kind of them).
+///
+/// `{module}_{method}` FFI functions and `cxx_name`s (eg. `myModuleMyMethod`) are named after the method names.
+fn generated_idents(kind: IdentKind, name: &str) -> Vec<(&'static str, String)> {
+    match kind {
+        IdentKind::Method => vec![
+            ("Rust method", rs_ident(&snake_case(name))),
+            ("C++ method", cxx_ident(&camel_case(name))),
+            ("FFI function", snake_case(name)),
+            ("cxx_name", pascal_case(name)),
+        ],
+        IdentKind::Param => vec![
+            ("Rust parameter", rs_ident(&snake_case(name))),
+            ("cxx bridge parameter", bridge_ident(&snake_case(name))),
+        ],
+        IdentKind::Prop => vec![
+            ("Rust field", rs_ident(&snake_case(name))),
+            ("cxx bridge field", bridge_ident(&snake_case(name))),
+            ("C++ variable", camel_case(name)),
+        ],
+        IdentKind::Signal => vec![
+            ("Rust enum variant", pascal_case(name)),
+            ("C++ method", camel_case(name)),
+            ("FFI function", snake_case(name)),
+        ],
+        IdentKind::EnumMember => vec![("enum member", name.to_string())],
+    }
+}
+
+/// Returns an error if the identifiers of the same scope are converted into the same identifier of the generated code
+/// (eg. `fooBar` and `foo_bar` are both `foo_bar` in Rust).
+fn check_collisions<'n>(
+    idents: impl IntoIterator<Item = ((IdentKind, &'n str), Span)>,
+) -> Result<(), OxcDiagnostic> {
+    let mut generated = FxHashMap::<(&'static str, String), (&'n str, Span)>::default();
+
+    for ((kind, name), span) in idents {
+        for (ident_kind, ident) in generated_idents(kind, name) {
+            match generated.get(&(ident_kind, ident.clone())) {
+                Some((prev_name, prev_span)) => {
+                    let message = if *prev_name == name {
+                        format!("Duplicate name `{name}`")
+                    } else {
+                        format!(
+                            "`{prev_name}` and `{name}` are converted into the same {ident_kind} `{ident}`"
+                        )
+                    };
+
+                    return Err(OxcDiagnostic::error(message).with_labels([*prev_span, span]));
+                }
+                None => drop(generated.insert((ident_kind, ident), (name, span))),
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
     let allocator = Allocator::default();
     let source_type = SourceType::tsx();
@@ -911,7 +1917,7 @@ pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
     }
 
     let scoping = ret.semantic.into_scoping();
-    let mut analyzer = NativeModuleAnalyzer::new(&scoping);
+    let mut analyzer = NativeModuleAnalyzer::new(&scoping).with_docs(src, &program.comments);
 
     analyzer.visit_program(&program);
 
@@ -924,6 +1930,14 @@ pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
     debug!("Collected decls: {:?}", analyzer.decls);
 
     let schemas = analyzer.try_into_schema()?;
+    let diagnostics = schemas
+        .iter()
+        .filter_map(|schema| analyzer.check_type_names(schema).err())
+        .collect::<Vec<_>>();
+
+    if !diagnostics.is_empty() {
+        return Err(ParseError::Oxc { diagnostics });
+    }
 
     Ok(schemas)
 }
@@ -932,7 +1946,10 @@ pub fn try_parse_schema(src: &str) -> Result<Vec<Schema>, ParseError> {
 mod tests {
     use insta::{assert_debug_snapshot, assert_snapshot};
 
-    use crate::{parser::native_spec_parser::try_parse_schema, types::Schema};
+    use crate::{
+        parser::{native_spec_parser::try_parse_schema, types::ParseError},
+        types::Schema,
+    };
 
     #[test]
     fn test_common_spec() {
@@ -1099,6 +2116,292 @@ mod tests {
         assert_debug_snapshot!(schemas);
     }
 
+    #[test]
+    fn test_interface_inheritance() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Base {
+            id: string;
+            name: string;
+        }
+
+        interface Timestamped {
+            createdAt: number;
+        }
+
+        interface User extends Base, Timestamped {
+            name: string | null;
+            email: string;
+        }
+
+        export interface Spec extends NativeModule {
+            getUser(): User;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let user = schemas[0].methods[0].ret_type.as_object().unwrap();
+        let props = user
+            .props
+            .iter()
+            .map(|prop| (prop.name.as_str(), prop.type_annotation.is_nullable()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(user.name, "User");
+        assert_eq!(
+            props,
+            vec![
+                ("id", false),
+                ("createdAt", false),
+                ("name", true),
+                ("email", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_circular_inheritance() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Foo extends Bar {
+            foo: string;
+        }
+
+        interface Bar extends Foo {
+            bar: string;
+        }
+
+        export interface Spec extends NativeModule {
+            getFoo(): Foo;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_utility_types() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface User {
+            id: string;
+            name: string;
+            age: number | null;
+        }
+
+        type UserPatch = Partial<Omit<User, 'id'>>;
+
+        type Options = Readonly<{
+            tags: ReadonlyArray<string>;
+        }>;
+
+        interface Profile extends Pick<User, 'id' | 'name'> {
+            bio: string;
+        }
+
+        export interface Spec extends NativeModule {
+            update(id: string, patch: UserPatch): Readonly<User>;
+            getSummary(options: Options): Pick<User, 'name'>;
+            getProfile(): Profile;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_utility_key() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface User {
+            id: string;
+        }
+
+        export interface Spec extends NativeModule {
+            getUser(): Pick<User, 'name'>;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_inline_type_literals() {
+        let src = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Shape {
+            /** @typeName Size */
+            size: { width: number; height: number };
+            origin: { x: number; y: number } | null;
+        }
+
+        export interface Spec extends NativeModule {
+            move(shape: Shape, to: { x: number; y: number }): { moved: boolean };
+            batch(points: /** @typeName Point */ { x: number; y: number }[]): void;
+            onMove: Signal<{ distance: number }>;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let names = schemas[0]
+            .aliases
+            .iter()
+            .map(|alias| alias.as_object().unwrap().name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec![
+                "MoveArg1",
+                "MoveReturn",
+                "OnMovePayload",
+                "Point",
+                "Shape",
+                "ShapeOrigin",
+                "Size"
+            ]
+        );
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_generic_types() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface User {
+            name: string;
+        }
+
+        type Page<T> = {
+            items: T[];
+            next: string | null;
+            meta: { total: number };
+        };
+
+        interface Entry<K, V> {
+            key: K;
+            value: V;
+        }
+
+        type UserPage = Page<User>;
+
+        export interface Spec extends NativeModule {
+            getUsers(): Page<User>;
+            getNames(): Page<string>;
+            getUserPage(): UserPage;
+            getEntries(): Entry<string, User | null>[];
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let names = schemas[0]
+            .aliases
+            .iter()
+            .map(|alias| alias.as_object().unwrap().name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec![
+                "EntryStringNullableUser",
+                "PageString",
+                "PageStringMeta",
+                "PageUser",
+                "PageUserMeta",
+                "User",
+                "UserPage"
+            ]
+        );
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_generic_types() {
+        let cases = [
+            // Missing type arguments
+            "type Page<T> = { items: T[] };
+            export interface Spec extends NativeModule { getPage(): Page; }",
+            // Higher-kinded type parameter
+            "interface User { name: string }
+            type Wrap<T> = { value: T<string> };
+            export interface Spec extends NativeModule { getUser(): Wrap<User>; }",
+            // Type parameter constraint
+            "interface User { name: string }
+            type Page<T extends User> = { items: T[] };
+            export interface Spec extends NativeModule { getPage(): Page<User>; }",
+        ];
+
+        for case in cases {
+            let src = format!(
+                "import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+                {case}
+                export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');"
+            );
+
+            assert!(try_parse_schema(&src).is_err(), "{case}");
+        }
+    }
+
+    #[test]
+    fn test_type_name_conflicts() {
+        let cases = [
+            // Inline type literal named after the method parameter
+            "interface MoveArg0 { y: string }
+            export interface Spec extends NativeModule { move(a: { x: number }, b: MoveArg0): void; }",
+            // Generic type instance (`Page<User>` → `PageUser`)
+            "interface User { name: string }
+            interface PageUser { id: number }
+            type Page<T> = { items: T[] };
+            export interface Spec extends NativeModule { getPage(): Page<User>; getUser(): PageUser; }",
+        ];
+
+        for case in cases {
+            let src = format!(
+                "import type {{ NativeModule }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+                {case}
+                export default NativeModuleRegistry.getEnforcing<Spec>('TestModule');"
+            );
+
+            match try_parse_schema(&src) {
+                Err(ParseError::Oxc { diagnostics }) => {
+                    let labels = diagnostics[0]
+                        .labels
+                        .as_ref()
+                        .map_or(0, |labels| labels.len());
+                    assert!(diagnostics[0].message.contains("used by different types"));
+                    assert_eq!(labels, 2, "{case}");
+                }
+                _ => panic!("{case}"),
+            }
+        }
+    }
+
     #[test]
     fn test_multiple_specs() {
         let src = "
@@ -1124,6 +2427,203 @@ mod tests {
         assert_debug_snapshot!(schemas);
     }
 
+    #[test]
+    fn test_constants() {
+        let src = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Limits {
+            maxSize: number;
+        }
+
+        export interface Spec extends NativeModule {
+            readonly VERSION: string;
+            readonly LIMITS: Limits;
+            readonly onChanged: Signal;
+            getVersion(): string;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas[0].constants.len() == 2);
+        assert!(schemas[0].signals.len() == 1);
+        assert!(schemas[0].aliases.len() == 1);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let src = "
+        import type { NativeModule, Signal } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        enum State {
+            /** Turned on */
+            On = 'on',
+            Off = 'off',
+        }
+
+        interface Options {
+            /** Timeout in milliseconds */
+            timeout: number;
+        }
+
+        export interface Spec extends NativeModule {
+            /** Current version */
+            readonly VERSION: string;
+            /**
+             * Emitted when the state changes.
+             */
+            onChanged: Signal<State>;
+            /**
+             * Multiplies two numbers.
+             *
+             * @deprecated Use `mul` instead.
+             */
+            multiply(a: number, b: number): number;
+            // Not a JSDoc comment
+            mul(a: number, b: number): number;
+            configure(options: Options): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+        let schema = &schemas[0];
+        let multiply = schema
+            .methods
+            .iter()
+            .find(|m| m.name == "multiply")
+            .unwrap();
+        let mul = schema.methods.iter().find(|m| m.name == "mul").unwrap();
+
+        assert_eq!(
+            multiply.doc.as_ref().unwrap().deprecated.as_deref(),
+            Some("Use `mul` instead.")
+        );
+        assert!(mul.doc.is_none());
+        assert!(schema.signals[0].doc.is_some());
+        assert!(schema.constants[0].doc.is_some());
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_constant() {
+        let src = "
+        import type { NativeModule } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Spec extends NativeModule {
+            readonly VERSION: Promise<string>;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_native_object() {
+        let src = "
+        import type { NativeModule, NativeObject } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        interface Chunk {
+            data: ArrayBuffer;
+        }
+
+        export interface Reader extends NativeObject {
+            next(): Chunk | null;
+            split(size: number): Reader;
+        }
+
+        export interface Spec extends NativeModule {
+            open(path: string): Reader;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let schemas = try_parse_schema(src).unwrap();
+
+        assert!(schemas.len() == 1);
+        assert!(schemas[0].objects.len() == 1);
+        assert!(schemas[0].aliases.len() == 1);
+        assert_debug_snapshot!(schemas);
+    }
+
+    #[test]
+    fn test_invalid_native_object_param() {
+        let src = "
+        import type { NativeModule, NativeObject } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Reader extends NativeObject {
+            close(): void;
+        }
+
+        export interface Spec extends NativeModule {
+            open(path: string): Reader;
+            close(reader: Reader): void;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_native_object_promise() {
+        let src = "
+        import type { NativeModule, NativeObject } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Reader extends NativeObject {
+            close(): void;
+        }
+
+        export interface Spec extends NativeModule {
+            open(path: string): Promise<Reader>;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_native_object_property() {
+        let src = "
+        import type { NativeModule, NativeObject } from 'craby-modules';
+        import { NativeModuleRegistry } from 'craby-modules';
+
+        export interface Reader extends NativeObject {
+            close(): void;
+        }
+
+        type Handle = {
+            reader: Reader;
+        };
+
+        export interface Spec extends NativeModule {
+            open(path: string): Handle;
+        }
+
+        export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+        let result = try_parse_schema(src);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_non_spec_1() {
         let src = "
@@ -1399,6 +2899,136 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_reserved_keywords() {
+        let specs = [
+            // Rust keywords that cannot be raw identifiers
+            "myMethod(self: number): void;",
+            "Self(): void;",
+            // Implemented by `#[craby_module]`
+            "id(): number;",
+            // Signals and enum members are used as is
+            "delete: Signal;",
+            "myMethod(arg: MyEnum): void;",
+        ];
+
+        for spec in specs {
+            let src = format!(
+                "
+                import type {{ NativeModule, Signal }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export enum MyEnum {{
+                    {member} = 0,
+                }}
+
+                export interface Spec extends NativeModule {{
+                    {spec}
+                }}
+
+                export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+                ",
+                member = if spec.contains("MyEnum") {
+                    "match"
+                } else {
+                    "Foo"
+                },
+            );
+
+            assert!(try_parse_schema(&src).is_err(), "{spec}");
+        }
+    }
+
+    #[test]
+    fn test_name_collisions() {
+        let specs = [
+            // `foo_bar` in Rust
+            ("fooBar(): void;\n foo_bar(): void;", "fooBar: number;"),
+            (
+                "myMethod(fooBar: number, foo_bar: number): void;",
+                "fooBar: number;",
+            ),
+            (
+                "myMethod(arg: MyObject): void;",
+                "fooBar: number;\n foo_bar: number;",
+            ),
+            // `r#type` in Rust
+            (
+                "myMethod(type: string, type_: string): void;",
+                "fooBar: number;",
+            ),
+            // `type_` in the cxx bridge
+            (
+                "myMethod(arg: MyObject): void;",
+                "type: string;\n type_: string;",
+            ),
+            // `delete` in Rust (`delete_` in C++)
+            ("delete(): void;\n delete_(): void;", "fooBar: number;"),
+            // `OnChange` variant of the signal enum
+            ("onChange: Signal;\n OnChange: Signal;", "fooBar: number;"),
+            // `onChange` method of the C++ TurboModule
+            ("onChange(): void;\n on_change: Signal;", "fooBar: number;"),
+        ];
+
+        for (spec, props) in specs {
+            let src = format!(
+                "
+                import type {{ NativeModule, Signal }} from 'craby-modules';
+                import {{ NativeModuleRegistry }} from 'craby-modules';
+
+                export interface MyObject {{
+                    {props}
+                }}
+
+                export interface Spec extends NativeModule {{
+                    {spec}
+                }}
+
+                export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+                ",
+            );
+
+            match try_parse_schema(&src) {
+                Err(ParseError::Oxc { diagnostics }) => {
+                    let labels = diagnostics[0]
+                        .labels
+                        .as_ref()
+                        .map_or(0, |labels| labels.len());
+                    assert_eq!(labels, 2, "{spec} {props}");
+                }
+                _ => panic!("{spec} {props}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_inherited_name_collisions() {
+        let src = "
+            import type { NativeModule } from 'craby-modules';
+            import { NativeModuleRegistry } from 'craby-modules';
+
+            export interface Base {
+                fooBar: number;
+            }
+
+            export interface MyObject extends Base {
+                foo_bar: number;
+            }
+
+            export interface Spec extends NativeModule {
+                myMethod(arg: MyObject): void;
+            }
+
+            export default NativeModuleRegistry.getEnforcing<Spec>('MyModule');
+        ";
+
+        let err = try_parse_schema(src).unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::General(e) if e.to_string().contains("`fooBar` and `foo_bar` are converted into the same")
+        ));
+    }
+
     #[test]
     fn test_optional_method() {
         let src: &'static str = "
@@ -1,7 +1,7 @@
 use std::hash::{DefaultHasher, Hash, Hasher};
 
-use oxc::{diagnostics::OxcDiagnostic, semantic::ReferenceId};
-use serde::Serialize;
+use oxc::{diagnostics::OxcDiagnostic, semantic::ReferenceId, span::Span};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,22 +20,26 @@ pub struct Spec {
     pub methods: Vec<Method>,
     /// Module signals
     pub signals: Vec<Signal>,
+    /// Module constants (`readonly` properties)
+    pub constants: Vec<Prop>,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct Method {
     pub name: String,
     pub params: Vec<Param>,
     pub ret_type: TypeAnnotation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<Doc>,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct Param {
     pub name: String,
     pub type_annotation: TypeAnnotation,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash)]
 pub enum TypeAnnotation {
     Void,
     Boolean,
@@ -47,8 +51,14 @@ pub enum TypeAnnotation {
     Enum(EnumTypeAnnotation),
     Promise(Box<TypeAnnotation>),
     Nullable(Box<TypeAnnotation>),
+    // Reference to `NativeObject` (host object)
+    NativeObject(NativeObjectTypeAnnotation),
     // Reference to `TypeAnnotation::Object` or `TypeAnnotation::Enum` or Alias types (eg. `Promise`)
+    #[serde(skip_deserializing)]
     Ref(RefTypeAnnotation),
+    // Utility type of an object type (eg. `Partial<T>`), evaluated while resolving the references
+    #[serde(skip_deserializing)]
+    Utility(UtilityTypeAnnotation),
 }
 
 impl TypeAnnotation {
@@ -75,33 +85,106 @@ impl TypeAnnotation {
     pub fn is_nullable(&self) -> bool {
         matches!(self, TypeAnnotation::Nullable(..))
     }
+
+    /// Returns `true` if the type contains a native object (eg. `Decoder[]`, `{ decoder: Decoder }`).
+    pub fn contains_native_object(&self) -> bool {
+        match self {
+            TypeAnnotation::NativeObject(..) => true,
+            TypeAnnotation::Array(inner)
+            | TypeAnnotation::Promise(inner)
+            | TypeAnnotation::Nullable(inner) => inner.contains_native_object(),
+            TypeAnnotation::Object(obj) => obj
+                .props
+                .iter()
+                .any(|prop| prop.type_annotation.contains_native_object()),
+            _ => false,
+        }
+    }
+
+    /// Returns the nullable types in this type, including the ones nested in arrays and promises.
+    ///
+    /// eg. `Promise<Array<number | null> | null>` returns `Array<number | null> | null` and `number | null`
+    pub fn nullable_types(&self) -> Vec<&TypeAnnotation> {
+        let mut types = vec![];
+        let mut current = Some(self);
+
+        while let Some(type_annotation) = current {
+            current = match type_annotation {
+                TypeAnnotation::Nullable(inner) => {
+                    types.push(type_annotation);
+                    Some(&**inner)
+                }
+                TypeAnnotation::Array(inner) | TypeAnnotation::Promise(inner) => Some(&**inner),
+                _ => None,
+            };
+        }
+
+        types
+    }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash)]
+pub struct NativeObjectTypeAnnotation {
+    pub name: String,
+}
+
+/// Rust object exposed to JS as a host object.
+///
+/// ```typescript
+/// export interface Decoder extends NativeObject {
+///   decode(data: ArrayBuffer): string;
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NativeObject {
+    pub name: String,
+    pub methods: Vec<Method>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash)]
 pub struct ObjectTypeAnnotation {
     pub name: String,
     pub props: Vec<Prop>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct Prop {
     pub name: String,
     pub type_annotation: TypeAnnotation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<Doc>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash)]
 pub struct EnumTypeAnnotation {
     pub name: String,
     pub members: Vec<EnumMember>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct EnumMember {
     pub name: String,
     pub value: EnumMemberValue,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<Doc>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+// Doc comments are not part of the type identity
+impl Hash for Prop {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.type_annotation.hash(state);
+    }
+}
+
+impl Hash for EnumMember {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.value.hash(state);
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash)]
 pub enum EnumMemberValue {
     String(String),
     Number(usize),
@@ -112,12 +195,123 @@ pub struct RefTypeAnnotation {
     #[serde(skip)]
     pub ref_id: ReferenceId,
     pub name: String,
+    /// Type arguments of the generic type (eg. `User` of `Page<User>`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub type_args: Vec<TypeAnnotation>,
+    /// Span of the reference (the generic type instances are named at this span)
+    #[serde(skip)]
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+pub struct UtilityTypeAnnotation {
+    /// Name of the evaluated object type (named after the utility type if not specified, eg. `PickUserId`)
+    pub name: Option<String>,
+    pub kind: UtilityType,
+    pub type_annotation: Box<TypeAnnotation>,
+    /// Property names of `Pick<T, K>` and `Omit<T, K>`
+    pub keys: Vec<String>,
+    #[serde(skip)]
+    pub span: Span,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Hash)]
+pub enum UtilityType {
+    Partial,
+    Pick,
+    Omit,
+}
+
+impl std::fmt::Display for UtilityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            UtilityType::Partial => "Partial",
+            UtilityType::Pick => "Pick",
+            UtilityType::Omit => "Omit",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct Signal {
     pub name: String,
     pub payload_type: Option<TypeAnnotation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<Doc>,
+}
+
+/// JSDoc comment of the spec.
+///
+/// ```typescript
+/// /**
+///  * Multiplies two numbers.
+///  *
+///  * @deprecated Use `mul` instead.
+///  */
+/// multiply(a: number, b: number): number;
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash)]
+pub struct Doc {
+    /// Comment lines without the `@deprecated` tag
+    pub lines: Vec<String>,
+    /// Message of the `@deprecated` tag (empty if the tag has no message)
+    pub deprecated: Option<String>,
+    /// Name of the `@typeName` tag, used to name the inline type literal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+}
+
+impl Doc {
+    /// Parses the content of the JSDoc comment (between `/*` and `*/`).
+    pub fn parse(content: &str) -> Option<Doc> {
+        let mut lines = vec![];
+        let mut deprecated = None;
+        let mut type_name = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            let line = line.strip_prefix(' ').unwrap_or(line).trim_end();
+
+            if let Some(message) = Self::strip_tag(line, "@deprecated") {
+                deprecated = Some(message.to_string());
+            } else if let Some(name) = Self::strip_tag(line, "@typeName") {
+                type_name = Some(name.to_string()).filter(|name| !name.is_empty());
+            } else {
+                lines.push(line.to_string());
+            }
+        }
+
+        // Trim the empty lines around the comment
+        while lines.first().is_some_and(|line| line.is_empty()) {
+            lines.remove(0);
+        }
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        if lines.is_empty() && deprecated.is_none() && type_name.is_none() {
+            return None;
+        }
+
+        Some(Doc {
+            lines,
+            deprecated,
+            type_name,
+        })
+    }
+
+    /// Whether the comment has nothing to document (eg. only the `@typeName` tag).
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty() && self.deprecated.is_none()
+    }
+
+    fn strip_tag<'a>(line: &'a str, tag: &str) -> Option<&'a str> {
+        line.strip_prefix(tag)
+            .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+            .map(str::trim)
+    }
 }
 
 #[cfg(test)]
@@ -131,6 +325,7 @@ mod tests {
             props: vec![Prop {
                 name: "prop".to_string(),
                 type_annotation: TypeAnnotation::String,
+                doc: None,
             }],
         });
 
@@ -139,6 +334,7 @@ mod tests {
             props: vec![Prop {
                 name: "prop".to_string(),
                 type_annotation: TypeAnnotation::String,
+                doc: None,
             }],
         });
 
@@ -148,10 +344,12 @@ mod tests {
                 Prop {
                     name: "prop".to_string(),
                     type_annotation: TypeAnnotation::String,
+                    doc: None,
                 },
                 Prop {
                     name: "prop2".to_string(),
                     type_annotation: TypeAnnotation::String,
+                    doc: None,
                 },
             ],
         });
@@ -159,4 +357,39 @@ mod tests {
         assert_eq!(t1.to_id(), t2.to_id());
         assert_ne!(t1.to_id(), t3.to_id());
     }
+
+    #[test]
+    fn test_nullable_types() {
+        let element = TypeAnnotation::Nullable(Box::new(TypeAnnotation::Number));
+        let array =
+            TypeAnnotation::Nullable(Box::new(TypeAnnotation::Array(Box::new(element.clone()))));
+        let ret = TypeAnnotation::Promise(Box::new(array.clone()));
+
+        assert_eq!(ret.nullable_types(), vec![&array, &element]);
+        assert!(TypeAnnotation::Number.nullable_types().is_empty());
+    }
+
+    #[test]
+    fn test_doc_parse() {
+        let doc = Doc::parse("*\n * Multiplies two numbers.\n *\n * @param a first\n * @deprecated Use `mul` instead.\n ").unwrap();
+
+        assert_eq!(
+            doc.lines,
+            vec!["Multiplies two numbers.", "", "@param a first"]
+        );
+        assert_eq!(doc.deprecated.as_deref(), Some("Use `mul` instead."));
+        assert_eq!(
+            Doc::parse("* @deprecated ").unwrap().deprecated.as_deref(),
+            Some("")
+        );
+        assert!(Doc::parse("*\n *\n ").is_none());
+
+        let doc = Doc::parse("* @typeName Point ").unwrap();
+        assert!(doc.lines.is_empty());
+        assert_eq!(doc.type_name.as_deref(), Some("Point"));
+        assert!(Doc::parse("* @typeNames Point")
+            .unwrap()
+            .type_name
+            .is_none());
+    }
 }
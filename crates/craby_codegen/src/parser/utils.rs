@@ -6,7 +6,8 @@ use oxc::{
 };
 
 pub struct RenderReportOptions<'a> {
-    pub project_root: &'a PathBuf,
+    /// Base directory of the reported file path
+    pub root: &'a PathBuf,
     pub path: &'a PathBuf,
     pub src: &'a str,
 }
@@ -17,7 +18,7 @@ pub fn render_report<'a>(diagnostics: Vec<OxcDiagnostic>, opts: RenderReportOpti
         .with_links(false);
 
     for diagnostic in
-        DiagnosticService::wrap_diagnostics(opts.project_root, opts.path, opts.src, diagnostics)
+        DiagnosticService::wrap_diagnostics(opts.root, opts.path, opts.src, diagnostics)
     {
         let mut output = String::new();
         if handler
@@ -1,16 +1,19 @@
 use std::collections::{btree_map::Entry as BTreeMapEntry, BTreeMap};
 
-use craby_common::utils::string::camel_case;
+use craby_common::utils::string::{camel_case, pascal_case};
 use indoc::formatdoc;
 use log::debug;
 use template::{cxx_arg_ref, cxx_arg_var};
 
 use crate::{
     common::IntoCode,
-    constants::specs::RESERVED_ARG_NAME_MODULE,
-    parser::types::{EnumTypeAnnotation, Method, ObjectTypeAnnotation, TypeAnnotation},
-    platform::cxx::template::CxxBridgingTemplate,
-    types::{CxxModuleName, CxxNamespace, Schema},
+    constants::{reserved::CXX_KEYWORDS, specs::RESERVED_ARG_NAME_MODULE},
+    parser::types::{
+        Doc, EnumTypeAnnotation, Method, NativeObject, NativeObjectTypeAnnotation,
+        ObjectTypeAnnotation, TypeAnnotation,
+    },
+    platform::{cxx::template::CxxBridgingTemplate, rust::native_object_struct_name},
+    types::{CxxHostObjectName, CxxModuleName, CxxNamespace, MethodAttrs, Schema},
     utils::{calc_deps_order, indent_str},
 };
 
@@ -45,6 +48,65 @@ pub struct CxxMethod {
     /// }
     /// ```
     pub impl_func: String,
+    /// JSDoc comment of the method
+    pub doc: Option<Doc>,
+}
+
+/// Returns the C++ identifier (keywords are escaped with the `_` suffix, eg. `delete_`).
+pub fn cxx_ident(name: &str) -> String {
+    if CXX_KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Prepends the C++ doc comment of the spec to the declaration (if exists).
+pub fn with_cxx_doc(doc: &Option<Doc>, decl: String) -> String {
+    match doc {
+        Some(doc) => format!("{}\n{decl}", doc.as_cxx_doc()),
+        None => decl,
+    }
+}
+
+impl Doc {
+    /// Converts the JSDoc comment to the C++ doc comment.
+    ///
+    /// # Generated Code
+    ///
+    /// ```cpp
+    /// /**
+    ///  * Multiplies two numbers.
+    ///  *
+    ///  * @deprecated Use `mul` instead.
+    ///  */
+    /// ```
+    pub fn as_cxx_doc(&self) -> String {
+        let mut lines = self.lines.clone();
+
+        if let Some(message) = &self.deprecated {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("@deprecated {message}"));
+        }
+
+        let lines = lines
+            .iter()
+            .map(|line| format!(" * {line}").trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("/**\n{lines}\n */")
+    }
+}
+
+#[derive(Debug)]
+pub struct CxxHostObject {
+    /// Host object class declaration
+    pub decl: String,
+    /// Host object class implementation
+    pub impl_class: String,
 }
 
 impl TypeAnnotation {
@@ -61,6 +123,7 @@ impl TypeAnnotation {
     /// craby::mymodule::bridging::MyEnum       // Enum
     /// craby::mymodule::bridging::MyStruct     // Object
     /// craby::mymodule::bridging::NullableNumber  // Nullable<Number>
+    /// rust::Box<craby::mymodule::bridging::DecoderObject>  // NativeObject
     /// ```
     pub fn as_cxx_type(&self, cxx_ns: &CxxNamespace) -> Result<String, anyhow::Error> {
         let cxx_type = match self {
@@ -78,6 +141,12 @@ impl TypeAnnotation {
             TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
                 format!("{cxx_ns}::bridging::{name}")
             }
+            TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name }) => {
+                format!(
+                    "rust::Box<{cxx_ns}::bridging::{}>",
+                    native_object_struct_name(name)
+                )
+            }
             TypeAnnotation::Nullable(type_annotation) => {
                 let cxx_struct = match &**type_annotation {
                     TypeAnnotation::Boolean => "NullableBoolean".to_string(),
@@ -220,8 +289,11 @@ impl TypeAnnotation {
 
     /// Returns the cxx `toJs` for the `TypeAnnotation`.
     ///
+    /// Native objects are wrapped with the host object (requires `callInvoker` in the scope).
+    ///
     /// ```cpp
     /// react::bridging::toJs(rt, value)
+    /// jsi::Object::createFromHostObject(rt, std::make_shared<CxxDecoderHostObject>(std::move(value), callInvoker))
     /// ```
     pub fn as_cxx_to_js(&self, ident: &str) -> Result<CxxToJs, anyhow::Error> {
         let to_js_expr = match self {
@@ -236,6 +308,10 @@ impl TypeAnnotation {
             TypeAnnotation::Promise(..) => {
                 format!("react::bridging::toJs(rt, {})", ident)
             }
+            TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name }) => format!(
+                "jsi::Object::createFromHostObject(rt, std::make_shared<{}>(std::move({ident}), callInvoker))",
+                CxxHostObjectName::from(name),
+            ),
             TypeAnnotation::Void => "jsi::Value::undefined()".to_string(),
             _ => {
                 return Err(anyhow::anyhow!(
@@ -250,6 +326,45 @@ impl TypeAnnotation {
 }
 
 impl Method {
+    /// Returns the argument variables and their declarations converted from the JS values.
+    ///
+    /// ```cpp
+    /// auto arg0 = react::bridging::fromJs<double>(rt, args[0], callInvoker);
+    /// auto arg1$raw = args[1].asString(rt).utf8(rt);
+    /// auto arg1 = rust::Str(arg1$raw.data(), arg1$raw.size());
+    /// ```
+    fn as_cxx_args(
+        &self,
+        cxx_ns: &CxxNamespace,
+    ) -> Result<(Vec<String>, Vec<String>), anyhow::Error> {
+        // ["arg0", "arg1", "arg2"]
+        let mut args = Vec::with_capacity(self.params.len() + 1);
+        // ["auto arg0 = facebook::react::bridging::fromJs<T>(rt, value, callInvoker)", "..."]
+        let mut args_decls = Vec::with_capacity(self.params.len());
+
+        for (idx, param) in self.params.iter().enumerate() {
+            let arg_ref = cxx_arg_ref(idx);
+            let arg_var = cxx_arg_var(idx);
+
+            // `rust::Str` holds a reference to `std::string`.
+            // To avoid dangling pointers, the converted `std::string` is retained within the scope for the lifetime of the reference.
+            let from_js = if let TypeAnnotation::String = &param.type_annotation {
+                // Capture the converted `std::string` within the scope of the reference
+                let str_var = format!("{arg_var}$raw");
+                args_decls.push(format!("auto {str_var} = {arg_ref}.asString(rt).utf8(rt);",));
+
+                // Convert the `std::string` to `rust::Str`
+                format!("rust::Str({str_var}.data(), {str_var}.size())")
+            } else {
+                param.type_annotation.as_cxx_from_js(cxx_ns, &arg_ref)?.expr
+            };
+            args.push(arg_var.clone());
+            args_decls.push(format!("auto {arg_var} = {from_js};"));
+        }
+
+        Ok((args, args_decls))
+    }
+
     /// Converts schema Method to C++ TurboModule method implementation.
     ///
     /// # Generated Code
@@ -284,87 +399,166 @@ impl Method {
         &self,
         cxx_ns: &CxxNamespace,
         cxx_mod: &CxxModuleName,
+        attrs: &MethodAttrs,
     ) -> Result<CxxMethod, anyhow::Error> {
-        let fn_name = camel_case(&self.name);
-        // ["arg0", "arg1", "arg2"]
-        let mut args = Vec::with_capacity(self.params.len() + 1);
-        // ["auto arg0 = facebook::react::bridging::fromJs<T>(rt, value, callInvoker)", "..."]
-        let mut args_decls = Vec::with_capacity(self.params.len());
+        let fn_name = cxx_ident(&camel_case(&self.name));
+        let (mut args, args_decls) = self.as_cxx_args(cxx_ns)?;
+
+        // `Trace` logs the execution time of the method when it goes out of scope
+        let trace_stmt = if attrs.trace {
+            format!("{cxx_ns}::utils::Trace trace({cxx_mod}::kModuleName, \"{fn_name}\");\n")
+        } else {
+            String::new()
+        };
 
-        for (idx, param) in self.params.iter().enumerate() {
-            let arg_ref = cxx_arg_ref(idx);
-            let arg_var = cxx_arg_var(idx);
+        // Promise methods are invoked on the thread pool
+        let async_ret_type = match &self.ret_type {
+            TypeAnnotation::Promise(resolve_type) => Some(&**resolve_type),
+            _ => None,
+        };
 
-            // `rust::Str` holds a reference to `std::string`.
-            // To avoid dangling pointers, the converted `std::string` is retained within the scope for the lifetime of the reference.
-            let from_js = if let TypeAnnotation::String = &param.type_annotation {
-                // Capture the converted `std::string` within the scope of the reference
-                let str_var = format!("{arg_var}$raw");
-                args_decls.push(format!("auto {str_var} = {arg_ref}.asString(rt).utf8(rt);",));
+        // Host objects are created on the JS thread
+        if async_ret_type.is_some_and(|ret_type| ret_type.contains_native_object()) {
+            anyhow::bail!(
+                "Native object cannot be returned from Promise methods: {}",
+                self.name
+            );
+        }
 
-                // Convert the `std::string` to `rust::Str`
-                format!("rust::Str({str_var}.data(), {str_var}.size())")
-            } else {
-                param.type_annotation.as_cxx_from_js(cxx_ns, &arg_ref)?.expr
-            };
-            args.push(arg_var.clone());
-            args_decls.push(format!("auto {arg_var} = {from_js};"));
+        // The spec must declare the Promise, JavaScript cannot receive a Promise from a synchronous method
+        if async_ret_type.is_none() {
+            if attrs.blocking {
+                anyhow::bail!(
+                    "`blocking` method must return a Promise in the spec: {}",
+                    self.name
+                );
+            }
+
+            if attrs.timeout.is_some() {
+                anyhow::bail!(
+                    "`timeout` is only supported for Promise methods: {}",
+                    self.name
+                );
+            }
         }
 
-        let invoke_stmts = match &self.ret_type {
-            TypeAnnotation::Promise(resolve_type) => {
-                let mut bind_args = Vec::with_capacity(args.len() + 2);
+        let invoke_stmts = match async_ret_type {
+            Some(resolve_type) => {
+                let mut bind_args = Vec::with_capacity(args.len() + 3);
                 bind_args.push(RESERVED_ARG_NAME_MODULE.to_string());
                 bind_args.push("promise".to_string());
+                if attrs.timeout.is_some() {
+                    bind_args.push("settlement".to_string());
+                }
                 bind_args.extend(args.clone());
 
                 args.insert(0, format!("*{}", RESERVED_ARG_NAME_MODULE));
                 let fn_args = args.join(", ");
 
-                let ret_stmts = if let TypeAnnotation::Void = &**resolve_type {
+                // Settles the promise only once if the method has a timeout
+                //
+                // ```cpp
+                // if (settlement->settle()) {
+                //   promise.resolve(ret);
+                // }
+                // ```
+                let settle = |stmt: String| {
+                    if attrs.timeout.is_some() {
+                        formatdoc! {
+                            r#"
+                            if (settlement->settle()) {{
+                              {stmt}
+                            }}"#,
+                        }
+                    } else {
+                        stmt
+                    }
+                };
+
+                let ret_stmts = if let TypeAnnotation::Void = resolve_type {
                     formatdoc! {
                         r#"
-                        {cxx_ns}::bridging::{fn_name}({fn_args});
-                        promise.resolve(std::monostate{{}});
+                        {trace_stmt}{cxx_ns}::bridging::{fn_name}({fn_args});
+                        {resolve}
                         "#,
+                        resolve = settle("promise.resolve(std::monostate{});".to_string()),
                     }
                 } else {
                     formatdoc! {
                         r#"
-                        auto ret = {cxx_ns}::bridging::{fn_name}({fn_args});
-                        promise.resolve(ret);
+                        {trace_stmt}auto ret = {cxx_ns}::bridging::{fn_name}({fn_args});
+                        {resolve}
                         "#,
+                        resolve = settle("promise.resolve(ret);".to_string()),
                     }
                 };
 
                 let bind_args = bind_args.join(", ");
                 let ret_stmts = indent_str(&ret_stmts, 4);
-                let ret_type = if let TypeAnnotation::Void = &**resolve_type {
+                let ret_type = if let TypeAnnotation::Void = resolve_type {
                     "std::monostate".to_string()
                 } else {
                     resolve_type.as_cxx_type(cxx_ns)?
                 };
-                let ret = self.ret_type.as_cxx_to_js("promise")?.expr;
+                let ret = TypeAnnotation::Promise(Box::new(resolve_type.clone()))
+                    .as_cxx_to_js("promise")?
+                    .expr;
+                let reject_js_err =
+                    indent_str(&settle("promise.reject(err.getMessage());".to_string()), 4);
+                let reject_err = indent_str(
+                    &settle(format!(
+                        "promise.reject({cxx_ns}::utils::errorMessage(err));"
+                    )),
+                    4,
+                );
+
+                // Rejects the promise if the method does not finish in time
+                //
+                // ```cpp
+                // thisModule.timer_->schedule(std::chrono::milliseconds(5000), [promise, settlement]() mutable {
+                //   if (settlement->settle()) {
+                //     promise.reject("...");
+                //   }
+                // });
+                // ```
+                let (settlement_decl, timeout_stmts) = match attrs.timeout {
+                    Some(timeout) => (
+                        format!(
+                            "auto settlement = std::make_shared<{cxx_ns}::utils::Settlement>();\n"
+                        ),
+                        formatdoc! {
+                            r#"
+
+                            thisModule.timer_->schedule(std::chrono::milliseconds({timeout}), [promise, settlement]() mutable {{
+                              if (settlement->settle()) {{
+                                promise.reject("{fn_name} timed out after {timeout}ms");
+                              }}
+                            }});
+                            "#,
+                        },
+                    ),
+                    None => (String::new(), String::new()),
+                };
 
                 // Create a promise object and invoke the FFI function in a separate thread
                 formatdoc! {
                     r#"
                     react::AsyncPromise<{ret_type}> promise(rt, callInvoker);
-
+                    {settlement_decl}
                     thisModule.threadPool_->enqueue([{bind_args}]() mutable {{
                       try {{
                     {ret_stmts}
                       }} catch (const jsi::JSError &err) {{
-                        promise.reject(err.getMessage());
+                    {reject_js_err}
                       }} catch (const std::exception &err) {{
-                        promise.reject({cxx_ns}::utils::errorMessage(err));
+                    {reject_err}
                       }}
                     }});
-
+                    {timeout_stmts}
                     return {ret};"#,
                 }
             }
-            _ => {
+            None => {
                 // Invoke the FFI function synchronously and return the result
                 //
                 // ```cpp
@@ -374,9 +568,9 @@ impl Method {
                 args.insert(0, format!("*{RESERVED_ARG_NAME_MODULE}"));
                 let fn_args = args.join(", ");
                 let ret_stmts = if let TypeAnnotation::Void = &self.ret_type {
-                    format!("{cxx_ns}::bridging::{fn_name}({fn_args});")
+                    format!("{trace_stmt}{cxx_ns}::bridging::{fn_name}({fn_args});")
                 } else {
-                    format!("auto ret = {cxx_ns}::bridging::{fn_name}({fn_args});")
+                    format!("{trace_stmt}auto ret = {cxx_ns}::bridging::{fn_name}({fn_args});")
                 };
 
                 formatdoc! {
@@ -430,10 +624,205 @@ impl Method {
             name: self.name.clone(),
             metadata,
             impl_func,
+            doc: self.doc.clone(),
         })
     }
 }
 
+impl NativeObject {
+    /// Converts the native object to the C++ `jsi::HostObject` class.
+    ///
+    /// The host object owns the Rust object (`rust::Box`), so the Rust object is dropped when the JS GC collects the host object.
+    ///
+    /// # Generated Code (Declaration)
+    ///
+    /// ```cpp
+    /// class JSI_EXPORT CxxDecoderHostObject
+    ///     : public facebook::jsi::HostObject,
+    ///       public std::enable_shared_from_this<CxxDecoderHostObject> {
+    /// public:
+    ///   CxxDecoderHostObject(
+    ///       rust::Box<craby::mymodule::bridging::DecoderObject> object,
+    ///       std::shared_ptr<facebook::react::CallInvoker> callInvoker);
+    ///
+    ///   facebook::jsi::Value get(facebook::jsi::Runtime &rt,
+    ///                            const facebook::jsi::PropNameID &name) override;
+    ///   std::vector<facebook::jsi::PropNameID> getPropertyNames(facebook::jsi::Runtime &rt) override;
+    ///
+    ///   facebook::jsi::Value decode(facebook::jsi::Runtime &rt,
+    ///                               const facebook::jsi::Value args[], size_t count);
+    ///
+    /// protected:
+    ///   std::shared_ptr<facebook::react::CallInvoker> callInvoker_;
+    ///   rust::Box<craby::mymodule::bridging::DecoderObject> object_;
+    /// };
+    /// ```
+    ///
+    /// # Generated Code (Implementation)
+    ///
+    /// ```cpp
+    /// jsi::Value CxxDecoderHostObject::get(jsi::Runtime &rt, const jsi::PropNameID &name) {
+    ///   auto prop = name.utf8(rt);
+    ///
+    ///   if (prop == "decode") {
+    ///     return jsi::Function::createFromHostFunction(rt, name, 1,
+    ///       [self = shared_from_this()](jsi::Runtime &rt, const jsi::Value &, const jsi::Value *args, size_t count) {
+    ///         return self->decode(rt, args, count);
+    ///       });
+    ///   }
+    ///
+    ///   return jsi::Value::undefined();
+    /// }
+    ///
+    /// jsi::Value CxxDecoderHostObject::decode(jsi::Runtime &rt, const jsi::Value args[], size_t count) {
+    ///   // ...
+    ///   auto ret = craby::mymodule::bridging::decoderDecode(*it_, arg0);
+    ///   return react::bridging::toJs(rt, ret);
+    /// }
+    /// ```
+    pub fn as_cxx_host_object(
+        &self,
+        cxx_ns: &CxxNamespace,
+    ) -> Result<CxxHostObject, anyhow::Error> {
+        let class_name = CxxHostObjectName::from(&self.name);
+        let object_type = TypeAnnotation::NativeObject(NativeObjectTypeAnnotation {
+            name: self.name.clone(),
+        })
+        .as_cxx_type(cxx_ns)?;
+
+        let mut method_defs = Vec::with_capacity(self.methods.len());
+        let mut method_impls = Vec::with_capacity(self.methods.len());
+        let mut props = Vec::with_capacity(self.methods.len());
+        let mut getters = Vec::with_capacity(self.methods.len());
+
+        for method in &self.methods {
+            let method_name = cxx_ident(&camel_case(&method.name));
+            let bridge_fn_name = format!("{}{}", camel_case(&self.name), pascal_case(&method.name));
+            let (mut args, args_decls) = method.as_cxx_args(cxx_ns)?;
+            let args_count = method.params.len();
+
+            args.insert(0, format!("*{RESERVED_ARG_NAME_MODULE}"));
+            let fn_args = args.join(", ");
+            let ret_stmts = if let TypeAnnotation::Void = &method.ret_type {
+                format!("{cxx_ns}::bridging::{bridge_fn_name}({fn_args});")
+            } else {
+                format!("auto ret = {cxx_ns}::bridging::{bridge_fn_name}({fn_args});")
+            };
+            let invoke_stmts = formatdoc! {
+                r#"
+                {args_decls}
+                {ret_stmts}
+
+                return {to_js};"#,
+                args_decls = args_decls.join("\n"),
+                to_js = method.ret_type.as_cxx_to_js("ret")?.expr,
+            };
+            let invoke_stmts = indent_str(invoke_stmts.trim(), 4);
+
+            method_defs.push(with_cxx_doc(
+                &method.doc,
+                formatdoc! {
+                    r#"
+                    facebook::jsi::Value {method_name}(facebook::jsi::Runtime &rt,
+                        const facebook::jsi::Value args[], size_t count);"#,
+                },
+            ));
+
+            method_impls.push(formatdoc! {
+                r#"
+                jsi::Value {class_name}::{method_name}(jsi::Runtime &rt,
+                                                       const jsi::Value args[],
+                                                       size_t count) {{
+                  auto callInvoker = callInvoker_;
+                  auto &{it} = object_;
+
+                  try {{
+                    if ({args_count} != count) {{
+                      throw jsi::JSError(rt, "Expected {args_count} argument{plural}");
+                    }}
+
+                {invoke_stmts}
+                  }} catch (const jsi::JSError &err) {{
+                    throw err;
+                  }} catch (const std::exception &err) {{
+                    throw jsi::JSError(rt, {cxx_ns}::utils::errorMessage(err));
+                  }}
+                }}"#,
+                it = RESERVED_ARG_NAME_MODULE,
+                plural = if args_count > 1 { "s" } else { "" },
+            });
+
+            props.push(format!(
+                "names.push_back(jsi::PropNameID::forAscii(rt, \"{}\"));",
+                method.name
+            ));
+
+            getters.push(formatdoc! {
+                r#"
+                if (prop == "{name}") {{
+                  return jsi::Function::createFromHostFunction(rt, name, {args_count},
+                    [self = shared_from_this()](jsi::Runtime &rt, const jsi::Value &, const jsi::Value *args, size_t count) {{
+                      return self->{method_name}(rt, args, count);
+                    }});
+                }}"#,
+                name = method.name,
+            });
+        }
+
+        let method_defs = indent_str(&method_defs.join("\n\n"), 2);
+        let decl = formatdoc! {
+            r#"
+            class JSI_EXPORT {class_name}
+                : public facebook::jsi::HostObject,
+                  public std::enable_shared_from_this<{class_name}> {{
+            public:
+              {class_name}(
+                  {object_type} object,
+                  std::shared_ptr<facebook::react::CallInvoker> callInvoker);
+
+              facebook::jsi::Value get(facebook::jsi::Runtime &rt,
+                                       const facebook::jsi::PropNameID &name) override;
+              std::vector<facebook::jsi::PropNameID> getPropertyNames(facebook::jsi::Runtime &rt) override;
+
+            {method_defs}
+
+            protected:
+              std::shared_ptr<facebook::react::CallInvoker> callInvoker_;
+              {object_type} object_;
+            }};"#,
+        };
+
+        let getters = indent_str(&getters.join("\n\n"), 2);
+        let props = indent_str(&props.join("\n"), 2);
+        let method_impls = method_impls.join("\n\n");
+        let impl_class = formatdoc! {
+            r#"
+            {class_name}::{class_name}(
+                {object_type} object,
+                std::shared_ptr<react::CallInvoker> callInvoker)
+                : callInvoker_(std::move(callInvoker)), object_(std::move(object)) {{}}
+
+            jsi::Value {class_name}::get(jsi::Runtime &rt, const jsi::PropNameID &name) {{
+              auto prop = name.utf8(rt);
+
+            {getters}
+
+              return jsi::Value::undefined();
+            }}
+
+            std::vector<jsi::PropNameID> {class_name}::getPropertyNames(jsi::Runtime &rt) {{
+              std::vector<jsi::PropNameID> names;
+            {props}
+              return names;
+            }}
+
+            {method_impls}"#,
+        };
+
+        Ok(CxxHostObject { decl, impl_class })
+    }
+}
+
 impl Schema {
     /// Generates C++ bridging templates for custom types (structs, enums, nullables).
     ///
@@ -510,6 +899,17 @@ impl Schema {
         ordered_templates.extend(bridging_templates.into_values());
         ordered_templates.extend(nullable_bridging_templates.into_values());
 
+        // Module constants may depend on all the other types
+        if let Some(constants_type) = self.constants_type() {
+            ordered_templates.push(
+                CxxBridgingTemplate::try_into_struct_template(
+                    &cxx_ns,
+                    constants_type.as_object().unwrap(),
+                )?
+                .into_code(),
+            );
+        }
+
         Ok(ordered_templates)
     }
 
@@ -521,7 +921,7 @@ impl Schema {
     /// template <>
     /// struct Bridging<craby::mymodule::bridging::NullableNumber> {
     ///   static craby::mymodule::bridging::NullableNumber fromJs(jsi::Runtime &rt, const jsi::Value& value, std::shared_ptr<CallInvoker> callInvoker) {
-    ///     if (value.isNull()) {
+    ///     if (value.isNull() || value.isUndefined()) {
     ///       return craby::mymodule::bridging::NullableNumber{true, 0.0};
     ///     }
     ///
@@ -547,27 +947,30 @@ impl Schema {
         let cxx_ns = CxxNamespace::from(project_name);
         let mut templates = BTreeMap::new();
 
-        for method in &self.methods {
-            for param in &method.params {
-                if let nullable_type @ TypeAnnotation::Nullable(inner_type_annotation) =
-                    &param.type_annotation
-                {
-                    let key = nullable_type.as_cxx_type(&cxx_ns)?;
-                    if let BTreeMapEntry::Vacant(e) = templates.entry(key) {
-                        let bridging_template = CxxBridgingTemplate::try_into_nullable_template(
-                            &cxx_ns,
-                            nullable_type,
-                            inner_type_annotation,
-                        )?
-                        .into_code();
-                        e.insert(bridging_template);
-                    }
-                }
-            }
+        let methods = self
+            .methods
+            .iter()
+            .chain(self.objects.iter().flat_map(|object| object.methods.iter()));
+        let method_types = methods.flat_map(|method| {
+            method
+                .params
+                .iter()
+                .map(|param| &param.type_annotation)
+                .chain(std::iter::once(&method.ret_type))
+        });
+        let prop_types = self
+            .aliases
+            .iter()
+            .flat_map(|type_annotation| type_annotation.as_object().unwrap().props.iter())
+            .chain(self.constants.iter())
+            .map(|prop| &prop.type_annotation);
+
+        for type_annotation in method_types.chain(prop_types) {
+            for nullable_type in type_annotation.nullable_types() {
+                let TypeAnnotation::Nullable(inner_type_annotation) = nullable_type else {
+                    unreachable!();
+                };
 
-            if let nullable_type @ TypeAnnotation::Nullable(inner_type_annotation) =
-                &method.ret_type
-            {
                 let key = nullable_type.as_cxx_type(&cxx_ns)?;
                 if let BTreeMapEntry::Vacant(e) = templates.entry(key) {
                     let bridging_template = CxxBridgingTemplate::try_into_nullable_template(
@@ -581,25 +984,6 @@ impl Schema {
             }
         }
 
-        for type_annotation in &self.aliases {
-            for prop in &type_annotation.as_object().unwrap().props {
-                if let nullable_type @ TypeAnnotation::Nullable(inner_type_annotation) =
-                    &prop.type_annotation
-                {
-                    let key = nullable_type.as_cxx_type(&cxx_ns)?;
-                    if let BTreeMapEntry::Vacant(e) = templates.entry(key) {
-                        let bridging_template = CxxBridgingTemplate::try_into_nullable_template(
-                            &cxx_ns,
-                            nullable_type,
-                            inner_type_annotation,
-                        )?
-                        .into_code();
-                        e.insert(bridging_template);
-                    }
-                }
-            }
-        }
-
         Ok(templates)
     }
 }
@@ -614,6 +998,7 @@ pub mod template {
             EnumMemberValue as ParserEnumMemberValue, EnumTypeAnnotation, ObjectTypeAnnotation,
             TypeAnnotation,
         },
+        platform::rust::bridge_ident,
         types::CxxNamespace,
         utils::indent_str,
     };
@@ -713,7 +1098,7 @@ pub mod template {
                 let from_js = prop.type_annotation.as_cxx_from_js(cxx_ns, &ident)?;
                 let to_js = prop
                     .type_annotation
-                    .as_cxx_to_js(&format!("value.{}", snake_case(&prop.name)))?;
+                    .as_cxx_to_js(&format!("value.{}", bridge_ident(&snake_case(&prop.name))))?;
 
                 // ```cpp
                 // auto obj$name = obj.getProperty(rt, "name");
@@ -966,7 +1351,7 @@ pub mod template {
         /// template <>
         /// struct Bridging<craby::mymodule::bridging::NullableNumber> {
         ///   static craby::mymodule::bridging::NullableNumber fromJs(jsi::Runtime &rt, const jsi::Value& value, std::shared_ptr<CallInvoker> callInvoker) {
-        ///     if (value.isNull()) {
+        ///     if (value.isNull() || value.isUndefined()) {
         ///       return craby::mymodule::bridging::NullableNumber{true, 0.0};
         ///     }
         ///
@@ -996,7 +1381,7 @@ pub mod template {
 
             let from_js_impl = formatdoc! {
                 r#"
-                if (value.isNull()) {{
+                if (value.isNull() || value.isUndefined()) {{
                   return {nullable_type_namespace}{{true, {default_value}}};
                 }}
 
@@ -8,12 +8,18 @@ use rustc_hash::FxHashMap;
 
 use crate::{
     common::IntoCode,
-    constants::specs::RESERVED_ARG_NAME_MODULE,
+    constants::{
+        reserved::{CXX_KEYWORDS, RUST_KEYWORDS},
+        specs::RESERVED_ARG_NAME_MODULE,
+    },
     parser::types::{
-        EnumTypeAnnotation, Method, ObjectTypeAnnotation, Param, RefTypeAnnotation, TypeAnnotation,
+        Doc, EnumTypeAnnotation, Method, NativeObject, NativeObjectTypeAnnotation,
+        ObjectTypeAnnotation, Param, RefTypeAnnotation, TypeAnnotation,
     },
+    platform::cxx::cxx_ident,
     platform::rust::template::{
-        collect_alias_default_impls, RsDefaultImpl, RsNullableStruct, RsStruct,
+        collect_alias_bridge_impls, RsDefaultImpl, RsImplStruct, RsNativeObject, RsNullableStruct,
+        RsStruct,
     },
     types::Schema,
     utils::indent_str,
@@ -55,17 +61,18 @@ pub struct RsCxxBridge {
     /// type MyModule;
     /// ```
     pub impl_type: String,
-    /// The struct definition.
+    /// The struct definitions with the type IDs (shared by the modules).
     ///
     /// ```rust,ignore
-    /// struct MyStruct {
+    /// #[cxx_name = "MyStruct"]
+    /// struct MyStructBridge {
     ///   foo: String,
     ///   bar: f64,
     ///   baz: bool,
     /// }
     /// ```
-    pub struct_defs: Vec<String>,
-    /// The enum definition.
+    pub struct_defs: Vec<(u64, String)>,
+    /// The enum definitions with the type IDs.
     ///
     /// ```rust,ignore
     /// enum MyEnum {
@@ -74,7 +81,7 @@ pub struct RsCxxBridge {
     ///   Baz,
     /// }
     /// ```
-    pub enum_defs: Vec<String>,
+    pub enum_defs: Vec<(u64, String)>,
     /// The extern function declaration.
     ///
     /// **Example**
@@ -110,8 +117,9 @@ impl TypeAnnotation {
     /// String                        // String
     /// Vec<f64>                      // Array<Number>
     /// MyEnum                        // Enum
-    /// MyStruct                      // Object
+    /// MyStructBridge                // Object
     /// NullableNumber                // Nullable<Number>
+    /// Box<MyObjectObject>           // NativeObject
     /// Result<f64, anyhow::Error>    // Promise<Number>
     /// ```
     pub fn as_rs_type(&self) -> Result<RsType, anyhow::Error> {
@@ -130,8 +138,11 @@ impl TypeAnnotation {
                 }
                 format!("Vec<{}>", element_type.as_rs_type()?.into_code())
             }
-            TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => name.clone(),
+            TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => bridge_struct_name(name),
             TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => name.clone(),
+            TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name }) => {
+                format!("Box<{}>", native_object_struct_name(name))
+            }
             TypeAnnotation::Promise(resolve_type) => {
                 format!(
                     "Result<{}, anyhow::Error>",
@@ -222,7 +233,8 @@ impl TypeAnnotation {
     /// ArrayBuffer      // ArrayBuffer (aliased Vec<u8>)
     /// Array<Number>    // Array<Number>
     /// Promise<Number>  // Promise<Number>
-    /// Nullable<Number> // Nullable<Number>
+    /// Option<Number>   // Nullable<Number>
+    /// NativeObject<dyn MyObjectSpec>  // NativeObject
     /// ```
     pub fn as_rs_impl_type(&self) -> Result<RsImplType, anyhow::Error> {
         let rs_type = match self {
@@ -242,18 +254,73 @@ impl TypeAnnotation {
             }
             TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => name.clone(),
             TypeAnnotation::Enum(EnumTypeAnnotation { name, .. }) => name.clone(),
+            TypeAnnotation::NativeObject(NativeObjectTypeAnnotation { name }) => {
+                format!("NativeObject<dyn {}>", native_object_spec_name(name))
+            }
             TypeAnnotation::Promise(resolved_type) => {
                 format!("Promise<{}>", resolved_type.as_rs_impl_type()?.into_code())
             }
             TypeAnnotation::Nullable(type_annotation) => {
                 let type_annotation = type_annotation.as_rs_impl_type()?.into_code();
-                format!("Nullable<{type_annotation}>")
+                format!("Option<{type_annotation}>")
             }
-            TypeAnnotation::Ref(..) => unreachable!(),
+            TypeAnnotation::Ref(..) | TypeAnnotation::Utility(..) => unreachable!(),
         };
         Ok(RsImplType(rs_type))
     }
 
+    /// Returns `true` if the value should be converted between the bridge type and the implementation type.
+    fn needs_rs_conversion(&self) -> bool {
+        match self {
+            TypeAnnotation::Object(..)
+            | TypeAnnotation::Nullable(..)
+            | TypeAnnotation::NativeObject(..) => true,
+            TypeAnnotation::Array(element_type) | TypeAnnotation::Promise(element_type) => {
+                element_type.needs_rs_conversion()
+            }
+            _ => false,
+        }
+    }
+
+    /// Generates the expression that converts the value between the bridge type and the implementation type.
+    ///
+    /// Conversions are implemented with `From` in both directions, so the same expression is used for arguments and return values.
+    ///
+    /// # Generated Code Examples
+    ///
+    /// ```rust,ignore
+    /// arg                                        // Number
+    /// arg.into()                                 // Object, Nullable<Number>, NativeObject
+    /// arg.into_iter().map(Into::into).collect()  // Array<Object>
+    /// ret.map(Into::into)                        // Promise<Object>
+    /// ```
+    pub fn as_rs_conversion(&self, expr: &str) -> String {
+        if !self.needs_rs_conversion() {
+            return expr.to_string();
+        }
+
+        match self {
+            TypeAnnotation::Array(element_type) => {
+                format!(
+                    "{expr}.into_iter().map({}).collect()",
+                    element_type.as_rs_mapper()
+                )
+            }
+            TypeAnnotation::Promise(resolve_type) => {
+                format!("{expr}.map({})", resolve_type.as_rs_mapper())
+            }
+            _ => format!("{expr}.into()"),
+        }
+    }
+
+    /// Generates the conversion function for `map` (eg. `Into::into`).
+    fn as_rs_mapper(&self) -> String {
+        match self {
+            TypeAnnotation::Object(..) | TypeAnnotation::Nullable(..) => "Into::into".to_string(),
+            _ => format!("|val| {}", self.as_rs_conversion("val")),
+        }
+    }
+
     /// Generates default value for Rust types.
     ///
     /// # Generated Code Examples
@@ -264,7 +331,7 @@ impl TypeAnnotation {
     /// String::default()             // String
     /// Vec::default()                // Array
     /// MyEnum::default()             // Enum
-    /// MyStruct::default()           // Object
+    /// MyStructBridge::default()     // Object
     /// NullableNumber::default()     // Nullable<Number>
     /// ```
     pub fn as_rs_default_val(&self) -> Result<String, anyhow::Error> {
@@ -277,7 +344,7 @@ impl TypeAnnotation {
                 format!("{name}::default()")
             }
             TypeAnnotation::Object(ObjectTypeAnnotation { name, .. }) => {
-                format!("{name}::default()")
+                format!("{}::default()", bridge_struct_name(name))
             }
             TypeAnnotation::Nullable(..) => {
                 let nullable_type = self.as_rs_type()?.into_code();
@@ -295,6 +362,82 @@ impl TypeAnnotation {
     }
 }
 
+/// Returns the Rust name of the bridge struct for the object type.
+///
+/// The object type name is used by the user-facing struct in `generated.rs`,
+/// and the bridge struct keeps it as the C++ name with `#[cxx_name]`.
+pub fn bridge_struct_name(name: &str) -> String {
+    format!("{name}Bridge")
+}
+
+/// Returns the Rust name of the opaque struct that owns the native object (eg. `DecoderObject`).
+pub fn native_object_struct_name(name: &str) -> String {
+    format!("{}Object", pascal_case(name))
+}
+
+/// Returns the name of the trait that the native object implements (eg. `DecoderSpec`).
+pub fn native_object_spec_name(name: &str) -> String {
+    format!("{}Spec", pascal_case(name))
+}
+
+/// Returns the Rust identifier (keywords are escaped as raw identifiers, eg. `r#type`).
+pub fn rs_ident(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Returns the identifier of the cxx bridge, which must be valid in both Rust and C++.
+///
+/// cxx does not support raw identifiers, so the keywords of both languages are escaped with the `_` suffix (eg. `type_`).
+pub fn bridge_ident(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) || CXX_KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Prepends the Rust doc comment of the spec to the item (if exists).
+///
+/// The `#[deprecated]` attribute is only allowed on the user-facing items (not in the cxx bridge).
+pub fn with_rs_doc(doc: &Option<Doc>, item: String, deprecated: bool) -> String {
+    match doc {
+        Some(doc) => format!("{}\n{item}", doc.as_rs_doc(deprecated)),
+        None => item,
+    }
+}
+
+impl Doc {
+    /// Converts the JSDoc comment to the Rust doc comment.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// /// Multiplies two numbers.
+    /// #[deprecated = "Use `mul` instead."]
+    /// ```
+    pub fn as_rs_doc(&self, deprecated: bool) -> String {
+        let mut lines = self
+            .lines
+            .iter()
+            .map(|line| format!("/// {line}").trim_end().to_string())
+            .collect::<Vec<_>>();
+
+        match &self.deprecated {
+            Some(message) if deprecated && message.is_empty() => {
+                lines.push("#[deprecated]".to_string());
+            }
+            Some(message) if deprecated => lines.push(format!("#[deprecated = {message:?}]")),
+            _ => {}
+        }
+
+        lines.join("\n")
+    }
+}
+
 impl Method {
     /// Converts Method to Rust trait method signature.
     ///
@@ -316,7 +459,7 @@ impl Method {
             .collect::<Vec<_>>()
             .join(", ");
 
-        let fn_name = snake_case(&self.name);
+        let fn_name = rs_ident(&snake_case(&self.name));
         let ret_annotation = if return_type == "()" {
             String::new()
         } else {
@@ -325,6 +468,160 @@ impl Method {
 
         Ok(format!("fn {fn_name}({params_sig}){ret_annotation}"))
     }
+
+    /// Generates the extern function signature and the implementation that calls the method of `it_`.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// #[cxx_name = "multiply"]
+    /// fn my_module_multiply(it_: &mut MyModule, a: f64, b: f64) -> Result<f64>;
+    ///
+    /// fn my_module_multiply(it_: &mut MyModule, a: f64, b: f64) -> Result<f64, anyhow::Error> {
+    ///     craby::catch_panic!({
+    ///         let ret = it_.multiply(a, b);
+    ///         ret
+    ///     })
+    /// }
+    /// ```
+    fn try_into_rs_bridge_func(
+        &self,
+        it_type: &str,
+        it_expr: &str,
+        fn_prefix: &str,
+        cxx_name: &str,
+    ) -> Result<(String, String), anyhow::Error> {
+        let ret_type = self.ret_type.as_rs_type()?.into_code();
+        let ret_type = match self.ret_type {
+            TypeAnnotation::Promise(_) => ret_type,
+            _ => format!("Result<{ret_type}, anyhow::Error>"),
+        };
+        let ret_extern_type = self.ret_type.as_rs_bridge_type()?.into_code();
+        let ret_extern_type = match self.ret_type {
+            TypeAnnotation::Promise(_) => ret_extern_type,
+            _ => format!("Result<{ret_extern_type}>"),
+        };
+
+        let params_sig = self
+            .params
+            .iter()
+            .map(|param| param.try_into_cxx_sig())
+            .collect::<Result<Vec<_>, _>>()
+            .map(|mut params| {
+                params.insert(0, format!("{RESERVED_ARG_NAME_MODULE}: &mut {it_type}"));
+                params.join(", ")
+            })?;
+
+        let fn_name = rs_ident(&snake_case(&self.name));
+        let fn_args = self
+            .params
+            .iter()
+            .map(|param| {
+                param
+                    .type_annotation
+                    .as_rs_conversion(&bridge_ident(&snake_case(&param.name)))
+            })
+            .collect::<Vec<_>>();
+
+        let prefixed_fn_name = format!("{fn_prefix}_{}", snake_case(&self.name));
+        let ret_extern_annotation = format!(" -> {ret_extern_type}");
+        let ret_annotation = format!(" -> {ret_type}");
+        let extern_func = formatdoc! {
+            r#"
+            #[cxx_name = "{cxx_name}"]
+            fn {prefixed_fn_name}({params_sig}){ret_extern_annotation};"#,
+        };
+
+        let ret = self.ret_type.as_rs_conversion("ret");
+
+        let fn_args = fn_args.join(", ");
+        let impl_func = match self.ret_type {
+            TypeAnnotation::Promise(_) => formatdoc! {
+                r#"
+                fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
+                    craby::catch_panic!({{
+                        let ret = {it_expr}.{fn_name}({fn_args});
+                        {ret}
+                    }}).and_then(|r| r)
+                }}"#,
+            },
+            _ => formatdoc! {
+                r#"
+                fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
+                    craby::catch_panic!({{
+                        let ret = {it_expr}.{fn_name}({fn_args});
+                        {ret}
+                    }})
+                }}"#,
+            },
+        };
+
+        Ok((extern_func, impl_func))
+    }
+}
+
+impl NativeObject {
+    /// Generates the extern function signatures and the implementations of the native object methods.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// #[cxx_name = "decoderDecode"]
+    /// fn decoder_decode(it_: &mut DecoderObject, data: Vec<u8>) -> Result<String>;
+    ///
+    /// fn decoder_decode(it_: &mut DecoderObject, data: Vec<u8>) -> Result<String, anyhow::Error> {
+    ///     craby::catch_panic!({
+    ///         let ret = it_.0.decode(data);
+    ///         ret
+    ///     })
+    /// }
+    /// ```
+    pub fn try_into_rs_bridge_funcs(&self) -> Result<Vec<(String, String)>, anyhow::Error> {
+        let struct_name = native_object_struct_name(&self.name);
+        let fn_prefix = snake_case(&self.name);
+
+        self.methods
+            .iter()
+            .map(|method| {
+                let cxx_name = format!("{}{}", camel_case(&self.name), pascal_case(&method.name));
+                method.try_into_rs_bridge_func(
+                    &struct_name,
+                    &format!("{RESERVED_ARG_NAME_MODULE}.0"),
+                    &fn_prefix,
+                    &cxx_name,
+                )
+            })
+            .collect()
+    }
+
+    /// Generates the trait that the native object implements.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// pub trait DecoderSpec: Send {
+    ///     fn decode(&mut self, data: ArrayBuffer) -> String;
+    /// }
+    /// ```
+    pub fn try_into_rs_spec(&self) -> Result<String, anyhow::Error> {
+        let trait_name = native_object_spec_name(&self.name);
+        let methods = self
+            .methods
+            .iter()
+            .map(|method| {
+                let sig = format!("{};", method.try_into_impl_sig()?);
+                Ok(with_rs_doc(&method.doc, sig, true))
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+        let method_defs = indent_str(&methods.join("\n"), 4);
+        Ok(formatdoc! {
+            r#"
+            pub trait {trait_name}: Send {{
+            {method_defs}
+            }}"#,
+        })
+    }
 }
 
 impl Param {
@@ -343,7 +640,11 @@ impl Param {
         } else {
             self.type_annotation.as_rs_type()?.into_code()
         };
-        Ok(format!("{}: {}", snake_case(&self.name), param_type))
+        Ok(format!(
+            "{}: {}",
+            bridge_ident(&snake_case(&self.name)),
+            param_type
+        ))
     }
 
     /// Converts parameter to implementation function signature.
@@ -361,7 +662,11 @@ impl Param {
         } else {
             self.type_annotation.as_rs_impl_type()?.into_code()
         };
-        Ok(format!("{}: {}", snake_case(&self.name), param_type))
+        Ok(format!(
+            "{}: {}",
+            rs_ident(&snake_case(&self.name)),
+            param_type
+        ))
     }
 }
 
@@ -382,7 +687,8 @@ impl Schema {
     ///
     /// // Implementation:
     /// fn create_my_module(id: usize, data_path: &str) -> Box<MyModule> {
-    ///     Box::new(MyModule::new(id, data_path))
+    ///     let ctx = Context::new(id, data_path).with_invoker(Invoker::new(id, invoke_js_task));
+    ///     Box::new(MyModule::new(ctx))
     /// }
     ///
     /// fn my_module_multiply(it_: &mut MyModule, a: f64, b: f64) -> Result<f64> {
@@ -398,7 +704,6 @@ impl Schema {
 
         let mut func_extern_sigs = Vec::with_capacity(self.methods.len() + 1);
         let mut func_impls = Vec::with_capacity(self.methods.len() + 1);
-        let mut type_impls = vec![];
         let mut struct_defs = FxHashMap::default();
 
         func_extern_sigs.push(formatdoc! {
@@ -410,148 +715,112 @@ impl Schema {
         func_impls.push(formatdoc! {
             r#"
             fn create_{snake_module_name}(id: usize, data_path: &str) -> Box<{module_name}> {{
-                let ctx = Context::new(id, data_path);
+                let ctx = Context::new(id, data_path).with_invoker(Invoker::new(id, invoke_js_task));
                 Box::new({module_name}::new(ctx))
             }}"#,
         });
 
+        // Evaluate the module constants (before the module is created)
+        if let Some(constants_type) = self.constants_type() {
+            let constants_name = constants_type.as_object().unwrap().name.clone();
+            let bridge_type = constants_type.as_rs_type()?.into_code();
+
+            func_extern_sigs.push(formatdoc! {
+                r#"
+                #[cxx_name = "get{constants_name}"]
+                fn get_{snake_module_name}_constants(id: usize, data_path: &str) -> Result<{bridge_type}>;"#,
+            });
+
+            func_impls.push(formatdoc! {
+                r#"
+                fn get_{snake_module_name}_constants(id: usize, data_path: &str) -> Result<{bridge_type}, anyhow::Error> {{
+                    let ctx = Context::detached(id, data_path).with_invoker(Invoker::new(id, invoke_js_task));
+                    craby::catch_panic!({{
+                        let ret = {module_name}::constants(&ctx);
+                        ret.into()
+                    }})
+                }}"#,
+            });
+        }
+
         // Collect extern function signatures and implementations
         for method_spec in &self.methods {
-            // Collect nullable parameters
-            for param in &method_spec.params {
-                if param.type_annotation.is_nullable() {
-                    let id = param.type_annotation.to_id();
-                    if let HashMapEntry::Vacant(e) = struct_defs.entry(id) {
-                        let nullable = RsNullableStruct::try_from(&param.type_annotation)?;
-                        e.insert(nullable.definition);
-                        type_impls.push(nullable.implementation);
-                    }
-                }
-            }
+            // Collect nullable types of the parameters and the return type
+            let nullable_types = method_spec
+                .params
+                .iter()
+                .flat_map(|param| param.type_annotation.nullable_types())
+                .chain(method_spec.ret_type.nullable_types());
 
-            // Collect nullable return type
-            if method_spec.ret_type.is_nullable() {
-                let id = method_spec.ret_type.to_id();
-                if let HashMapEntry::Vacant(e) = struct_defs.entry(id) {
-                    let nullable = RsNullableStruct::try_from(&method_spec.ret_type)?;
-                    e.insert(nullable.definition);
-                    type_impls.push(nullable.implementation);
+            for nullable_type in nullable_types {
+                if let HashMapEntry::Vacant(e) = struct_defs.entry(nullable_type.to_id()) {
+                    e.insert(RsNullableStruct::try_from(nullable_type)?.definition);
                 }
             }
 
-            let ret_type = method_spec.ret_type.as_rs_type()?.into_code();
-            let ret_type = match method_spec.ret_type {
-                TypeAnnotation::Promise(_) => ret_type,
-                _ => format!("Result<{ret_type}, anyhow::Error>"),
-            };
-            let ret_extern_type = method_spec.ret_type.as_rs_bridge_type()?.into_code();
-            let ret_extern_type = match method_spec.ret_type {
-                TypeAnnotation::Promise(_) => ret_extern_type,
-                _ => format!("Result<{ret_extern_type}>"),
-            };
+            let (extern_func, impl_func) = method_spec.try_into_rs_bridge_func(
+                &module_name,
+                RESERVED_ARG_NAME_MODULE,
+                &snake_module_name,
+                &cxx_ident(&camel_case(&method_spec.name)),
+            )?;
 
-            let params_sig = method_spec
-                .params
-                .iter()
-                .map(|param| param.try_into_cxx_sig())
-                .collect::<Result<Vec<_>, _>>()
-                .map(|mut params| {
-                    params.insert(
-                        0,
-                        format!(
-                            "{RESERVED_ARG_NAME_MODULE}: &mut {}",
-                            pascal_case(&self.module_name)
-                        ),
-                    );
-                    params.join(", ")
-                })?;
-
-            let mod_name = snake_case(&self.module_name);
-            let fn_name = snake_case(&method_spec.name);
-            let fn_args = method_spec
-                .params
-                .iter()
-                .map(|param| {
-                    let name = snake_case(&param.name);
-                    if let TypeAnnotation::Nullable(..) = &param.type_annotation {
-                        format!("{name}.into()")
-                    } else {
-                        name
-                    }
-                })
-                .collect::<Vec<_>>();
+            func_extern_sigs.push(extern_func);
+            func_impls.push(impl_func);
+        }
 
-            let cxx_extern_fn_name = camel_case(&method_spec.name);
-            let prefixed_fn_name = format!("{mod_name}_{fn_name}");
-            let ret_extern_annotation = format!(" -> {ret_extern_type}");
-            let ret_annotation = format!(" -> {ret_type}");
-            let extern_func = formatdoc! {
-                r#"
-                #[cxx_name = "{cxx_extern_fn_name}"]
-                fn {prefixed_fn_name}({params_sig}){ret_extern_annotation};"#,
-            };
+        // Collect native object types and their methods
+        let mut impl_types = vec![format!("type {module_name};")];
+        for object in &self.objects {
+            impl_types.push(format!("type {};", native_object_struct_name(&object.name)));
 
-            let ret = if let TypeAnnotation::Nullable(..) = &method_spec.ret_type {
-                "ret.into()"
-            } else {
-                "ret"
-            };
+            let nullable_types = object.methods.iter().flat_map(|method| {
+                method
+                    .params
+                    .iter()
+                    .flat_map(|param| param.type_annotation.nullable_types())
+                    .chain(method.ret_type.nullable_types())
+            });
 
-            let fn_args = fn_args.join(", ");
-            let impl_func = match method_spec.ret_type {
-                TypeAnnotation::Promise(_) => formatdoc! {
-                    r#"
-                    fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
-                        craby::catch_panic!({{
-                            let ret = {it}.{fn_name}({fn_args});
-                            {ret}
-                        }}).and_then(|r| r)
-                    }}"#,
-                    it = RESERVED_ARG_NAME_MODULE,
-                },
-                _ => formatdoc! {
-                    r#"
-                    fn {prefixed_fn_name}({params_sig}){ret_annotation} {{
-                        craby::catch_panic!({{
-                            let ret = {it}.{fn_name}({fn_args});
-                            {ret}
-                        }})
-                    }}"#,
-                    it = RESERVED_ARG_NAME_MODULE,
-                },
-            };
+            for nullable_type in nullable_types {
+                if let HashMapEntry::Vacant(e) = struct_defs.entry(nullable_type.to_id()) {
+                    e.insert(RsNullableStruct::try_from(nullable_type)?.definition);
+                }
+            }
 
-            func_extern_sigs.push(extern_func);
-            func_impls.push(impl_func);
+            for (extern_func, impl_func) in object.try_into_rs_bridge_funcs()? {
+                func_extern_sigs.push(extern_func);
+                func_impls.push(impl_func);
+            }
         }
 
-        // Collect alias types (struct)
-        for type_annotation in &self.aliases {
+        // Collect alias types (struct) and the module constants
+        let constants_type = self.constants_type();
+        for type_annotation in self.aliases.iter().chain(&constants_type) {
             if let HashMapEntry::Vacant(e) = struct_defs.entry(type_annotation.to_id()) {
-                let id = type_annotation.to_id();
                 let obj = type_annotation.as_object().unwrap();
                 e.insert(RsStruct::try_from(obj)?.into_code());
 
                 for prop in &obj.props {
-                    if prop.type_annotation.is_nullable() {
-                        let id = prop.type_annotation.to_id();
-                        if let HashMapEntry::Vacant(e) = struct_defs.entry(id) {
-                            let nullable = RsNullableStruct::try_from(&prop.type_annotation)?;
-                            e.insert(nullable.definition);
+                    for nullable_type in prop.type_annotation.nullable_types() {
+                        if let HashMapEntry::Vacant(e) = struct_defs.entry(nullable_type.to_id()) {
+                            e.insert(RsNullableStruct::try_from(nullable_type)?.definition);
                         }
                     }
                 }
+            }
+        }
 
-                // Collect default implementations for the alias type
-                let mut type_impls_map = BTreeMap::new();
-                collect_alias_default_impls(id, obj, &mut type_impls_map)?;
+        // Collect nullable types of the signal payloads
+        let nullable_types = self
+            .signals
+            .iter()
+            .filter_map(|signal| signal.payload_type.as_ref())
+            .flat_map(|payload_type| payload_type.nullable_types());
 
-                type_impls.push(
-                    type_impls_map
-                        .into_values()
-                        .collect::<Vec<_>>()
-                        .join("\n\n"),
-                );
+        for nullable_type in nullable_types {
+            if let HashMapEntry::Vacant(e) = struct_defs.entry(nullable_type.to_id()) {
+                e.insert(RsNullableStruct::try_from(nullable_type)?.definition);
             }
         }
 
@@ -564,30 +833,34 @@ impl Schema {
                 let members = enum_schema
                     .members
                     .iter()
-                    .map(|m| format!("{},", m.name))
+                    .map(|m| with_rs_doc(&m.doc, format!("{},", m.name), false))
                     .collect::<Vec<_>>();
 
                 let members = indent_str(&members.join("\n"), 4);
-                formatdoc! {
+                let enum_def = formatdoc! {
                     r#"
                     enum {name} {{
                     {members}
                     }}"#,
                     name = enum_schema.name,
-                }
+                };
+
+                (type_annotation.to_id(), enum_def)
             })
             .collect();
 
         Ok(RsCxxBridge {
-            impl_type: format!("type {module_name};"),
-            struct_defs: struct_defs.into_values().collect(),
+            impl_type: impl_types.join("\n"),
+            struct_defs: struct_defs.into_iter().collect(),
             enum_defs,
             func_extern_sigs,
             func_impls,
         })
     }
 
-    /// Collects and generates all type implementations (Default, From traits).
+    /// Collects the implementations of the bridge types (`Default`, `From` traits).
+    ///
+    /// The bridge types are converted from/into the user-facing types, so the bridge types never appear in the `*Spec` traits.
     ///
     /// # Generated Code
     ///
@@ -601,51 +874,117 @@ impl Schema {
     ///     }
     /// }
     ///
-    /// impl From<NullableNumber> for Nullable<Number> {
+    /// impl From<NullableNumber> for Option<Number> {
     ///     fn from(val: NullableNumber) -> Self {
-    ///         Nullable::new(if val.null { None } else { Some(val.val) })
+    ///         if val.null { None } else { Some(val.val) }
+    ///     }
+    /// }
+    ///
+    /// impl From<MyStructBridge> for MyStruct {
+    ///     fn from(val: MyStructBridge) -> Self {
+    ///         MyStruct {
+    ///             foo: val.foo,
+    ///             bar: val.bar.into(),
+    ///         }
     ///     }
     /// }
     /// ```
-    pub fn try_collect_type_impls(
+    pub fn try_collect_bridge_impls(
         &self,
         type_impls: &mut BTreeMap<u64, String>,
     ) -> Result<(), anyhow::Error> {
-        // Collect extern function signatures and implementations
-        for method_spec in &self.methods {
-            for param in &method_spec.params {
-                // Collect nullable parameters
-                if param.type_annotation.is_nullable() {
-                    let id = param.type_annotation.to_id();
-                    if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
-                        let nullable = RsNullableStruct::try_from(&param.type_annotation)?;
-                        e.insert(nullable.implementation);
-                    }
-                }
+        // Collect nullable types of the parameters, the return types and the signal payloads
+        let nullable_types = self
+            .methods
+            .iter()
+            .flat_map(|method_spec| {
+                method_spec
+                    .params
+                    .iter()
+                    .flat_map(|param| param.type_annotation.nullable_types())
+                    .chain(method_spec.ret_type.nullable_types())
+            })
+            .chain(
+                self.objects
+                    .iter()
+                    .flat_map(|object| object.methods.iter())
+                    .flat_map(|method_spec| {
+                        method_spec
+                            .params
+                            .iter()
+                            .flat_map(|param| param.type_annotation.nullable_types())
+                            .chain(method_spec.ret_type.nullable_types())
+                    }),
+            )
+            .chain(
+                self.signals
+                    .iter()
+                    .filter_map(|signal| signal.payload_type.as_ref())
+                    .flat_map(|payload_type| payload_type.nullable_types()),
+            );
+
+        for nullable_type in nullable_types {
+            if let BTreeMapEntry::Vacant(e) = type_impls.entry(nullable_type.to_id()) {
+                e.insert(RsNullableStruct::try_from(nullable_type)?.implementation);
             }
+        }
 
-            // Collect nullable return type
-            if method_spec.ret_type.is_nullable() {
-                let id = method_spec.ret_type.to_id();
-                if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
-                    let nullable = RsNullableStruct::try_from(&method_spec.ret_type)?;
-                    e.insert(nullable.implementation);
-                }
+        for object in &self.objects {
+            let id = TypeAnnotation::NativeObject(NativeObjectTypeAnnotation {
+                name: object.name.clone(),
+            })
+            .to_id();
+
+            if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
+                e.insert(RsNativeObject::try_from(object)?.into_code());
             }
         }
 
-        // impl Default trait for the alias type
-        for type_annotation in &self.aliases {
+        let constants_type = self.constants_type();
+        for type_annotation in self.aliases.iter().chain(&constants_type) {
             let id = type_annotation.to_id();
             if !type_impls.contains_key(&id) {
                 let obj = type_annotation.as_object().unwrap();
-                collect_alias_default_impls(id, obj, type_impls)?;
+                collect_alias_bridge_impls(id, obj, type_impls)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects the user-facing types and their implementations.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// #[derive(Clone, Default)]
+    /// pub struct MyStruct {
+    ///     pub foo: String,
+    ///     pub bar: Option<Number>,
+    /// }
+    ///
+    /// impl Default for MyEnum {
+    ///     fn default() -> Self {
+    ///         MyEnum::Foo
+    ///     }
+    /// }
+    /// ```
+    pub fn try_collect_impl_types(
+        &self,
+        impl_types: &mut BTreeMap<u64, String>,
+    ) -> Result<(), anyhow::Error> {
+        let constants_type = self.constants_type();
+        for type_annotation in self.aliases.iter().chain(&constants_type) {
+            let id = type_annotation.to_id();
+            if let BTreeMapEntry::Vacant(e) = impl_types.entry(id) {
+                let obj = type_annotation.as_object().unwrap();
+                e.insert(RsImplStruct::try_from(obj)?.into_code());
             }
         }
 
         for type_annotation in &self.enums {
             let id = type_annotation.to_id();
-            if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
+            if let BTreeMapEntry::Vacant(e) = impl_types.entry(id) {
                 let enum_type_annotation = type_annotation.as_enum().unwrap();
                 e.insert(RsDefaultImpl::try_from(enum_type_annotation)?.into_code());
             }
@@ -663,7 +1002,11 @@ pub mod template {
 
     use crate::{
         common::IntoCode,
-        parser::types::{EnumTypeAnnotation, ObjectTypeAnnotation, TypeAnnotation},
+        parser::types::{EnumTypeAnnotation, NativeObject, ObjectTypeAnnotation, TypeAnnotation},
+        platform::rust::{
+            bridge_ident, bridge_struct_name, native_object_spec_name, native_object_struct_name,
+            rs_ident, with_rs_doc,
+        },
         utils::indent_str,
     };
 
@@ -672,7 +1015,9 @@ pub mod template {
     /// # Generated Code
     ///
     /// ```rust,ignore
-    /// struct MyStruct {
+    /// #[cxx_name = "MyStruct"]
+    /// #[derive(Clone)]
+    /// struct MyStructBridge {
     ///     foo: String,
     ///     bar: f64,
     ///     baz: bool,
@@ -699,27 +1044,158 @@ pub mod template {
                 // bar: f64,
                 // baz: bool,
                 // ```
-                props.push(format!(
+                let prop_def = format!(
                     "{}: {},",
-                    snake_case(&prop.name),
+                    bridge_ident(&snake_case(&prop.name)),
                     prop.type_annotation.as_rs_bridge_type()?.into_code()
-                ));
+                );
+                props.push(with_rs_doc(&prop.doc, prop_def, false));
             }
 
             let props = indent_str(&props.join("\n"), 4);
             let struct_def = formatdoc! {
                 r#"
+                #[cxx_name = "{cxx_name}"]
                 #[derive(Clone)]
                 struct {name} {{
                 {props}
                 }}"#,
-                name = obj.name,
+                cxx_name = obj.name,
+                name = bridge_struct_name(&obj.name),
             };
 
             Ok(RsStruct(struct_def))
         }
     }
 
+    /// User-facing Rust struct definition for the object type.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// #[derive(Clone, Default)]
+    /// pub struct MyStruct {
+    ///     pub foo: String,
+    ///     pub bar: Number,
+    ///     pub baz: Option<Boolean>,
+    /// }
+    /// ```
+    pub struct RsImplStruct(pub String);
+
+    impl IntoCode for RsImplStruct {
+        fn into_code(self) -> String {
+            self.0
+        }
+    }
+
+    impl TryFrom<&ObjectTypeAnnotation> for RsImplStruct {
+        type Error = anyhow::Error;
+
+        fn try_from(obj: &ObjectTypeAnnotation) -> Result<Self, Self::Error> {
+            let mut props = Vec::with_capacity(obj.props.len());
+
+            for prop in &obj.props {
+                let prop_def = format!(
+                    "pub {}: {},",
+                    rs_ident(&snake_case(&prop.name)),
+                    prop.type_annotation.as_rs_impl_type()?.into_code()
+                );
+                props.push(with_rs_doc(&prop.doc, prop_def, true));
+            }
+
+            let props = indent_str(&props.join("\n"), 4);
+            let struct_def = formatdoc! {
+                r#"
+                #[derive(Clone, Default)]
+                pub struct {name} {{
+                {props}
+                }}"#,
+                name = obj.name,
+            };
+
+            Ok(RsImplStruct(struct_def))
+        }
+    }
+
+    /// Conversions between the bridge struct and the user-facing struct.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// impl From<MyStructBridge> for MyStruct {
+    ///     fn from(val: MyStructBridge) -> Self {
+    ///         MyStruct {
+    ///             foo: val.foo,
+    ///             baz: val.baz.into(),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl From<MyStruct> for MyStructBridge {
+    ///     fn from(val: MyStruct) -> Self {
+    ///         MyStructBridge {
+    ///             foo: val.foo,
+    ///             baz: val.baz.into(),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub struct RsFromImpl(pub String);
+
+    impl IntoCode for RsFromImpl {
+        fn into_code(self) -> String {
+            self.0
+        }
+    }
+
+    impl From<&ObjectTypeAnnotation> for RsFromImpl {
+        fn from(obj: &ObjectTypeAnnotation) -> Self {
+            let (impl_props, bridge_props): (Vec<_>, Vec<_>) = obj
+                .props
+                .iter()
+                .map(|prop| {
+                    // Keywords are escaped differently in the bridge struct (eg. `type_` and `r#type`)
+                    let name = rs_ident(&snake_case(&prop.name));
+                    let bridge_name = bridge_ident(&snake_case(&prop.name));
+                    let conversion = |from: &str| {
+                        prop.type_annotation
+                            .as_rs_conversion(&format!("val.{from}"))
+                    };
+
+                    (
+                        format!("{name}: {},", conversion(&bridge_name)),
+                        format!("{bridge_name}: {},", conversion(&name)),
+                    )
+                })
+                .unzip();
+
+            let impl_props = indent_str(&impl_props.join("\n"), 12);
+            let bridge_props = indent_str(&bridge_props.join("\n"), 12);
+            let name = &obj.name;
+            let bridge_name = bridge_struct_name(name);
+            let from_impl = formatdoc! {
+                r#"
+                impl From<{bridge_name}> for {name} {{
+                    fn from(val: {bridge_name}) -> Self {{
+                        {name} {{
+                {impl_props}
+                        }}
+                    }}
+                }}
+
+                impl From<{name}> for {bridge_name} {{
+                    fn from(val: {name}) -> Self {{
+                        {bridge_name} {{
+                {bridge_props}
+                        }}
+                    }}
+                }}"#,
+            };
+
+            RsFromImpl(from_impl)
+        }
+    }
+
     /// Rust struct definition for nullable types.
     pub struct RsNullableStruct {
         pub definition: String,
@@ -733,8 +1209,13 @@ pub mod template {
             if let TypeAnnotation::Nullable(type_annotation) = nullable_type {
                 let struct_type = nullable_type.as_rs_bridge_type()?.into_code();
                 let base_type = type_annotation.as_rs_type()?.into_code();
-                let rs_impl_type = type_annotation.as_rs_impl_type()?.into_code();
+                let rs_impl_type = nullable_type.as_rs_impl_type()?.into_code();
                 let default_val = type_annotation.as_rs_default_val()?;
+                let from_val = type_annotation.as_rs_conversion("val.val");
+                let into_val = match type_annotation.as_rs_conversion("val").as_str() {
+                    "val" => "val".to_string(),
+                    val => format!("val: {val}"),
+                };
 
                 let struct_def = formatdoc! {
                     r#"
@@ -756,19 +1237,20 @@ pub mod template {
                         }}
                     }}
 
-                    impl From<{struct_type}> for Nullable<{rs_impl_type}> {{
+                    impl From<{struct_type}> for {rs_impl_type} {{
                         fn from(val: {struct_type}) -> Self {{
-                            Nullable::new(if val.null {{ None }} else {{ Some(val.val) }})
+                            if val.null {{ None }} else {{ Some({from_val}) }}
                         }}
                     }}
-    
-                    impl From<Nullable<{rs_impl_type}>> for {struct_type} {{
-                        fn from(val: Nullable<{rs_impl_type}>) -> Self {{
-                            let val = val.into_value();
-                            let null = val.is_none();
-                            {struct_type} {{
-                                val: val.unwrap_or({default_val}),
-                                null,
+
+                    impl From<{rs_impl_type}> for {struct_type} {{
+                        fn from(val: {rs_impl_type}) -> Self {{
+                            match val {{
+                                Some(val) => {struct_type} {{
+                                    null: false,
+                                    {into_val},
+                                }},
+                                None => {struct_type}::default(),
                             }}
                         }}
                     }}"#,
@@ -790,9 +1272,9 @@ pub mod template {
     ///
     /// ```rust,ignore
     /// // Struct
-    /// impl Default for MyStruct {
+    /// impl Default for MyStructBridge {
     ///     fn default() -> Self {
-    ///         MyStruct {
+    ///         MyStructBridge {
     ///             foo: String::default(),
     ///             bar: 0.0,
     ///             baz: false,
@@ -824,7 +1306,7 @@ pub mod template {
             for prop in &obj.props {
                 props_with_default_val.push(format!(
                     "{}: {}",
-                    snake_case(&prop.name),
+                    bridge_ident(&snake_case(&prop.name)),
                     prop.type_annotation.as_rs_default_val()?
                 ));
             }
@@ -839,7 +1321,7 @@ pub mod template {
                         }}
                     }}
                 }}"#,
-                name = obj.name,
+                name = bridge_struct_name(&obj.name),
             };
 
             Ok(RsDefaultImpl(default_impl))
@@ -870,22 +1352,140 @@ pub mod template {
         }
     }
 
-    pub fn collect_alias_default_impls(
+    /// Opaque struct that owns the native object, and its drop test.
+    ///
+    /// The struct is boxed and passed to C++ (`rust::Box`), then dropped when the host object is collected by the JS GC.
+    ///
+    /// # Generated Code
+    ///
+    /// ```rust,ignore
+    /// pub struct DecoderObject(NativeObject<dyn DecoderSpec>);
+    ///
+    /// impl From<NativeObject<dyn DecoderSpec>> for Box<DecoderObject> {
+    ///     fn from(val: NativeObject<dyn DecoderSpec>) -> Self {
+    ///         Box::new(DecoderObject(val))
+    ///     }
+    /// }
+    ///
+    /// #[cfg(test)]
+    /// mod decoder_object_tests {
+    ///     // ...
+    /// }
+    /// ```
+    pub struct RsNativeObject(String);
+
+    impl IntoCode for RsNativeObject {
+        fn into_code(self) -> String {
+            self.0
+        }
+    }
+
+    impl TryFrom<&NativeObject> for RsNativeObject {
+        type Error = anyhow::Error;
+
+        fn try_from(object: &NativeObject) -> Result<Self, Self::Error> {
+            let struct_name = native_object_struct_name(&object.name);
+            let spec_name = native_object_spec_name(&object.name);
+            let snake_name = snake_case(&struct_name);
+            let probe_methods = object
+                .methods
+                .iter()
+                .map(|method| {
+                    let params = method
+                        .params
+                        .iter()
+                        // `_r#type` is not a valid identifier
+                        .map(|param| {
+                            let sig = param.try_into_impl_sig()?;
+                            Ok(format!("_{}", sig.trim_start_matches("r#")))
+                        })
+                        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+                    let params_sig = std::iter::once("&mut self".to_string())
+                        .chain(params)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let ret_annotation = match method.ret_type {
+                        TypeAnnotation::Void => String::new(),
+                        _ => format!(" -> {}", method.ret_type.as_rs_impl_type()?.into_code()),
+                    };
+
+                    Ok(formatdoc! {
+                        r#"
+                        fn {fn_name}({params_sig}){ret_annotation} {{
+                            unreachable!()
+                        }}"#,
+                        fn_name = rs_ident(&snake_case(&method.name)),
+                    })
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+            let probe_methods = indent_str(&probe_methods.join("\n\n"), 8);
+            let code = formatdoc! {
+                r#"
+                pub struct {struct_name}(NativeObject<dyn {spec_name}>);
+
+                impl From<NativeObject<dyn {spec_name}>> for Box<{struct_name}> {{
+                    fn from(val: NativeObject<dyn {spec_name}>) -> Self {{
+                        Box::new({struct_name}(val))
+                    }}
+                }}
+
+                #[cfg(test)]
+                mod {snake_name}_tests {{
+                    use std::sync::atomic::{{AtomicBool, Ordering}};
+                    use std::sync::Arc;
+
+                    use super::*;
+
+                    struct Probe(Arc<AtomicBool>);
+
+                    impl Drop for Probe {{
+                        fn drop(&mut self) {{
+                            self.0.store(true, Ordering::SeqCst);
+                        }}
+                    }}
+
+                    impl {spec_name} for Probe {{
+                {probe_methods}
+                    }}
+
+                    #[test]
+                    fn drops_native_object_with_owner() {{
+                        let dropped = Arc::new(AtomicBool::new(false));
+                        let object: NativeObject<dyn {spec_name}> = Box::new(Probe(dropped.clone()));
+                        let owner: Box<{struct_name}> = object.into();
+
+                        assert!(!dropped.load(Ordering::SeqCst));
+                        drop(owner);
+                        assert!(dropped.load(Ordering::SeqCst));
+                    }}
+                }}"#,
+            };
+
+            Ok(RsNativeObject(code))
+        }
+    }
+
+    /// Collects the `Default` and `From` implementations of the bridge struct and its nullable fields.
+    pub fn collect_alias_bridge_impls(
         id: u64,
         obj: &ObjectTypeAnnotation,
         type_impls: &mut BTreeMap<u64, String>,
     ) -> Result<(), anyhow::Error> {
         for prop in &obj.props {
-            if prop.type_annotation.is_nullable() {
-                let id = prop.type_annotation.to_id();
-                if let BTreeMapEntry::Vacant(e) = type_impls.entry(id) {
-                    let nullable = RsNullableStruct::try_from(&prop.type_annotation)?;
+            for nullable_type in prop.type_annotation.nullable_types() {
+                if let BTreeMapEntry::Vacant(e) = type_impls.entry(nullable_type.to_id()) {
+                    let nullable = RsNullableStruct::try_from(nullable_type)?;
                     e.insert(nullable.implementation);
                 }
             }
         }
 
-        type_impls.insert(id, RsDefaultImpl::try_from(obj)?.into_code());
+        let impls = [
+            RsDefaultImpl::try_from(obj)?.into_code(),
+            RsFromImpl::from(obj).into_code(),
+        ];
+        type_impls.insert(id, impls.join("\n\n"));
         Ok(())
     }
 }
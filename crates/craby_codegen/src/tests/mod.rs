@@ -1,14 +1,18 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
-use crate::{parser::native_spec_parser::try_parse_schema, types::CodegenContext};
+use crate::{
+    parser::native_spec_parser::try_parse_schema,
+    types::{CodegenContext, MethodAttrs},
+};
 
 pub fn get_codegen_context() -> CodegenContext {
     let schemas = try_parse_schema(
         "
-        import type { NativeModule, Signal } from 'craby-modules';
+        import type { NativeModule, NativeObject, Signal } from 'craby-modules';
         import { NativeModuleRegistry } from 'craby-modules';
 
         export interface TestObject {
+            /** Foo value */
             foo: string;
             bar: number;
             baz: boolean;
@@ -16,6 +20,8 @@ pub fn get_codegen_context() -> CodegenContext {
             camelCase: number;
             PascalCase: number;
             snake_case: number;
+            type: string;
+            delete: number;
         }
 
         export type SubObject = {
@@ -34,22 +40,41 @@ pub fn get_codegen_context() -> CodegenContext {
 
         export enum SwitchState {
             Off = 0,
+            /** Switch is turned on */
             On = 1,
         }
 
+        export interface Decoder extends NativeObject {
+            /** Decodes the data into a string. */
+            decode(data: ArrayBuffer): string;
+            reset(): void;
+        }
+
         export interface Spec extends NativeModule {
+            /** Version of the module */
+            readonly VERSION: string;
+            readonly DEFAULT_STATE: SwitchState;
+            /**
+             * Returns the numeric value.
+             *
+             * @deprecated Use `camelMethod` instead.
+             */
             numericMethod(arg: number): number;
             booleanMethod(arg: boolean): boolean;
             stringMethod(arg: string): string;
             objectMethod(arg: TestObject): TestObject;
             arrayBufferMethod(arg: ArrayBuffer): ArrayBuffer;
             arrayMethod(arg: number[]): number[];
+            inlineMethod(arg: { v: number }[]): { w: string } | null;
             enumMethod(arg0: MyEnum, arg1: SwitchState): string;
             nullableMethod(arg: number | null): MaybeNumber;
             promiseMethod(arg: number): Promise<number>;
             camelMethod(firstArg: number, secondArg: number): number;
             PascalMethod(FirstArg: number, SecondArg: number): number;
             snakeMethod(first_arg: number, second_arg: number): number;
+            createDecoder(encoding: string): Decoder;
+            delete(type: string, match: number): void;
+            /** Emitted when something happens. */
             onSignal: Signal;
         }
 
@@ -63,5 +88,26 @@ pub fn get_codegen_context() -> CodegenContext {
         root: PathBuf::from("."),
         schemas,
         android_package_name: "rs.craby.testmodule".to_string(),
+        method_attrs: BTreeMap::from([(
+            "CrabyTest".to_string(),
+            BTreeMap::from([
+                (
+                    "promise_method".to_string(),
+                    MethodAttrs {
+                        blocking: true,
+                        timeout: Some(1000),
+                        trace: true,
+                    },
+                ),
+                (
+                    "numeric_method".to_string(),
+                    MethodAttrs {
+                        blocking: false,
+                        timeout: None,
+                        trace: true,
+                    },
+                ),
+            ]),
+        )]),
     }
 }
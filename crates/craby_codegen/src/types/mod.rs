@@ -1,9 +1,11 @@
-use std::{fmt::Display, hash::Hasher, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Display, hash::Hasher, path::PathBuf};
 
-use crate::parser::types::{Method, Signal, TypeAnnotation};
+use crate::parser::types::{
+    Doc, Method, NativeObject, ObjectTypeAnnotation, Prop, Signal, TypeAnnotation,
+};
 use craby_common::utils::string::{flat_case, pascal_case};
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use xxhash_rust::xxh3::Xxh3;
 
 pub struct CodegenContext {
@@ -11,9 +13,35 @@ pub struct CodegenContext {
     pub root: PathBuf,
     pub schemas: Vec<Schema>,
     pub android_package_name: String,
+    /// Method attributes of each module (key: module name)
+    pub method_attrs: BTreeMap<String, MethodAttrsMap>,
 }
 
-#[derive(Debug, Serialize)]
+impl CodegenContext {
+    /// Returns the method attributes of the module.
+    pub fn method_attrs_of(&self, module_name: &str) -> MethodAttrsMap {
+        self.method_attrs
+            .get(module_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Attributes of the method specified by `#[craby::method(...)]`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MethodAttrs {
+    /// Marks the method that blocks the thread (the spec must return a Promise).
+    pub blocking: bool,
+    /// Rejects the Promise if the method does not finish within the duration (milliseconds).
+    pub timeout: Option<u64>,
+    /// Logs the execution time of the method.
+    pub trace: bool,
+}
+
+/// Method attributes of the module (key: Rust method name)
+pub type MethodAttrsMap = BTreeMap<String, MethodAttrs>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Schema {
     pub module_name: String,
     // `TypeAnnotation::ObjectTypeAnnotation`
@@ -22,9 +50,55 @@ pub struct Schema {
     pub enums: Vec<TypeAnnotation>,
     pub methods: Vec<Method>,
     pub signals: Vec<Signal>,
+    // Native objects returned by the methods (host objects)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<NativeObject>,
+    // Constants of the module (`readonly` properties)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constants: Vec<Prop>,
 }
 
 impl Schema {
+    /// Returns the object type of the module constants (eg. `MyModuleConstants`).
+    pub fn constants_type(&self) -> Option<TypeAnnotation> {
+        if self.constants.is_empty() {
+            return None;
+        }
+
+        Some(TypeAnnotation::Object(ObjectTypeAnnotation {
+            name: format!("{}Constants", pascal_case(&self.module_name)),
+            props: self.constants.clone(),
+        }))
+    }
+
+    /// Returns `true` if any spec of the module is marked as `@deprecated`.
+    pub fn has_deprecated(&self) -> bool {
+        let is_deprecated =
+            |doc: &Option<Doc>| doc.as_ref().is_some_and(|d| d.deprecated.is_some());
+        let props = self
+            .aliases
+            .iter()
+            .filter_map(|alias| alias.as_object())
+            .flat_map(|obj| &obj.props)
+            .chain(&self.constants);
+        let members = self
+            .enums
+            .iter()
+            .filter_map(|enum_type| enum_type.as_enum())
+            .flat_map(|enum_type| &enum_type.members);
+        let methods = self
+            .methods
+            .iter()
+            .chain(self.objects.iter().flat_map(|obj| &obj.methods));
+
+        props
+            .map(|prop| &prop.doc)
+            .chain(members.map(|member| &member.doc))
+            .chain(methods.map(|method| &method.doc))
+            .chain(self.signals.iter().map(|signal| &signal.doc))
+            .any(is_deprecated)
+    }
+
     pub fn to_hash(schemas: &[Schema]) -> String {
         let serialized = serde_json::to_string(schemas).unwrap();
         debug!("Serialized schemas: {}", serialized);
@@ -72,6 +146,25 @@ impl Display for CxxModuleName {
     }
 }
 
+/// Represents the C++ host object class name of the native object. (eg. `CxxDecoderHostObject`)
+#[derive(Debug)]
+pub struct CxxHostObjectName(pub String);
+
+impl<T> From<T> for CxxHostObjectName
+where
+    T: AsRef<str>,
+{
+    fn from(value: T) -> Self {
+        CxxHostObjectName(format!("Cxx{}HostObject", pascal_case(value.as_ref())))
+    }
+}
+
+impl Display for CxxHostObjectName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 // Represents the Objective-C module provider name. (eg. `FastCalculatorModuleProvider`)
 #[derive(Debug)]
 pub struct ObjCProviderName(pub String);
@@ -1,4 +1,9 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, ErrorKind, Write},
+    process::{Command, Output, Stdio},
+    thread,
+};
 
 use crate::{
     common::IntoCode,
@@ -126,6 +131,39 @@ pub fn calc_deps_order(schema: &Schema) -> Result<Vec<String>, anyhow::Error> {
     Ok(result)
 }
 
+/// Runs the command with the input on stdin and collects its output.
+pub fn run_with_input(command: &mut Command, input: String) -> io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    // Written on another thread to not block on the output of the command
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output()?;
+
+    match writer.join().unwrap() {
+        // Commands may exit without reading the input
+        Err(e) if e.kind() != ErrorKind::BrokenPipe && output.status.success() => Err(e),
+        _ => Ok(output),
+    }
+}
+
+/// Returns the TypeScript spec source of the module with the declarations (used by the tests).
+#[doc(hidden)]
+pub fn spec_source(module_name: &str, body: &str) -> String {
+    format!(
+        "import type {{ NativeModule, Signal }} from 'craby-modules';
+import {{ NativeModuleRegistry }} from 'craby-modules';
+
+{body}
+
+export default NativeModuleRegistry.getEnforcing<Spec>('{module_name}');
+"
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
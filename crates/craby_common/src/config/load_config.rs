@@ -7,7 +7,10 @@ use log::debug;
 
 use crate::{
     constants::crate_dir,
-    utils::{android::is_valid_android_package_name, cargo::cargo_version, string::flat_case},
+    utils::{
+        android::is_valid_android_package_name, cargo::cargo_version, fs::FilePatterns,
+        string::flat_case,
+    },
 };
 
 use super::{types::Config, CargoManifest, CompleteConfig};
@@ -21,16 +24,27 @@ pub fn load_config(project_root: &Path) -> Result<CompleteConfig, anyhow::Error>
 
     let config = fs::read_to_string(config_path)?;
     let config = toml::from_str::<Config>(&config)?;
-    let source_dir = project_root.join(PathBuf::from(&config.project.source_dir));
+    let source_dirs = config
+        .project
+        .source_dirs()
+        .into_iter()
+        .map(|source_dir| project_root.join(PathBuf::from(source_dir)))
+        .collect();
 
-    validate_config(&config)?;
+    validate_config(project_root, &config)?;
+
+    let spec_patterns = FilePatterns::new(&config.project.include, &config.project.exclude)?;
 
     Ok(CompleteConfig {
         project_root: project_root.to_path_buf(),
         project: config.project,
         android: config.android,
         ios: config.ios,
-        source_dir,
+        generators: config.generators,
+        format: config.format,
+        types: config.types,
+        source_dirs,
+        spec_patterns,
     })
 }
 
@@ -67,14 +81,21 @@ fn validate_manifest(
         )));
     }
 
-    if config.project.source_dir.is_empty() {
+    let source_dirs = config.project.source_dirs();
+    if source_dirs.is_empty() || source_dirs.iter().any(|dir| dir.is_empty()) {
         return Err(anyhow::anyhow!("Source directory is not set"));
     }
 
+    if config.project.include.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Spec file patterns (`include`) are not set"
+        ));
+    }
+
     Ok(config)
 }
 
-fn validate_config(config: &Config) -> Result<(), anyhow::Error> {
+fn validate_config(project_root: &Path, config: &Config) -> Result<(), anyhow::Error> {
     if !is_valid_android_package_name(&config.android.package_name)? {
         anyhow::bail!(format!(
             "Invalid Android package name: {}",
@@ -82,5 +103,24 @@ fn validate_config(config: &Config) -> Result<(), anyhow::Error> {
         ));
     }
 
+    for (i, generator) in config.generators.iter().enumerate() {
+        if generator.command.is_empty() {
+            anyhow::bail!("Generator command is not set: {}", generator.name);
+        }
+
+        if config.generators[..i]
+            .iter()
+            .any(|g| g.name == generator.name)
+        {
+            anyhow::bail!("Duplicate generator name: {}", generator.name);
+        }
+    }
+
+    if let Some(style) = &config.format.clang_format_style {
+        if !project_root.join(style).try_exists()? {
+            anyhow::bail!("clang-format style file not found: {}", style);
+        }
+    }
+
     Ok(())
 }
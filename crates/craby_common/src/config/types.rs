@@ -2,6 +2,11 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    constants::{DEFAULT_SPEC_EXCLUDE, DEFAULT_SPEC_INCLUDE},
+    utils::fs::FilePatterns,
+};
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct CargoManifest {
     pub package: PackageConfig,
@@ -23,12 +28,44 @@ pub struct Config {
     pub project: ProjectConfig,
     pub android: AndroidConfig,
     pub ios: IosConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generators: Vec<GeneratorConfig>,
+    #[serde(default)]
+    pub format: FormatConfig,
+    #[serde(default)]
+    pub types: TypesConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ProjectConfig {
     pub name: String,
-    pub source_dir: String,
+    /// Single source directory (same as `source_dirs = [source_dir]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_dir: Option<String>,
+    /// Directories to scan for the spec files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_dirs: Vec<String>,
+    /// Glob patterns of the spec files (relative to each source directory)
+    #[serde(default = "default_include")]
+    pub include: Vec<String>,
+    /// Glob patterns of the files to ignore (relative to each source directory)
+    #[serde(default = "default_exclude")]
+    pub exclude: Vec<String>,
+}
+
+impl ProjectConfig {
+    /// Returns the source directories (`source_dir` first, then `source_dirs`).
+    pub fn source_dirs(&self) -> Vec<&String> {
+        self.source_dir.iter().chain(&self.source_dirs).collect()
+    }
+}
+
+fn default_include() -> Vec<String> {
+    vec![DEFAULT_SPEC_INCLUDE.to_string()]
+}
+
+fn default_exclude() -> Vec<String> {
+    vec![DEFAULT_SPEC_EXCLUDE.to_string()]
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -42,11 +79,58 @@ pub struct IosConfig {
     pub targets: Option<Vec<String>>,
 }
 
+/// External generator (`[[generators]]`) that receives the schema IR on stdin and prints the files to generate.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GeneratorConfig {
+    pub name: String,
+    /// Executable to run (paths are relative to the project root)
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+/// Formatting of the generated code (`[format]`).
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct FormatConfig {
+    /// Formats the generated Rust code with prettyplease
+    #[serde(default)]
+    pub rust: bool,
+    /// Formats the generated C++ code with clang-format
+    #[serde(default)]
+    pub cxx: bool,
+    /// clang-format style file (relative to the project root, the bundled style is used if not set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clang_format_style: Option<String>,
+}
+
+/// Types of the generated code (`[types]`).
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct TypesConfig {
+    /// Prefixes the type names with the module name (eg. `CalculatorOptions`)
+    #[serde(default)]
+    pub namespace: bool,
+}
+
 #[derive(Debug)]
 pub struct CompleteConfig {
     pub project: ProjectConfig,
     pub project_root: PathBuf,
-    pub source_dir: PathBuf,
+    pub source_dirs: Vec<PathBuf>,
+    pub spec_patterns: FilePatterns,
     pub android: AndroidConfig,
     pub ios: IosConfig,
+    pub generators: Vec<GeneratorConfig>,
+    pub format: FormatConfig,
+    pub types: TypesConfig,
+}
+
+impl CompleteConfig {
+    /// Returns the source directories for display (eg. `src, packages/foo/src`).
+    pub fn source_dirs_display(&self) -> String {
+        self.source_dirs
+            .iter()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
@@ -33,7 +33,11 @@ pub mod android {
 
 pub mod ios {}
 
-pub const SPEC_FILE_PREFIX: &str = "Native";
+/// Default glob pattern of the spec files (eg. `NativeCalculator.ts`)
+pub const DEFAULT_SPEC_INCLUDE: &str = "**/Native*.ts";
+
+/// Default glob pattern of the files that are never collected as spec files
+pub const DEFAULT_SPEC_EXCLUDE: &str = "**/node_modules/**";
 
 pub fn lib_base_name(name: &SanitizedString) -> String {
     flat_case(name.0.as_ref()).to_string()
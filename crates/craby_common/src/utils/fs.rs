@@ -1,5 +1,9 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use log::debug;
 
 pub fn collect_files(
@@ -30,3 +34,78 @@ pub fn collect_files(
 
     Ok(files)
 }
+
+/// Include/exclude glob patterns of the files (relative to the source directory).
+#[derive(Debug)]
+pub struct FilePatterns {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl FilePatterns {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, anyhow::Error> {
+        Ok(FilePatterns {
+            include: build_glob_set(include)?,
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    /// Returns `true` if the relative path matches any include pattern and no exclude pattern.
+    pub fn is_match(&self, path: &Path) -> bool {
+        self.include.is_match(path) && !self.exclude.is_match(path)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, anyhow::Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid glob pattern `{}`: {}", pattern, e))?;
+        builder.add(glob);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Collects the files in the root directory that match the patterns.
+pub fn collect_matched_files(
+    root: &PathBuf,
+    patterns: &FilePatterns,
+) -> Result<Vec<PathBuf>, anyhow::Error> {
+    collect_files(root, &|path: &PathBuf| {
+        path.strip_prefix(root)
+            .is_ok_and(|relative| patterns.is_match(relative))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_patterns() {
+        let patterns = FilePatterns::new(
+            &[
+                "**/Native*.ts".to_string(),
+                "**/*.spec.{mts,tsx}".to_string(),
+            ],
+            &["**/node_modules/**".to_string()],
+        )
+        .unwrap();
+
+        assert!(patterns.is_match(Path::new("NativeCalculator.ts")));
+        assert!(patterns.is_match(Path::new("specs/NativeCalculator.ts")));
+        assert!(patterns.is_match(Path::new("a/b/calculator.spec.mts")));
+        assert!(!patterns.is_match(Path::new("Calculator.ts")));
+        assert!(!patterns.is_match(Path::new("NativeCalculator.tsx")));
+        assert!(!patterns.is_match(Path::new("node_modules/pkg/NativeCalculator.ts")));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(FilePatterns::new(&["src/[".to_string()], &[]).is_err());
+    }
+}
@@ -1,38 +1,64 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, parse_quote, ImplItem, ItemImpl};
+use syn::{parse_macro_input, spanned::Spanned, ItemImpl};
 
+use crate::{module::ModuleArgs, source::CrateSource};
+
+mod method;
+mod module;
+mod source;
+
+/// Implements the `*Spec` trait for the Craby Module.
+///
+/// - Injects `new` and `id` if they are missing (the `Context` field is located by its type)
+/// - Reports missing or extra methods and mismatched signatures against the generated `*Spec` trait
+/// - Collects `#[craby::method(...)]` attributes of the methods
+///
+/// ```rust,ignore
+/// #[craby_module]
+/// impl MyModuleSpec for MyModule { ... }
+///
+/// // Specify the `Context` field explicitly
+/// #[craby_module(ctx = my_field)]
+/// impl MyModuleSpec for MyModule { ... }
+/// ```
 #[proc_macro_attribute]
-pub fn craby_module(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn craby_module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as ModuleArgs);
     let mut input = parse_macro_input!(item as ItemImpl);
 
-    let has_new = input
-        .items
-        .iter()
-        .any(|item| matches!(item, ImplItem::Fn(method) if method.sig.ident == "new"));
-
-    let has_id = input
-        .items
-        .iter()
-        .any(|item| matches!(item, ImplItem::Fn(method) if method.sig.ident == "id"));
-
-    if !has_new {
-        let new_method: ImplItem = parse_quote! {
-            fn new(ctx: Context) -> Self {
-                Self { ctx }
-            }
-        };
-        input.items.push(new_method);
-    }
-
-    if !has_id {
-        let id_method: ImplItem = parse_quote! {
-            fn id(&self) -> usize {
-                self.ctx.id
-            }
-        };
-        input.items.push(id_method);
-    }
-
-    TokenStream::from(quote! { #input })
+    let methods = match method::collect(&mut input) {
+        Ok(methods) => methods,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let source = CrateSource::load(&module::source_names(&input));
+
+    TokenStream::from(module::expand(args, input, source.as_ref(), &methods))
+}
+
+/// Configures the method of the Craby Module.
+///
+/// Must be used on the methods in the `#[craby_module]` impl block.
+///
+/// - `blocking`: Marks the method that blocks the thread (the spec must return a Promise, so it runs on the thread pool)
+/// - `timeout = "5s"`: Rejects the Promise if the method does not finish in time (Promise methods only)
+/// - `trace`: Logs the execution time of the method
+///
+/// ```rust,ignore
+/// #[craby_module]
+/// impl MyModuleSpec for MyModule {
+///     #[craby::method(blocking, timeout = "5s", trace)]
+///     fn download(&mut self, url: String) -> Promise<String> { ... }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn method(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = proc_macro2::TokenStream::from(item);
+
+    syn::Error::new(
+        item.span(),
+        "#[craby::method] must be used in the `#[craby_module]` impl block",
+    )
+    .into_compile_error()
+    .into()
 }
@@ -0,0 +1,214 @@
+use syn::{spanned::Spanned, Attribute, ImplItem, ItemImpl, LitStr, Token};
+
+/// Attributes of the method specified by `#[craby::method(...)]`.
+#[derive(Debug, Default, PartialEq)]
+pub struct MethodAttrs {
+    pub name: String,
+    /// Marks the method that blocks the thread, it must return a Promise to run on the thread pool.
+    pub blocking: bool,
+    /// Rejects the promise if the method does not finish within the duration (milliseconds).
+    pub timeout: Option<u64>,
+    /// Logs the execution time of the method.
+    pub trace: bool,
+}
+
+/// Collects `#[craby::method(...)]` attributes and removes them from the impl block.
+pub fn collect(input: &mut ItemImpl) -> syn::Result<Vec<MethodAttrs>> {
+    let mut methods = vec![];
+
+    for item in input.items.iter_mut() {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+
+        let mut attrs = MethodAttrs {
+            name: method.sig.ident.to_string(),
+            ..Default::default()
+        };
+        let mut found = false;
+        let mut res = Ok(());
+
+        method.attrs.retain(|attr| {
+            if !is_method_attr(attr) {
+                return true;
+            }
+
+            found = true;
+            if res.is_ok() {
+                res = parse_attr(attr, &mut attrs);
+            }
+
+            false
+        });
+
+        res?;
+
+        if found {
+            methods.push(attrs);
+        }
+    }
+
+    Ok(methods)
+}
+
+fn is_method_attr(attr: &Attribute) -> bool {
+    let path = attr.path();
+
+    path.is_ident("method")
+        || (path.segments.len() == 2
+            && path.segments[0].ident == "craby"
+            && path.segments[1].ident == "method")
+}
+
+fn parse_attr(attr: &Attribute, attrs: &mut MethodAttrs) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("blocking") {
+            attrs.blocking = true;
+            Ok(())
+        } else if meta.path.is_ident("trace") {
+            attrs.trace = true;
+            Ok(())
+        } else if meta.path.is_ident("timeout") {
+            meta.input.parse::<Token![=]>()?;
+            let value: LitStr = meta.input.parse()?;
+            match parse_duration(&value.value()) {
+                Some(timeout) if timeout > 0 => {
+                    attrs.timeout = Some(timeout);
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    value.span(),
+                    "invalid duration, expected a value such as `500ms`, `5s` or `1m`",
+                )),
+            }
+        } else {
+            Err(syn::Error::new(
+                meta.path.span(),
+                "unknown method attribute, expected `blocking`, `timeout = \"..\"` or `trace`",
+            ))
+        }
+    })
+}
+
+/// Parses the duration string into milliseconds.
+///
+/// Supported units: `ms`, `s`, `m`
+fn parse_duration(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (num, scale) = if let Some(num) = value.strip_suffix("ms") {
+        (num, 1)
+    } else if let Some(num) = value.strip_suffix('s') {
+        (num, 1_000)
+    } else if let Some(num) = value.strip_suffix('m') {
+        (num, 60_000)
+    } else {
+        return None;
+    };
+
+    num.trim().parse::<u64>().ok()?.checked_mul(scale)
+}
+
+/// Serializes the method attributes as JSON.
+///
+/// ```json
+/// {"multiply":{"blocking":true,"timeout":5000,"trace":false}}
+/// ```
+pub fn to_json(methods: &[MethodAttrs]) -> String {
+    let entries = methods
+        .iter()
+        .map(|attrs| {
+            let timeout = attrs
+                .timeout
+                .map(|timeout| timeout.to_string())
+                .unwrap_or_else(|| "null".to_string());
+
+            format!(
+                "\"{}\":{{\"blocking\":{},\"timeout\":{},\"trace\":{}}}",
+                attrs.name, attrs.blocking, timeout, attrs.trace
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("{{{}}}", entries.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        let mut input: ItemImpl = parse_quote! {
+            impl CalculatorSpec for Calculator {
+                #[craby::method(blocking, timeout = "5s")]
+                fn add(&mut self, a: Number, b: Number) -> Number { a + b }
+
+                #[inline]
+                #[method(trace)]
+                fn subtract(&mut self, a: Number, b: Number) -> Number { a - b }
+
+                fn multiply(&mut self, a: Number, b: Number) -> Number { a * b }
+            }
+        };
+
+        let methods = collect(&mut input).unwrap();
+
+        assert_eq!(
+            methods,
+            vec![
+                MethodAttrs {
+                    name: "add".to_string(),
+                    blocking: true,
+                    timeout: Some(5000),
+                    trace: false,
+                },
+                MethodAttrs {
+                    name: "subtract".to_string(),
+                    blocking: false,
+                    timeout: None,
+                    trace: true,
+                },
+            ]
+        );
+        assert_eq!(
+            to_json(&methods),
+            r#"{"add":{"blocking":true,"timeout":5000,"trace":false},"subtract":{"blocking":false,"timeout":null,"trace":true}}"#
+        );
+
+        // Other attributes are kept
+        let ImplItem::Fn(subtract) = &input.items[1] else {
+            unreachable!()
+        };
+        assert_eq!(subtract.attrs.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_attr() {
+        let mut input: ItemImpl = parse_quote! {
+            impl CalculatorSpec for Calculator {
+                #[craby::method(timeout = "5 seconds")]
+                fn add(&mut self, a: Number, b: Number) -> Number { a + b }
+            }
+        };
+        assert!(collect(&mut input).is_err());
+
+        let mut input: ItemImpl = parse_quote! {
+            impl CalculatorSpec for Calculator {
+                #[craby::method(unknown)]
+                fn add(&mut self, a: Number, b: Number) -> Number { a + b }
+            }
+        };
+        assert!(collect(&mut input).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Some(500));
+        assert_eq!(parse_duration("5s"), Some(5_000));
+        assert_eq!(parse_duration("1m"), Some(60_000));
+        assert_eq!(parse_duration("5"), None);
+        assert_eq!(parse_duration("1.5s"), None);
+    }
+}
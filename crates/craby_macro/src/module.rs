@@ -0,0 +1,625 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned,
+    FnArg, GenericArgument, Ident, ImplItem, ItemImpl, ItemStruct, ItemTrait, Member,
+    PathArguments, ReturnType, Signature, Token, TraitItem, Type,
+};
+
+use crate::{
+    method::{self, MethodAttrs},
+    source::CrateSource,
+};
+
+/// Methods that are injected by the macro if they are missing.
+const INJECTED_METHODS: [&str; 2] = ["new", "id"];
+
+/// Arguments of `#[craby_module(...)]`.
+///
+/// ```rust,ignore
+/// #[craby_module(ctx = my_field)]
+/// ```
+pub struct ModuleArgs {
+    ctx: Option<Member>,
+}
+
+impl Parse for ModuleArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(ModuleArgs { ctx: None });
+        }
+
+        let key: Ident = input.parse()?;
+        if key != "ctx" {
+            return Err(syn::Error::new(
+                key.span(),
+                format!("unknown argument `{key}`, expected `ctx = <field>`"),
+            ));
+        }
+
+        input.parse::<Token![=]>()?;
+        let ctx: Member = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument"));
+        }
+
+        Ok(ModuleArgs { ctx: Some(ctx) })
+    }
+}
+
+/// Expands `#[craby_module]` on the `impl <Module>Spec for <Module>` block.
+///
+/// - Injects `new` and `id` if they are missing
+/// - Validates the methods against the generated `*Spec` trait (if found)
+/// - Exports the method attributes as `CRABY_METHOD_METADATA`
+pub fn expand(
+    args: ModuleArgs,
+    mut input: ItemImpl,
+    source: Option<&CrateSource>,
+    methods: &[MethodAttrs],
+) -> TokenStream {
+    let trait_path = match &input.trait_ {
+        Some((_, path, _)) => path.clone(),
+        None => {
+            return syn::Error::new(
+                input.self_ty.span(),
+                "#[craby_module] must be applied to `impl <Module>Spec for <Module>`",
+            )
+            .into_compile_error();
+        }
+    };
+
+    let trait_name = trait_path
+        .segments
+        .last()
+        .map(|seg| seg.ident.to_string())
+        .unwrap_or_default();
+    let module_struct = type_name(&input.self_ty)
+        .and_then(|name| source.and_then(|source| source.find_struct(&name)));
+    let spec_trait = source.and_then(|source| source.find_trait(&trait_name));
+
+    let mut errors = vec![];
+    let ctx = match resolve_ctx_field(&args, module_struct, &input.self_ty) {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            errors.push(e);
+            Member::Named(format_ident!("ctx"))
+        }
+    };
+
+    if let Some(spec_trait) = spec_trait {
+        errors.extend(validate(&input, &trait_path, spec_trait));
+    }
+
+    errors.extend(validate_method_attrs(&input, methods));
+
+    if !has_method(&input, "new") {
+        input.items.push(new_method(&ctx, module_struct));
+    }
+
+    if !has_method(&input, "id") {
+        input.items.push(parse_quote! {
+            fn id(&self) -> usize {
+                self.#ctx.id
+            }
+        });
+    }
+
+    let errors = errors.iter().map(syn::Error::to_compile_error);
+    let self_ty = &input.self_ty;
+    let metadata = method::to_json(methods);
+
+    quote! {
+        #input
+
+        impl #self_ty {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub const CRABY_METHOD_METADATA: &'static str = #metadata;
+        }
+
+        #(#errors)*
+    }
+}
+
+/// Names of the items looked up from the source files (the `*Spec` trait and the module struct).
+pub fn source_names(input: &ItemImpl) -> Vec<String> {
+    let trait_name = input
+        .trait_
+        .as_ref()
+        .and_then(|(_, path, _)| path.segments.last())
+        .map(|seg| seg.ident.to_string());
+
+    trait_name
+        .into_iter()
+        .chain(type_name(&input.self_ty))
+        .collect()
+}
+
+/// Finds the field of the `Context` type in the module struct.
+///
+/// Falls back to the `ctx` field if the struct definition is not found.
+fn resolve_ctx_field(
+    args: &ModuleArgs,
+    module_struct: Option<&ItemStruct>,
+    self_ty: &Type,
+) -> syn::Result<Member> {
+    let fields = module_struct.map(|item| field_members(item).collect::<Vec<_>>());
+
+    if let Some(ctx) = &args.ctx {
+        if let Some(fields) = &fields {
+            match fields.iter().find(|(member, _)| member == ctx) {
+                Some((_, ty)) if !is_context_type(ty) => {
+                    return Err(syn::Error::new(
+                        ctx.span(),
+                        format!(
+                            "field `{}` of `{}` must be of type `Context`",
+                            ctx.to_token_stream(),
+                            type_name(self_ty).unwrap_or_default()
+                        ),
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    return Err(syn::Error::new(
+                        ctx.span(),
+                        format!(
+                            "`{}` has no field `{}`",
+                            type_name(self_ty).unwrap_or_default(),
+                            ctx.to_token_stream()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        return Ok(ctx.clone());
+    }
+
+    let Some(fields) = fields else {
+        return Ok(Member::Named(format_ident!("ctx")));
+    };
+
+    let mut ctx_fields = fields
+        .into_iter()
+        .filter(|(_, ty)| is_context_type(ty))
+        .map(|(member, _)| member);
+
+    match (ctx_fields.next(), ctx_fields.next()) {
+        (Some(ctx), None) => Ok(ctx),
+        (None, _) => Err(syn::Error::new(
+            self_ty.span(),
+            format!(
+                "`{}` must have a field of type `Context`",
+                type_name(self_ty).unwrap_or_default()
+            ),
+        )),
+        (Some(_), Some(_)) => Err(syn::Error::new(
+            self_ty.span(),
+            format!(
+                "`{}` has multiple fields of type `Context`, specify one with `#[craby_module(ctx = <field>)]`",
+                type_name(self_ty).unwrap_or_default()
+            ),
+        )),
+    }
+}
+
+/// Validates the methods of the impl block against the `*Spec` trait.
+fn validate(input: &ItemImpl, trait_path: &syn::Path, spec_trait: &ItemTrait) -> Vec<syn::Error> {
+    let trait_name = &spec_trait.ident;
+    let trait_methods = spec_trait
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Fn(method) => Some(method),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut errors = vec![];
+
+    for item in &input.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+
+        match trait_methods
+            .iter()
+            .find(|trait_method| trait_method.sig.ident == method.sig.ident)
+        {
+            Some(trait_method) => match is_same_signature(&method.sig, &trait_method.sig) {
+                Ok(true) => {}
+                Ok(false) => errors.push(syn::Error::new(
+                    method.sig.span(),
+                    format!(
+                        "method `{}` does not match `{trait_name}`, expected `{}`",
+                        method.sig.ident,
+                        display_sig(&trait_method.sig)
+                    ),
+                )),
+                Err(e) => errors.push(e),
+            },
+            None => errors.push(syn::Error::new(
+                method.sig.ident.span(),
+                format!(
+                    "method `{}` is not a member of `{trait_name}`",
+                    method.sig.ident
+                ),
+            )),
+        }
+    }
+
+    for trait_method in trait_methods {
+        let name = trait_method.sig.ident.to_string();
+        let is_required =
+            trait_method.default.is_none() && !INJECTED_METHODS.contains(&name.as_str());
+
+        if is_required && !has_method(input, &name) {
+            errors.push(syn::Error::new(
+                trait_path.span(),
+                format!(
+                    "missing method `{name}` of `{trait_name}`, expected `{}`",
+                    display_sig(&trait_method.sig)
+                ),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Validates that `blocking` and `timeout` are only used on the Promise methods.
+///
+/// The generated C++ code can only settle the Promise declared in the spec.
+fn validate_method_attrs(input: &ItemImpl, methods: &[MethodAttrs]) -> Vec<syn::Error> {
+    methods
+        .iter()
+        .filter_map(|attrs| {
+            let sig = input.items.iter().find_map(|item| match item {
+                ImplItem::Fn(method) if method.sig.ident == attrs.name => Some(&method.sig),
+                _ => None,
+            })?;
+
+            let is_promise = match &sig.output {
+                ReturnType::Type(_, ty) => {
+                    type_name(ty).is_some_and(|name| name == "Promise" || name == "Result")
+                }
+                ReturnType::Default => false,
+            };
+
+            if is_promise {
+                None
+            } else if attrs.blocking {
+                Some(syn::Error::new(
+                    sig.span(),
+                    format!("`blocking` method `{}` must return `Promise<T>`", attrs.name),
+                ))
+            } else if attrs.timeout.is_some() {
+                Some(syn::Error::new(
+                    sig.span(),
+                    format!(
+                        "`timeout` is only supported for Promise methods, `{}` must return `Promise<T>`",
+                        attrs.name
+                    ),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Generates the `new` method that initializes the `Context` field
+/// and the other fields with `Default::default()`.
+fn new_method(ctx: &Member, module_struct: Option<&ItemStruct>) -> ImplItem {
+    let inits = match module_struct {
+        Some(item) => field_members(item)
+            .map(|(member, _)| {
+                if member == *ctx {
+                    quote! { #member: ctx }
+                } else {
+                    quote! { #member: Default::default() }
+                }
+            })
+            .collect::<Vec<_>>(),
+        None => vec![quote! { #ctx: ctx }],
+    };
+
+    parse_quote! {
+        fn new(ctx: Context) -> Self {
+            Self { #(#inits),* }
+        }
+    }
+}
+
+fn field_members(item: &ItemStruct) -> impl Iterator<Item = (Member, &Type)> {
+    item.fields.iter().enumerate().map(|(idx, field)| {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(idx.into()),
+        };
+        (member, &field.ty)
+    })
+}
+
+fn has_method(input: &ItemImpl, name: &str) -> bool {
+    input
+        .items
+        .iter()
+        .any(|item| matches!(item, ImplItem::Fn(method) if method.sig.ident == name))
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|seg| seg.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn is_context_type(ty: &Type) -> bool {
+    type_name(ty).is_some_and(|name| name == "Context")
+}
+
+fn is_same_signature(a: &Signature, b: &Signature) -> syn::Result<bool> {
+    let params = |sig: &Signature| {
+        sig.inputs
+            .iter()
+            .map(|arg| match arg {
+                FnArg::Receiver(receiver) => Ok(match &receiver.reference {
+                    Some(_) if receiver.mutability.is_some() => "&mut self".to_string(),
+                    Some(_) => "&self".to_string(),
+                    None => "self".to_string(),
+                }),
+                FnArg::Typed(pat) => normalize_type(&pat.ty),
+            })
+            .collect::<syn::Result<Vec<_>>>()
+    };
+
+    let ret = |sig: &Signature| match &sig.output {
+        ReturnType::Default => Ok("()".to_string()),
+        ReturnType::Type(_, ty) => normalize_type(ty),
+    };
+
+    Ok(params(a)? == params(b)? && ret(a)? == ret(b)?)
+}
+
+/// Normalizes the type to compare the Craby type aliases with the Rust types.
+///
+/// eg. `Number` and `f64`, `Promise<T>` and `Result<T, anyhow::Error>`, `Nullable<T>` and `Option<T>`
+///
+/// Returns an error if `Result` has an error type other than `anyhow::Error`.
+fn normalize_type(ty: &Type) -> syn::Result<String> {
+    Ok(match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let Some(seg) = path.path.segments.last() else {
+                return Ok(pretty(ty.to_token_stream()));
+            };
+
+            let ident = seg.ident.to_string();
+            let args = match &seg.arguments {
+                PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        GenericArgument::Type(ty) => normalize_type(ty),
+                        arg => Ok(pretty(arg.to_token_stream())),
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?,
+                _ => vec![],
+            };
+
+            if ident == "Result" && !is_anyhow_result(&seg.arguments) {
+                return Err(syn::Error::new(
+                    ty.span(),
+                    "expected `Result<T>` or `Result<T, anyhow::Error>`",
+                ));
+            }
+
+            match (ident.as_str(), args.as_slice()) {
+                ("Number", []) => "f64".to_string(),
+                ("Boolean", []) => "bool".to_string(),
+                ("Void", []) => "()".to_string(),
+                ("ArrayBuffer", []) => "Vec<u8>".to_string(),
+                ("Array", [ty]) => format!("Vec<{ty}>"),
+                ("Nullable", [ty]) => format!("Option<{ty}>"),
+                ("NativeObject", [ty]) => format!("Box<{ty}>"),
+                ("Promise", [ty]) | ("Result", [ty] | [ty, _]) => format!("Result<{ty}>"),
+                (_, []) => ident,
+                _ => format!("{ident}<{}>", args.join(", ")),
+            }
+        }
+        Type::Tuple(tuple) if tuple.elems.is_empty() => "()".to_string(),
+        Type::Reference(reference) => format!(
+            "&{}{}",
+            if reference.mutability.is_some() {
+                "mut "
+            } else {
+                ""
+            },
+            normalize_type(&reference.elem)?
+        ),
+        Type::Paren(paren) => normalize_type(&paren.elem)?,
+        Type::Group(group) => normalize_type(&group.elem)?,
+        _ => pretty(ty.to_token_stream()),
+    })
+}
+
+/// Whether the arguments of `Result` are `<T>` or `<T, anyhow::Error>`.
+fn is_anyhow_result(args: &PathArguments) -> bool {
+    let PathArguments::AngleBracketed(args) = args else {
+        return false;
+    };
+
+    match args.args.iter().collect::<Vec<_>>().as_slice() {
+        [GenericArgument::Type(_)] => true,
+        [GenericArgument::Type(_), GenericArgument::Type(Type::Path(err))] => {
+            let segments = err
+                .path
+                .segments
+                .iter()
+                .map(|seg| seg.ident.to_string())
+                .collect::<Vec<_>>();
+            err.qself.is_none() && segments == ["anyhow", "Error"]
+        }
+        _ => false,
+    }
+}
+
+fn display_sig(sig: &Signature) -> String {
+    pretty(sig.to_token_stream())
+}
+
+fn pretty(tokens: TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace(" :", ":")
+        .replace("& ", "&")
+        .replace("( ", "(")
+        .replace(" (", "(")
+        .replace(" )", ")")
+        .replace(")->", ") ->")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> CrateSource {
+        CrateSource::from_files(vec![parse_quote! {
+            pub trait CalculatorSpec {
+                fn new(ctx: Context) -> Self;
+                fn id(&self) -> usize;
+                fn add(&mut self, a: Number, b: Number) -> Number;
+                fn divide(&mut self, a: Number, b: Number) -> Promise<Number>;
+            }
+
+            pub struct Calculator {
+                count: usize,
+                my_ctx: craby::prelude::Context,
+            }
+        }])
+    }
+
+    fn expand_impl(args: ModuleArgs, input: ItemImpl) -> String {
+        expand(args, input, Some(&source()), &[]).to_string()
+    }
+
+    #[test]
+    fn test_validate_method_attrs() {
+        let mut input: ItemImpl = parse_quote! {
+            impl CalculatorSpec for Calculator {
+                #[craby::method(blocking)]
+                fn add(&mut self, a: Number, b: Number) -> Number { a + b }
+                #[craby::method(blocking, timeout = "5s")]
+                fn divide(&mut self, a: Number, b: Number) -> Promise<Number> { Ok(a / b) }
+            }
+        };
+        let methods = method::collect(&mut input).unwrap();
+        let output = expand(
+            ModuleArgs { ctx: None },
+            input.clone(),
+            Some(&source()),
+            &methods,
+        )
+        .to_string();
+
+        assert!(output.contains("`blocking` method `add` must return `Promise<T>`"));
+        assert!(!output.contains("`divide`"));
+
+        let mut input: ItemImpl = parse_quote! {
+            impl CalculatorSpec for Calculator {
+                #[craby::method(timeout = "5s")]
+                fn add(&mut self, a: Number, b: Number) -> Number { a + b }
+                fn divide(&mut self, a: Number, b: Number) -> Promise<Number> { Ok(a / b) }
+            }
+        };
+        let methods = method::collect(&mut input).unwrap();
+        let output = expand(ModuleArgs { ctx: None }, input, Some(&source()), &methods).to_string();
+
+        assert!(output.contains("`timeout` is only supported for Promise methods"));
+    }
+
+    #[test]
+    fn test_inject_methods() {
+        let output = expand_impl(
+            ModuleArgs { ctx: None },
+            parse_quote! {
+                impl CalculatorSpec for Calculator {
+                    fn add(&mut self, a: f64, b: Number) -> Number { a + b }
+                    fn divide(&mut self, a: Number, b: Number) -> Result<f64, anyhow::Error> { Ok(a / b) }
+                }
+            },
+        );
+
+        assert!(!output.contains("compile_error"), "{output}");
+        assert!(output
+            .contains(&quote! { Self { count: Default::default(), my_ctx: ctx } }.to_string()));
+        assert!(output.contains(&quote! { self.my_ctx.id }.to_string()));
+    }
+
+    #[test]
+    fn test_validate_methods() {
+        let output = expand_impl(
+            ModuleArgs { ctx: None },
+            parse_quote! {
+                impl CalculatorSpec for Calculator {
+                    fn add(&self, a: Number, b: Number) -> Number { a + b }
+                    fn subtract(&mut self, a: Number, b: Number) -> Number { a - b }
+                }
+            },
+        );
+
+        assert!(output.contains(
+            "method `add` does not match `CalculatorSpec`, expected `fn add(&mut self, a: Number, b: Number) -> Number`"
+        ));
+        assert!(output.contains("method `subtract` is not a member of `CalculatorSpec`"));
+        assert!(output.contains(
+            "missing method `divide` of `CalculatorSpec`, expected `fn divide(&mut self, a: Number, b: Number) -> Promise<Number>`"
+        ));
+    }
+
+    #[test]
+    fn test_ctx_override() {
+        let input: ItemImpl = parse_quote! {
+            impl CalculatorSpec for Calculator {
+                fn add(&mut self, a: Number, b: Number) -> Number { a + b }
+                fn divide(&mut self, a: Number, b: Number) -> Promise<Number> { Ok(a / b) }
+            }
+        };
+
+        let output = expand_impl(syn::parse_quote!(ctx = my_ctx), input.clone());
+        assert!(!output.contains("compile_error"), "{output}");
+        assert!(output.contains(&quote! { self.my_ctx.id }.to_string()));
+
+        let output = expand_impl(syn::parse_quote!(ctx = count), input.clone());
+        assert!(output.contains("field `count` of `Calculator` must be of type `Context`"));
+
+        let output = expand_impl(syn::parse_quote!(ctx = unknown), input);
+        assert!(output.contains("`Calculator` has no field `unknown`"));
+    }
+
+    #[test]
+    fn test_result_error_type() {
+        let output = expand_impl(
+            ModuleArgs { ctx: None },
+            parse_quote! {
+                impl CalculatorSpec for Calculator {
+                    fn add(&mut self, a: Number, b: Number) -> Number { a + b }
+                    fn divide(&mut self, a: Number, b: Number) -> Result<f64, String> { Ok(a / b) }
+                }
+            },
+        );
+
+        assert!(output.contains("expected `Result<T>` or `Result<T, anyhow::Error>`"));
+        assert!(!output.contains("does not match"));
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+    time::SystemTime,
+};
+
+use syn::{Item, ItemStruct, ItemTrait};
+
+/// Parsed source files of the crate that is being compiled.
+///
+/// Attribute macros can only see the item they are attached to,
+/// so the module struct and the generated `*Spec` trait are looked up from the source files.
+pub struct CrateSource {
+    files: Vec<syn::File>,
+}
+
+impl CrateSource {
+    /// Loads the source files under `$CARGO_MANIFEST_DIR/src` that mention any of the given names.
+    ///
+    /// The file contents are cached per process (until modified),
+    /// and only the files that may declare the items are parsed.
+    /// Files that cannot be read or parsed are ignored.
+    pub fn load(names: &[String]) -> Option<Self> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").ok()?;
+        let mut paths = vec![];
+        collect_files(&Path::new(&manifest_dir).join("src"), &mut paths);
+
+        Some(CrateSource {
+            files: paths
+                .into_iter()
+                .filter_map(|path| read_cached(&path))
+                .filter(|src| names.iter().any(|name| src.contains(name.as_str())))
+                .filter_map(|src| syn::parse_file(&src).ok())
+                .collect(),
+        })
+    }
+
+    #[cfg(test)]
+    pub fn from_files(files: Vec<syn::File>) -> Self {
+        CrateSource { files }
+    }
+
+    pub fn find_struct(&self, name: &str) -> Option<&ItemStruct> {
+        self.files.iter().find_map(|file| {
+            find_item(&file.items, &|item| match item {
+                Item::Struct(item) if item.ident == name => Some(item),
+                _ => None,
+            })
+        })
+    }
+
+    pub fn find_trait(&self, name: &str) -> Option<&ItemTrait> {
+        self.files.iter().find_map(|file| {
+            find_item(&file.items, &|item| match item {
+                Item::Trait(item) if item.ident == name => Some(item),
+                _ => None,
+            })
+        })
+    }
+}
+
+fn find_item<'a, T>(items: &'a [Item], f: &impl Fn(&'a Item) -> Option<&'a T>) -> Option<&'a T> {
+    items.iter().find_map(|item| match item {
+        Item::Mod(item_mod) => item_mod
+            .content
+            .as_ref()
+            .and_then(|(_, items)| find_item(items, f)),
+        item => f(item),
+    })
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Reads the source file, reusing the content read by the previous expansions if it is not modified.
+///
+/// Only the contents are cached because the parsed items hold the spans of the expansion that parsed them.
+fn read_cached(path: &Path) -> Option<Arc<str>> {
+    type Cache = Mutex<HashMap<PathBuf, (SystemTime, Arc<str>)>>;
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    let mut cache = CACHE.get_or_init(Default::default).lock().unwrap();
+
+    if let Some((cached_at, src)) = cache.get(path) {
+        if *cached_at == modified {
+            return Some(src.clone());
+        }
+    }
+
+    let src = Arc::<str>::from(fs::read_to_string(path).ok()?);
+    cache.insert(path.to_path_buf(), (modified, src.clone()));
+
+    Some(src)
+}
@@ -12,15 +12,32 @@ export interface CleanOptions {
   projectRoot: string
 }
 
+/**
+ * Runs the codegen command synchronously.
+ *
+ * With `watch: true`, it does not return until the watcher fails, so the Node.js event loop is blocked
+ * (the CLI process only runs this command).
+ */
 export declare function codegen(opts: CodegenOptions): void
 
 export interface CodegenOptions {
   projectRoot: string
   overwrite: boolean
+  fromIr?: string
+  check?: boolean
+  watch?: boolean
 }
 
 export declare function debug(message: string): void
 
+export declare function diff(opts: DiffOptions): void
+
+export interface DiffOptions {
+  projectRoot: string
+  baseline: string
+  strict?: boolean
+}
+
 export declare function doctor(opts: DoctorOptions): void
 
 export interface DoctorOptions {
@@ -44,6 +61,7 @@ export declare function show(opts: ShowOptions): void
 
 export interface ShowOptions {
   projectRoot: string
+  format?: string
 }
 
 export declare function trace(message: string): void
@@ -45,13 +45,23 @@ pub fn init(opts: InitOptions) -> napi::Result<()> {
 pub struct CodegenOptions {
     pub project_root: String,
     pub overwrite: bool,
+    pub from_ir: Option<String>,
+    pub check: Option<bool>,
+    pub watch: Option<bool>,
 }
 
+/// Runs the codegen command synchronously.
+///
+/// With `watch: true`, it does not return until the watcher fails, so the Node.js event loop is blocked
+/// (the CLI process only runs this command).
 #[napi]
 pub fn codegen(opts: CodegenOptions) -> napi::Result<()> {
     let opts = craby_cli::commands::codegen::CodegenOptions {
         project_root: opts.project_root.into(),
         overwrite: opts.overwrite,
+        from_ir: opts.from_ir.map(Into::into),
+        check: opts.check.unwrap_or(false),
+        watch: opts.watch.unwrap_or(false),
     };
 
     match craby_cli::commands::codegen::perform(opts) {
@@ -86,12 +96,20 @@ pub fn build(opts: BuildOptions) -> napi::Result<()> {
 #[napi(object)]
 pub struct ShowOptions {
     pub project_root: String,
+    pub format: Option<String>,
 }
 
 #[napi]
 pub fn show(opts: ShowOptions) -> napi::Result<()> {
+    let format = match opts.format {
+        Some(format) => format
+            .parse::<craby_cli::commands::show::ShowFormat>()
+            .map_err(|e| napi::Error::new(napi::Status::InvalidArg, e.to_string()))?,
+        None => Default::default(),
+    };
     let opts = craby_cli::commands::show::ShowOptions {
         project_root: opts.project_root.into(),
+        format,
     };
 
     match craby_cli::commands::show::perform(opts) {
@@ -103,6 +121,30 @@ pub fn show(opts: ShowOptions) -> napi::Result<()> {
     }
 }
 
+#[napi(object)]
+pub struct DiffOptions {
+    pub project_root: String,
+    pub baseline: String,
+    pub strict: Option<bool>,
+}
+
+#[napi]
+pub fn diff(opts: DiffOptions) -> napi::Result<()> {
+    let opts = craby_cli::commands::diff::DiffOptions {
+        project_root: opts.project_root.into(),
+        baseline: opts.baseline,
+        strict: opts.strict.unwrap_or(false),
+    };
+
+    match craby_cli::commands::diff::perform(opts) {
+        Err(e) => Err(napi::Error::new(
+            napi::Status::GenericFailure,
+            e.to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
 #[napi(object)]
 pub struct DoctorOptions {
     pub project_root: String,
@@ -2,6 +2,11 @@ import { Platform, TurboModuleRegistry } from 'react-native';
 
 type NativeModule = {};
 
+/**
+ * Rust object returned from the native module methods (exposed as a host object).
+ */
+type NativeObject = {};
+
 type Signal<T = void> = (handler: (data: T) => void) => () => void;
 
 /**
@@ -43,4 +48,4 @@ export const NativeModuleRegistry: NativeModuleRegistry = {
   },
 };
 
-export type { NativeModule, Signal };
+export type { NativeModule, NativeObject, Signal };
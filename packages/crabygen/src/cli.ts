@@ -3,6 +3,7 @@ import { version } from '../package.json';
 import { command as buildCommand } from './commands/build';
 import { command as cleanCommand } from './commands/clean';
 import { command as codegenCommand } from './commands/codegen';
+import { command as diffCommand } from './commands/diff';
 import { command as doctorCommand } from './commands/doctor';
 import { command as initCommand } from './commands/init';
 import { command as showCommand } from './commands/show';
@@ -14,6 +15,7 @@ export function run(baseCommand: string) {
   cli.addCommand(initCommand);
   cli.addCommand(buildCommand);
   cli.addCommand(showCommand);
+  cli.addCommand(diffCommand);
   cli.addCommand(doctorCommand);
   cli.addCommand(cleanCommand);
 
@@ -3,11 +3,23 @@ import { codegen } from '@craby/cli-bindings';
 import { withVerbose } from '../utils/command';
 import { withErrorHandler } from '../utils/errors';
 
-export const runCodegen = withErrorHandler((overwrite: boolean) => codegen({ projectRoot: process.cwd(), overwrite }));
+interface RunCodegenOptions {
+  overwrite: boolean;
+  fromIr?: string;
+  check?: boolean;
+  watch?: boolean;
+}
+
+export const runCodegen = withErrorHandler((options: RunCodegenOptions) =>
+  codegen({ projectRoot: process.cwd(), ...options }),
+);
 
 export const command = withVerbose(
   new Command()
     .name('codegen')
     .option('--no-overwrite', 'Do not overwrite existing files')
-    .action((options) => runCodegen(options.overwrite)),
+    .option('--from-ir <file>', 'Generate from the schema IR file (`craby show --format json`)')
+    .option('--check', 'Check that the generated files are up to date without writing them')
+    .option('--watch', 'Regenerate the files whenever the spec files change')
+    .action(({ overwrite, fromIr, check, watch }) => runCodegen({ overwrite, fromIr, check, watch })),
 );
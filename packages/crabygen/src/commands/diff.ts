@@ -0,0 +1,14 @@
+import { Command } from '@commander-js/extra-typings';
+import { diff } from '@craby/cli-bindings';
+import { withVerbose } from '../utils/command';
+import { withErrorHandler } from '../utils/errors';
+
+export const command = withVerbose(
+  new Command()
+    .name('diff')
+    .argument('<baseline>', 'Schema IR file or git revision to compare with')
+    .option('--strict', 'Fail on the changes that break the Rust implementations too')
+    .action((baseline, { strict }) =>
+      withErrorHandler(diff.bind(null, { projectRoot: process.cwd(), baseline, strict }))(),
+    ),
+);
@@ -1,8 +1,11 @@
-import { Command } from '@commander-js/extra-typings';
+import { Command, Option } from '@commander-js/extra-typings';
 import { show } from '@craby/cli-bindings';
 import { withVerbose } from '../utils/command';
 import { withErrorHandler } from '../utils/errors';
 
 export const command = withVerbose(
-  new Command().name('show').action(withErrorHandler(show.bind(null, { projectRoot: process.cwd() }))),
+  new Command()
+    .name('show')
+    .addOption(new Option('--format <format>', 'Output format').choices(['text', 'json'] as const).default('text'))
+    .action((options) => withErrorHandler(show.bind(null, { projectRoot: process.cwd(), format: options.format }))()),
 );